@@ -0,0 +1,54 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+/// a finite color domain of a colored (symmetric) Petri net
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ColorDomainKind {
+    /// an ordered set of named constants
+    /// the order is cyclic when considering the successor or predecessor of a constant
+    Enumeration(Vec<String>),
+    /// the cartesian product of several enumeration domains (given by their ids)
+    Product(Vec<usize>)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ColorDomain {
+    pub name : String,
+    pub kind : ColorDomainKind
+}
+
+impl ColorDomain {
+    pub fn new(name: String, kind: ColorDomainKind) -> Self {
+        Self { name, kind }
+    }
+}
+
+
+/// a color i.e., for each component of its domain, the index of a constant of that component
+/// (the color of an enumeration domain has a single component)
+#[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+pub struct Color {
+    pub components : Vec<usize>
+}
+
+impl Color {
+    pub fn new(components: Vec<usize>) -> Self {
+        Self { components }
+    }
+}
@@ -0,0 +1,253 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::colored::domain::Color;
+use crate::colored::net::ColoredPetriNet;
+
+
+
+
+/// an assignment of constants to variables
+/// maps variable ids to the index of a constant of the domain of the variable
+#[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord, Default)]
+pub struct ColorBinding {
+    pub values : BTreeMap<usize,usize>
+}
+
+impl ColorBinding {
+    pub fn new(values: BTreeMap<usize,usize>) -> Self {
+        Self { values }
+    }
+}
+
+
+/// a term which evaluates to a color under a binding of its variables
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ColorTerm {
+    /// the constant of a certain index in an enumeration domain
+    Constant{domain_id:usize,constant_id:usize},
+    /// a variable, given by its id, whose domain must be an enumeration domain
+    Variable(usize),
+    /// the next constant in the (cyclic) order of the domain
+    Successor(Box<ColorTerm>),
+    /// the previous constant in the (cyclic) order of the domain
+    Predecessor(Box<ColorTerm>),
+    /// a color of a product domain
+    Tuple(Vec<ColorTerm>)
+}
+
+impl ColorTerm {
+
+    /// for each component of the resulting color, the id of its enumeration domain and the index of the constant
+    fn evaluate_components(&self, net : &ColoredPetriNet, binding : &ColorBinding) -> Vec<(usize,usize)> {
+        match self {
+            ColorTerm::Constant { domain_id, constant_id } => {
+                vec![(*domain_id,*constant_id)]
+            },
+            ColorTerm::Variable(var_id) => {
+                let domain_id = net.variables.get(*var_id).unwrap().domain_id;
+                vec![(domain_id,*binding.values.get(var_id).unwrap())]
+            },
+            ColorTerm::Successor(sub_term) => {
+                sub_term.evaluate_components(net, binding).into_iter()
+                    .map(|(domain_id,constant_id)| {
+                        let domain_size = net.get_domain_size(domain_id);
+                        (domain_id,(constant_id + 1) % domain_size)
+                    }).collect()
+            },
+            ColorTerm::Predecessor(sub_term) => {
+                sub_term.evaluate_components(net, binding).into_iter()
+                    .map(|(domain_id,constant_id)| {
+                        let domain_size = net.get_domain_size(domain_id);
+                        (domain_id,(constant_id + domain_size - 1) % domain_size)
+                    }).collect()
+            },
+            ColorTerm::Tuple(sub_terms) => {
+                sub_terms.iter().flat_map(|sub_term| sub_term.evaluate_components(net, binding)).collect()
+            }
+        }
+    }
+
+    pub fn evaluate(&self, net : &ColoredPetriNet, binding : &ColorBinding) -> Color {
+        Color::new(
+            self.evaluate_components(net, binding).into_iter().map(|(_,constant_id)| constant_id).collect()
+        )
+    }
+
+    pub fn collect_variables(&self, variables : &mut BTreeSet<usize>) {
+        match self {
+            ColorTerm::Constant { .. } => {},
+            ColorTerm::Variable(var_id) => {
+                variables.insert(*var_id);
+            },
+            ColorTerm::Successor(sub_term) | ColorTerm::Predecessor(sub_term) => {
+                sub_term.collect_variables(variables);
+            },
+            ColorTerm::Tuple(sub_terms) => {
+                for sub_term in sub_terms {
+                    sub_term.collect_variables(variables);
+                }
+            }
+        }
+    }
+}
+
+
+/// an arc expression, which evaluates to a multiset of colors under a binding of its variables
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ColoredArcExpression {
+    /// a single token of a certain color
+    Single(ColorTerm),
+    /// one token of each color of a domain
+    All(usize),
+    /// a multiset whose multiplicities are multiplied by a certain number
+    Scaled(u32,Box<ColoredArcExpression>),
+    Add(Vec<ColoredArcExpression>),
+    /// multiset difference, in which multiplicities cannot go below zero
+    Subtract(Box<ColoredArcExpression>,Box<ColoredArcExpression>)
+}
+
+impl ColoredArcExpression {
+
+    /// returns None if some multiplicity does not fit in a u32
+    pub fn evaluate(&self, net : &ColoredPetriNet, binding : &ColorBinding) -> Option<BTreeMap<Color,u32>> {
+        match self {
+            ColoredArcExpression::Single(term) => {
+                Some(BTreeMap::from([(term.evaluate(net, binding),1)]))
+            },
+            ColoredArcExpression::All(domain_id) => {
+                Some(net.get_domain_colors(*domain_id).into_iter().map(|color| (color,1)).collect())
+            },
+            ColoredArcExpression::Scaled(factor, sub_expr) => {
+                let mut multiset = sub_expr.evaluate(net, binding)?;
+                for multiplicity in multiset.values_mut() {
+                    *multiplicity = multiplicity.checked_mul(*factor)?;
+                }
+                multiset.retain(|_,multiplicity| *multiplicity > 0);
+                Some(multiset)
+            },
+            ColoredArcExpression::Add(sub_exprs) => {
+                let mut multiset = BTreeMap::new();
+                for sub_expr in sub_exprs {
+                    for (color,multiplicity) in sub_expr.evaluate(net, binding)? {
+                        let sum : &mut u32 = multiset.entry(color).or_insert(0);
+                        *sum = sum.checked_add(multiplicity)?;
+                    }
+                }
+                Some(multiset)
+            },
+            ColoredArcExpression::Subtract(left, right) => {
+                let mut multiset = left.evaluate(net, binding)?;
+                for (color,multiplicity) in right.evaluate(net, binding)? {
+                    if let Some(left_multiplicity) = multiset.get_mut(&color) {
+                        *left_multiplicity = left_multiplicity.saturating_sub(multiplicity);
+                    }
+                }
+                multiset.retain(|_,multiplicity| *multiplicity > 0);
+                Some(multiset)
+            }
+        }
+    }
+
+    pub fn collect_variables(&self, variables : &mut BTreeSet<usize>) {
+        match self {
+            ColoredArcExpression::Single(term) => {
+                term.collect_variables(variables);
+            },
+            ColoredArcExpression::All(_) => {},
+            ColoredArcExpression::Scaled(_, sub_expr) => {
+                sub_expr.collect_variables(variables);
+            },
+            ColoredArcExpression::Add(sub_exprs) => {
+                for sub_expr in sub_exprs {
+                    sub_expr.collect_variables(variables);
+                }
+            },
+            ColoredArcExpression::Subtract(left, right) => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+        }
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ColorComparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual
+}
+
+
+/// a transition guard, which is a boolean condition on the binding of the variables of the transition
+/// colors are compared according to the order of the constants in their domains
+/// (lexicographically for product domains)
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ColorGuard {
+    Constant(bool),
+    Not(Box<ColorGuard>),
+    And(Vec<ColorGuard>),
+    Or(Vec<ColorGuard>),
+    Compare(ColorTerm,ColorComparison,ColorTerm)
+}
+
+impl ColorGuard {
+
+    pub fn evaluate(&self, net : &ColoredPetriNet, binding : &ColorBinding) -> bool {
+        match self {
+            ColorGuard::Constant(value) => *value,
+            ColorGuard::Not(sub_guard) => !sub_guard.evaluate(net, binding),
+            ColorGuard::And(sub_guards) => sub_guards.iter().all(|g| g.evaluate(net, binding)),
+            ColorGuard::Or(sub_guards) => sub_guards.iter().any(|g| g.evaluate(net, binding)),
+            ColorGuard::Compare(left, comparison, right) => {
+                let left = left.evaluate(net, binding);
+                let right = right.evaluate(net, binding);
+                match comparison {
+                    ColorComparison::Equal => left == right,
+                    ColorComparison::NotEqual => left != right,
+                    ColorComparison::Less => left < right,
+                    ColorComparison::LessOrEqual => left <= right,
+                    ColorComparison::Greater => left > right,
+                    ColorComparison::GreaterOrEqual => left >= right
+                }
+            }
+        }
+    }
+
+    pub fn collect_variables(&self, variables : &mut BTreeSet<usize>) {
+        match self {
+            ColorGuard::Constant(_) => {},
+            ColorGuard::Not(sub_guard) => {
+                sub_guard.collect_variables(variables);
+            },
+            ColorGuard::And(sub_guards) | ColorGuard::Or(sub_guards) => {
+                for sub_guard in sub_guards {
+                    sub_guard.collect_variables(variables);
+                }
+            },
+            ColorGuard::Compare(left, _, right) => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+        }
+    }
+}
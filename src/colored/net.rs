@@ -0,0 +1,161 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use itertools::Itertools;
+
+use crate::colored::domain::{Color, ColorDomain, ColorDomainKind};
+use crate::colored::expression::{ColoredArcExpression, ColorGuard};
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColoredPlace {
+    pub name : String,
+    /// the domain of the tokens that this place may contain
+    pub domain_id : usize
+}
+
+impl ColoredPlace {
+    pub fn new(name: String, domain_id: usize) -> Self {
+        Self { name, domain_id }
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColoredVariable {
+    pub name : String,
+    /// must be an enumeration domain
+    pub domain_id : usize
+}
+
+impl ColoredVariable {
+    pub fn new(name: String, domain_id: usize) -> Self {
+        Self { name, domain_id }
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColoredTransition {
+    pub name : String,
+    /// the transition may only fire under the bindings that satisfy its guard
+    pub guard : ColorGuard,
+    /// maps place ids to the multiset of tokens consumed from that place
+    pub preset : HashMap<usize,ColoredArcExpression>,
+    /// maps place ids to the multiset of tokens produced in that place
+    pub postset : HashMap<usize,ColoredArcExpression>
+}
+
+impl ColoredTransition {
+    pub fn new(name: String, guard: ColorGuard, preset: HashMap<usize,ColoredArcExpression>, postset: HashMap<usize,ColoredArcExpression>) -> Self {
+        Self { name, guard, preset, postset }
+    }
+
+    /// the ids of the variables that occur in the guard or on the arcs of the transition
+    pub fn get_variables(&self) -> BTreeSet<usize> {
+        let mut variables = BTreeSet::new();
+        self.guard.collect_variables(&mut variables);
+        for arc_expr in self.preset.values().chain(self.postset.values()) {
+            arc_expr.collect_variables(&mut variables);
+        }
+        variables
+    }
+}
+
+
+/// a colored Petri net, restricted to finite color domains as in symmetric nets
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ColoredPetriNet {
+    pub domains : Vec<ColorDomain>,
+    pub variables : Vec<ColoredVariable>,
+    pub places : Vec<ColoredPlace>,
+    pub transitions : Vec<ColoredTransition>
+}
+
+impl ColoredPetriNet {
+    pub fn new(domains: Vec<ColorDomain>, variables: Vec<ColoredVariable>, places: Vec<ColoredPlace>, transitions: Vec<ColoredTransition>) -> Self {
+        Self { domains, variables, places, transitions }
+    }
+
+    /// the number of colors in a domain
+    pub fn get_domain_size(&self, domain_id : usize) -> usize {
+        match &self.domains.get(domain_id).unwrap().kind {
+            ColorDomainKind::Enumeration(constants) => {
+                constants.len()
+            },
+            ColorDomainKind::Product(components) => {
+                components.iter().map(|component_id| self.get_domain_size(*component_id)).product()
+            }
+        }
+    }
+
+    /// all the colors of a domain, in increasing order
+    pub fn get_domain_colors(&self, domain_id : usize) -> Vec<Color> {
+        match &self.domains.get(domain_id).unwrap().kind {
+            ColorDomainKind::Enumeration(constants) => {
+                (0..constants.len()).map(|constant_id| Color::new(vec![constant_id])).collect()
+            },
+            ColorDomainKind::Product(components) => {
+                components.iter()
+                    .map(|component_id| 0..self.get_domain_size(*component_id))
+                    .multi_cartesian_product()
+                    .map(Color::new)
+                    .collect()
+            }
+        }
+    }
+
+    /// the names of the constants of a color of a certain domain, one per component
+    pub fn get_color_constants_names(&self, domain_id : usize, color : &Color) -> Vec<String> {
+        let components_domains = match &self.domains.get(domain_id).unwrap().kind {
+            ColorDomainKind::Enumeration(_) => {
+                vec![domain_id]
+            },
+            ColorDomainKind::Product(components) => {
+                components.clone()
+            }
+        };
+        components_domains.iter().zip(color.components.iter())
+            .map(|(component_id,constant_id)| {
+                match &self.domains.get(*component_id).unwrap().kind {
+                    ColorDomainKind::Enumeration(constants) => {
+                        constants.get(*constant_id).unwrap().clone()
+                    },
+                    ColorDomainKind::Product(_) => {
+                        unreachable!()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+
+/// a marking of a colored Petri net i.e., for each place, a multiset of colors
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ColoredMarking {
+    pub tokens : BTreeMap<usize,BTreeMap<Color,u32>>
+}
+
+impl ColoredMarking {
+    pub fn new(tokens: BTreeMap<usize,BTreeMap<Color,u32>>) -> Self {
+        Self { tokens }
+    }
+}
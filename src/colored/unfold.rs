@@ -0,0 +1,168 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use itertools::Itertools;
+
+use crate::colored::domain::Color;
+use crate::colored::expression::{ColorBinding, ColoredArcExpression};
+use crate::colored::net::{ColoredMarking, ColoredPetriNet};
+use crate::model::label::{PetriStateLabel, PetriTransitionLabel};
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::transition::PetriTransition;
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ColoredUnfoldingError {
+    /// an arc of a certain transition produces or consumes tokens which do not belong to the domain of a certain place
+    IllTypedArc{transition_id:usize,place_id:usize},
+    /// the initial marking contains tokens which do not belong to the domain of a certain place
+    IllTypedInitialMarking{place_id:usize},
+    /// the arcs of a certain transition produce or consume a number of tokens which does not fit in a u32
+    MultiplicityOverflow{transition_id:usize},
+    /// two unfolded places would have the same name (e.g., place "P" with color "a_b" and place "P_a" with color "b")
+    DuplicatePlaceName{name:String}
+}
+
+
+/// the P/T net obtained by unfolding a colored Petri net
+pub struct ColoredUnfolding {
+    pub petri_net : PetriNet,
+    pub initial_marking : Marking,
+    /// for each place of the unfolded net, the id of the colored place and the color it stands for
+    pub place_origins : Vec<(usize,Color)>,
+    /// for each transition of the unfolded net, the id of the colored transition and the binding it stands for
+    pub transition_origins : Vec<(usize,ColorBinding)>,
+    /// maps a colored place and a color to the corresponding place of the unfolded net
+    pub unfolded_place_ids : HashMap<(usize,Color),usize>
+}
+
+
+/// unfolds a colored Petri net into a P/T net
+///
+/// there is one unfolded place per colored place and color of its domain
+/// its name is that of the colored place followed by the names of the constants of the color, separated by underscores
+/// (except for domains with a single color, in which case it is the name of the colored place)
+/// so that it can be referred to in CTL formulas if the names of the constants are alphanumeric
+/// an error is returned if two unfolded places end up with the same name
+///
+/// there is one unfolded transition per colored transition and binding of its variables that satisfies its guard
+/// all the unfolded transitions of a colored transition share a label, which is the name of the colored transition
+pub fn unfold_colored_petri_net(
+    colored_net : &ColoredPetriNet,
+    initial_marking : &ColoredMarking
+) -> Result<ColoredUnfolding,ColoredUnfoldingError> {
+    let mut places = Vec::new();
+    let mut place_origins = Vec::new();
+    let mut unfolded_place_ids = HashMap::new();
+    let mut place_names = HashSet::new();
+    for (colored_place_id,colored_place) in colored_net.places.iter().enumerate() {
+        let has_single_color = colored_net.get_domain_size(colored_place.domain_id) == 1;
+        for color in colored_net.get_domain_colors(colored_place.domain_id) {
+            let name = if has_single_color {
+                colored_place.name.clone()
+            } else {
+                let mut name_parts = vec![colored_place.name.clone()];
+                name_parts.extend(colored_net.get_color_constants_names(colored_place.domain_id, &color));
+                name_parts.join("_")
+            };
+            if !place_names.insert(name.clone()) {
+                return Err(ColoredUnfoldingError::DuplicatePlaceName { name });
+            }
+            unfolded_place_ids.insert((colored_place_id,color.clone()), places.len());
+            places.push(Some(Arc::new(PetriStateLabel::new(name))));
+            place_origins.push((colored_place_id,color));
+        }
+    }
+    // ***
+    let mut transitions = Vec::new();
+    let mut transition_origins = Vec::new();
+    for (colored_transition_id,colored_transition) in colored_net.transitions.iter().enumerate() {
+        let label = Arc::new(PetriTransitionLabel::new(colored_transition.name.clone()));
+        let variables : Vec<usize> = colored_transition.get_variables().into_iter().collect();
+        let bindings = variables.iter()
+            .map(|var_id| 0..colored_net.get_domain_size(colored_net.variables.get(*var_id).unwrap().domain_id))
+            .multi_cartesian_product()
+            .map(|values| ColorBinding::new(variables.iter().cloned().zip(values).collect()));
+        for binding in bindings {
+            if !colored_transition.guard.evaluate(colored_net, &binding) {
+                continue;
+            }
+            let preset_tokens = unfold_arcs(colored_net, colored_transition_id, &colored_transition.preset, &binding, &unfolded_place_ids)?;
+            let postset_tokens = unfold_arcs(colored_net, colored_transition_id, &colored_transition.postset, &binding, &unfolded_place_ids)?;
+            transitions.push(PetriTransition::new(Some(label.clone()), preset_tokens, postset_tokens));
+            transition_origins.push((colored_transition_id,binding));
+        }
+    }
+    // ***
+    let mut initial_tokens = BTreeMap::new();
+    for (colored_place_id,multiset) in &initial_marking.tokens {
+        for (color,multiplicity) in multiset {
+            match unfolded_place_ids.get(&(*colored_place_id,color.clone())) {
+                None => {
+                    return Err(ColoredUnfoldingError::IllTypedInitialMarking { place_id: *colored_place_id });
+                },
+                Some(place_id) => {
+                    if *multiplicity > 0 {
+                        initial_tokens.insert(*place_id, *multiplicity);
+                    }
+                }
+            }
+        }
+    }
+    Ok(
+        ColoredUnfolding {
+            petri_net : PetriNet::new(places, transitions),
+            initial_marking : Marking::new(initial_tokens),
+            place_origins,
+            transition_origins,
+            unfolded_place_ids
+        }
+    )
+}
+
+
+/// evaluates the arc expressions of a certain transition under a binding
+fn unfold_arcs(
+    colored_net : &ColoredPetriNet,
+    colored_transition_id : usize,
+    arcs : &HashMap<usize,ColoredArcExpression>,
+    binding : &ColorBinding,
+    unfolded_place_ids : &HashMap<(usize,Color),usize>
+) -> Result<HashMap<usize,u32>,ColoredUnfoldingError> {
+    let overflow = ColoredUnfoldingError::MultiplicityOverflow { transition_id: colored_transition_id };
+    let mut tokens = HashMap::new();
+    for (colored_place_id,arc_expr) in arcs.iter().sorted_by_key(|(place_id,_)| **place_id) {
+        let multiset = arc_expr.evaluate(colored_net, binding).ok_or(overflow.clone())?;
+        for (color,multiplicity) in multiset {
+            match unfolded_place_ids.get(&(*colored_place_id,color)) {
+                None => {
+                    return Err(ColoredUnfoldingError::IllTypedArc { transition_id: colored_transition_id, place_id: *colored_place_id });
+                },
+                Some(place_id) => {
+                    let sum : &mut u32 = tokens.entry(*place_id).or_insert(0);
+                    *sum = sum.checked_add(multiplicity).ok_or(overflow.clone())?;
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
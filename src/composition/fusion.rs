@@ -0,0 +1,187 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use citreelo::ctl::{CTLFormula, CTLFormulaLeaf};
+
+use crate::model::label::PetriStateLabel;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model_checking::props::{BuiltinPetriAtomicProposition, TokensCountAtom};
+
+
+
+
+/// the net obtained by fusing the interface places of several nets
+pub struct PetriFusionComposition {
+    pub petri_net : PetriNet,
+    pub initial_marking : Marking,
+    /// for each place of the composed net, the places it stems from, as pairs of a component net index and a place id in that net
+    /// (several ones for a fused interface place, a single one otherwise)
+    pub place_origins : Vec<Vec<(usize,usize)>>,
+    /// for each transition of the composed net, the index of the component net it comes from and its id in that net
+    pub transition_origins : Vec<(usize,usize)>,
+    /// for each component net, maps each of its place ids to the id of the corresponding place in the composed net
+    pub component_place_ids : Vec<Vec<usize>>
+}
+
+impl PetriFusionComposition {
+    /// the id in the composed net of a place of a component net
+    pub fn get_composed_place_id(&self, component_id : usize, place_id : usize) -> Option<usize> {
+        self.component_place_ids.get(component_id)?.get(place_id).cloned()
+    }
+
+    /// the id in the composed net of a transition of a component net
+    pub fn get_composed_transition_id(&self, component_id : usize, transition_id : usize) -> Option<usize> {
+        self.transition_origins.iter().position(|origin| *origin == (component_id,transition_id))
+    }
+
+    /// re-targets an atomic proposition written against a component net so that it refers to the composed net
+    /// (returns None if it refers to a place which does not exist in the component net)
+    pub fn retarget_atomic_proposition(
+        &self,
+        component_id : usize,
+        proposition : &BuiltinPetriAtomicProposition
+    ) -> Option<BuiltinPetriAtomicProposition> {
+        let retarget_atom = |atom : &TokensCountAtom| {
+            match atom {
+                TokensCountAtom::RawInteger(raw_int) => Some(TokensCountAtom::RawInteger(*raw_int)),
+                TokensCountAtom::NumberOfTokensInPlace(place_id) => {
+                    self.get_composed_place_id(component_id, *place_id).map(TokensCountAtom::NumberOfTokensInPlace)
+                }
+            }
+        };
+        match proposition {
+            BuiltinPetriAtomicProposition::TokensCount(rel, left, right) => {
+                Some(BuiltinPetriAtomicProposition::TokensCount(rel.clone(), retarget_atom(left)?, retarget_atom(right)?))
+            },
+            BuiltinPetriAtomicProposition::PreviousTransitionLabelMustBe(transition_label) => {
+                Some(BuiltinPetriAtomicProposition::PreviousTransitionLabelMustBe(transition_label.clone()))
+            }
+        }
+    }
+
+    /// re-targets a CTL formula written against a component net
+    /// (e.g., parsed with a BuiltinPetriCtlParser built from that net) so that it refers to the composed net
+    ///
+    /// the "is-fireable" conditions of the formula are expanded at parsing time into conditions on the places of the component net
+    /// these remain exact in the composed net as long as the transitions of the other component nets do not have higher priorities
+    /// and no transition is connected to several places that are fused together
+    pub fn retarget_formula(
+        &self,
+        component_id : usize,
+        formula : &CTLFormula<BuiltinPetriAtomicProposition>
+    ) -> Option<CTLFormula<BuiltinPetriAtomicProposition>> {
+        match formula {
+            CTLFormula::Leaf(CTLFormulaLeaf::AtomicProp(proposition)) => {
+                let retargeted = self.retarget_atomic_proposition(component_id, proposition)?;
+                Some(CTLFormula::Leaf(CTLFormulaLeaf::AtomicProp(retargeted)))
+            },
+            CTLFormula::Leaf(leaf) => {
+                Some(CTLFormula::Leaf(leaf.clone()))
+            },
+            CTLFormula::Unary(op, sub_formula) => {
+                let sub_formula = self.retarget_formula(component_id, sub_formula)?;
+                Some(CTLFormula::Unary(op.clone(), Box::new(sub_formula)))
+            },
+            CTLFormula::Binary(op, left, right) => {
+                let left = self.retarget_formula(component_id, left)?;
+                let right = self.retarget_formula(component_id, right)?;
+                Some(CTLFormula::Binary(op.clone(), Box::new(left), Box::new(right)))
+            }
+        }
+    }
+}
+
+
+/// composes several nets (each with its initial marking) by fusing their interface places
+///
+/// all the places whose label belongs to the set of interface labels are fused into a single place per label
+/// (including several such places within the same component net)
+/// the other places are kept apart, as are all the transitions, which are copied into the composed net
+/// in the order of the component nets
+///
+/// a fused place initially holds the maximal number of tokens it has in the initial markings of the component nets
+/// (so that an interface place which is declared in several component nets, e.g., a shared lock, is not counted several times)
+/// and its capacity, if any, is the minimal capacity among the places it stems from
+pub fn compose_by_place_fusion(
+    components : &[(&PetriNet,&Marking)],
+    interface_labels : &HashSet<PetriStateLabel>
+) -> PetriFusionComposition {
+    let mut places = Vec::new();
+    let mut place_origins : Vec<Vec<(usize,usize)>> = Vec::new();
+    let mut component_place_ids = Vec::new();
+    let mut interface_place_ids : HashMap<PetriStateLabel,usize> = HashMap::new();
+    for (component_id,(component_net,_)) in components.iter().enumerate() {
+        let mut place_ids = Vec::new();
+        for (place_id,place_label) in component_net.get_places().iter().enumerate() {
+            let fused_place_id = match place_label {
+                Some(label) if interface_labels.contains(label.as_ref()) => {
+                    interface_place_ids.get(label.as_ref()).cloned()
+                },
+                _ => None
+            };
+            let composed_place_id = match fused_place_id {
+                Some(composed_place_id) => {
+                    place_origins[composed_place_id].push((component_id,place_id));
+                    composed_place_id
+                },
+                None => {
+                    let composed_place_id = places.len();
+                    if let Some(label) = place_label 
+                        && interface_labels.contains(label.as_ref()) {
+                        interface_place_ids.insert(label.as_ref().clone(), composed_place_id);
+                    }
+                    places.push(place_label.clone());
+                    place_origins.push(vec![(component_id,place_id)]);
+                    composed_place_id
+                }
+            };
+            place_ids.push(composed_place_id);
+        }
+        component_place_ids.push(place_ids);
+    }
+    // ***
+    let mut place_capacities : HashMap<usize,u32> = HashMap::new();
+    let mut initial_tokens : BTreeMap<usize,u32> = BTreeMap::new();
+    let mut transitions = Vec::new();
+    let mut transition_origins = Vec::new();
+    for (component_id,(component_net,component_marking)) in components.iter().enumerate() {
+        let place_ids = &component_place_ids[component_id];
+        for (place_id,capacity) in component_net.iter_place_capacities() {
+            let entry = place_capacities.entry(place_ids[*place_id]).or_insert(*capacity);
+            *entry = u32::min(*entry, *capacity);
+        }
+        for (place_id,num_toks) in component_marking.iter_tokens() {
+            let entry = initial_tokens.entry(place_ids[*place_id]).or_insert(*num_toks);
+            *entry = u32::max(*entry, *num_toks);
+        }
+        for (transition_id,transition) in component_net.get_transitions().iter().enumerate() {
+            transitions.push(transition.map_places(|place_id| place_ids[place_id]));
+            transition_origins.push((component_id,transition_id));
+        }
+    }
+    // ***
+    let petri_net = PetriNet::new(places, transitions).with_place_capacities(place_capacities);
+    PetriFusionComposition {
+        petri_net,
+        initial_marking : Marking::new(initial_tokens),
+        place_origins,
+        transition_origins,
+        component_place_ids
+    }
+}
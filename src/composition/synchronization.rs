@@ -0,0 +1,178 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::model::label::PetriTransitionLabel;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::transition::PetriTransition;
+
+
+
+
+/// the product of several nets synchronized on the labels of their transitions
+pub struct PetriSynchronizedComposition {
+    pub petri_net : PetriNet,
+    pub initial_marking : Marking,
+    /// for each place of the composed net, the index of the component net it comes from and its id in that net
+    pub place_origins : Vec<(usize,usize)>,
+    /// for each transition of the composed net, the transitions it stems from, as pairs of a component net index and a transition id in that net
+    /// (a single one for an interleaved transition, one per participating component net for a synchronized transition)
+    pub transition_origins : Vec<Vec<(usize,usize)>>,
+    /// for each component net, the id in the composed net of its first place
+    /// (the places of a component net are kept contiguous and in the same order)
+    pub(crate) place_offsets : Vec<usize>
+}
+
+impl PetriSynchronizedComposition {
+    /// the id in the composed net of a place of a component net
+    pub fn get_composed_place_id(&self, component_id : usize, place_id : usize) -> Option<usize> {
+        let composed_place_id = self.place_offsets.get(component_id)? + place_id;
+        match self.place_origins.get(composed_place_id) {
+            Some((origin_component_id,_)) if *origin_component_id == component_id => Some(composed_place_id),
+            _ => None
+        }
+    }
+
+    /// the ids of the transitions of the composed net in which a transition of a component net takes part
+    pub fn get_composed_transition_ids(&self, component_id : usize, transition_id : usize) -> Vec<usize> {
+        self.transition_origins.iter().enumerate()
+            .filter(|(_,origins)| origins.contains(&(component_id,transition_id)))
+            .map(|(composed_transition_id,_)| composed_transition_id)
+            .collect()
+    }
+}
+
+
+/// composes several nets (each with its initial marking) in parallel
+///
+/// the places of the composed net are the disjoint union of the places of the component nets
+/// (their labels are kept as is, so that place names should be distinct across component nets
+/// for the composed net to be used with the builtin CTL parser)
+///
+/// transitions whose label does not belong to the synchronization set are interleaved
+/// i.e., they are copied as is into the composed net
+///
+/// transitions whose label belongs to the synchronization set are fused:
+/// for each such label, every component net having transitions with that label must take part
+/// so there is one composed transition per choice of one such transition in each of these component nets
+/// (i.e., the cartesian product of their instances)
+/// whose arcs are the union of those of the chosen transitions and whose priority is their maximal priority
+/// the firing intervals and stochastic timings of fused transitions are not carried over
+///
+/// the interleaved transitions come first, in the order of the component nets,
+/// followed by the synchronized transitions, in the order of their labels
+pub fn compose_by_synchronization(
+    components : &[(&PetriNet,&Marking)],
+    synchronization_labels : &HashSet<PetriTransitionLabel>
+) -> PetriSynchronizedComposition {
+    let mut places = Vec::new();
+    let mut place_origins = Vec::new();
+    let mut place_offsets = Vec::new();
+    let mut place_capacities = HashMap::new();
+    let mut initial_tokens = BTreeMap::new();
+    for (component_id,(component_net,component_marking)) in components.iter().enumerate() {
+        let offset = places.len();
+        place_offsets.push(offset);
+        for (place_id,place_label) in component_net.get_places().iter().enumerate() {
+            places.push(place_label.clone());
+            place_origins.push((component_id,place_id));
+        }
+        for (place_id,capacity) in component_net.iter_place_capacities() {
+            place_capacities.insert(offset + place_id, *capacity);
+        }
+        for (place_id,num_toks) in component_marking.iter_tokens() {
+            initial_tokens.insert(offset + place_id, *num_toks);
+        }
+    }
+    // ***
+    let is_synchronized = |transition : &PetriTransition| {
+        match &transition.transition_label {
+            Some(label) => synchronization_labels.contains(label.as_ref()),
+            None => false
+        }
+    };
+    let mut transitions = Vec::new();
+    let mut transition_origins = Vec::new();
+    for (component_id,(component_net,_)) in components.iter().enumerate() {
+        let offset = place_offsets[component_id];
+        for (transition_id,transition) in component_net.get_transitions().iter().enumerate() {
+            if !is_synchronized(transition) {
+                transitions.push(transition.map_places(|place_id| offset + place_id));
+                transition_origins.push(vec![(component_id,transition_id)]);
+            }
+        }
+    }
+    for label in synchronization_labels.iter().sorted() {
+        // for each participating component net, its transitions having that label
+        let participants : Vec<Vec<(usize,usize)>> = components.iter().enumerate()
+            .map(|(component_id,(component_net,_))| {
+                component_net.get_transitions().iter().enumerate()
+                    .filter(|(_,transition)| transition.transition_label.as_deref() == Some(label))
+                    .map(|(transition_id,_)| (component_id,transition_id))
+                    .collect::<Vec<(usize,usize)>>()
+            })
+            .filter(|instances| !instances.is_empty())
+            .collect();
+        if participants.is_empty() {
+            continue;
+        }
+        for instances in participants.into_iter().multi_cartesian_product() {
+            transitions.push(fuse_transitions(components, &place_offsets, &instances));
+            transition_origins.push(instances);
+        }
+    }
+    // ***
+    let petri_net = PetriNet::new(places, transitions).with_place_capacities(place_capacities);
+    PetriSynchronizedComposition {
+        petri_net,
+        initial_marking : Marking::new(initial_tokens),
+        place_origins,
+        transition_origins,
+        place_offsets
+    }
+}
+
+
+fn fuse_transitions(
+    components : &[(&PetriNet,&Marking)],
+    place_offsets : &[usize],
+    instances : &[(usize,usize)]
+) -> PetriTransition {
+    let mut shifted_instances = instances.iter().map(|(component_id,transition_id)| {
+        let (component_net,_) = components[*component_id];
+        let offset = place_offsets[*component_id];
+        component_net.get_transitions()[*transition_id].map_places(|place_id| offset + place_id)
+    });
+    let mut fused = shifted_instances.next().unwrap();
+    if instances.len() > 1 {
+        fused.firing_interval = None;
+        fused.stochastic_timing = None;
+    }
+    // the places of distinct component nets are disjoint so the arcs can simply be gathered
+    for instance in shifted_instances {
+        fused.preset_tokens.extend(instance.preset_tokens);
+        fused.postset_tokens.extend(instance.postset_tokens);
+        fused.inhibitor_tokens.extend(instance.inhibitor_tokens);
+        fused.reset_places.extend(instance.reset_places);
+        fused.read_tokens.extend(instance.read_tokens);
+        fused.priority = fused.priority.max(instance.priority);
+    }
+    fused
+}
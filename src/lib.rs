@@ -25,3 +25,11 @@ pub mod util;
 
 pub mod reduction;
 
+pub mod colored;
+
+pub mod performance;
+
+pub mod composition;
+
+pub mod structural;
+
@@ -0,0 +1,238 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::model::label::{PetriStateLabel, PetriTransitionLabel};
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::transition::PetriTransition;
+
+
+
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
+pub enum PetriNetBuildingError {
+    #[error("the name '{}' is declared several times (places and transitions must have distinct names)", .0)]
+    DuplicateName(String),
+    #[error("'{}' is neither a declared place nor a declared transition", .0)]
+    UnknownName(String),
+    #[error("unknown place '{}'", .0)]
+    UnknownPlace(String),
+    #[error("unknown transition '{}'", .0)]
+    UnknownTransition(String),
+    #[error("the arc from '{}' to '{}' does not connect a place and a transition", .from, .to)]
+    ArcMustConnectPlaceAndTransition{from:String,to:String},
+    #[error("the arc from '{}' to '{}' has a zero weight", .from, .to)]
+    ZeroWeight{from:String,to:String}
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum PetriBuilderArcKind {
+    Normal,
+    Read,
+    Inhibitor,
+    Reset
+}
+
+struct PetriBuilderArc {
+    source : String,
+    target : String,
+    weight : u32,
+    kind : PetriBuilderArcKind
+}
+
+
+/// builds a PetriNet and its initial marking by referring to places and transitions by their names
+/// rather than by their ids
+///
+/// all the declarations are validated when calling "build"
+/// so that arcs may refer to places and transitions that are declared afterwards
+#[derive(Default)]
+pub struct PetriNetBuilder {
+    places : Vec<String>,
+    /// the name of each transition and its label (which may be shared between several transitions)
+    transitions : Vec<(String,Option<String>)>,
+    arcs : Vec<PetriBuilderArc>,
+    initial_tokens : Vec<(String,u32)>
+}
+
+impl PetriNetBuilder {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// declares a place, which is labelled by its name
+    pub fn add_place(mut self, name : &str) -> Self {
+        self.places.push(name.to_string());
+        self
+    }
+
+    /// declares a transition, which is labelled by its name
+    pub fn add_transition(mut self, name : &str) -> Self {
+        self.transitions.push((name.to_string(),Some(name.to_string())));
+        self
+    }
+
+    /// declares a transition with a label distinct from its name
+    /// (several transitions may have the same label, and a transition without label is silent)
+    pub fn add_labelled_transition(mut self, name : &str, label : Option<&str>) -> Self {
+        self.transitions.push((name.to_string(),label.map(|x| x.to_string())));
+        self
+    }
+
+    /// adds a weighted arc from a place to a transition (preset) or from a transition to a place (postset)
+    /// the weights of several arcs between the same place and transition add up
+    pub fn add_arc(mut self, source : &str, target : &str, weight : u32) -> Self {
+        self.arcs.push(PetriBuilderArc{source:source.to_string(), target:target.to_string(), weight, kind:PetriBuilderArcKind::Normal});
+        self
+    }
+
+    /// adds a read arc, so that the transition requires (without consuming them) some tokens in the place
+    pub fn add_read_arc(mut self, place : &str, transition : &str, weight : u32) -> Self {
+        self.arcs.push(PetriBuilderArc{source:place.to_string(), target:transition.to_string(), weight, kind:PetriBuilderArcKind::Read});
+        self
+    }
+
+    /// adds an inhibitor arc, so that the transition may only fire if the place has less tokens than the threshold
+    pub fn add_inhibitor_arc(mut self, place : &str, transition : &str, threshold : u32) -> Self {
+        self.arcs.push(PetriBuilderArc{source:place.to_string(), target:transition.to_string(), weight:threshold, kind:PetriBuilderArcKind::Inhibitor});
+        self
+    }
+
+    /// adds a reset arc, so that firing the transition empties the place
+    pub fn add_reset_arc(mut self, transition : &str, place : &str) -> Self {
+        self.arcs.push(PetriBuilderArc{source:transition.to_string(), target:place.to_string(), weight:1, kind:PetriBuilderArcKind::Reset});
+        self
+    }
+
+    /// sets the number of tokens in a place in the initial marking (zero by default)
+    pub fn set_initial_tokens(mut self, place : &str, num_toks : u32) -> Self {
+        self.initial_tokens.push((place.to_string(),num_toks));
+        self
+    }
+
+    pub fn build(self) -> Result<(PetriNet,Marking),PetriNetBuildingError> {
+        let mut declared_names = HashSet::new();
+        let mut place_name_to_id = HashMap::new();
+        let mut places = Vec::new();
+        for place_name in self.places {
+            if !declared_names.insert(place_name.clone()) {
+                return Err(PetriNetBuildingError::DuplicateName(place_name));
+            }
+            place_name_to_id.insert(place_name.clone(), places.len());
+            places.push(Some(Arc::new(PetriStateLabel::new(place_name))));
+        }
+        // ***
+        let mut transition_name_to_id = HashMap::new();
+        let mut labels : HashMap<String,Arc<PetriTransitionLabel>> = HashMap::new();
+        let mut transitions = Vec::new();
+        for (transition_name,opt_label) in self.transitions {
+            if !declared_names.insert(transition_name.clone()) {
+                return Err(PetriNetBuildingError::DuplicateName(transition_name));
+            }
+            // transitions with the same label share it
+            let label = opt_label.map(|lab| {
+                labels.entry(lab.clone())
+                    .or_insert_with(|| Arc::new(PetriTransitionLabel::new(lab)))
+                    .clone()
+            });
+            transition_name_to_id.insert(transition_name, transitions.len());
+            transitions.push(PetriTransition::new(label, HashMap::new(), HashMap::new()));
+        }
+        // ***
+        for arc in self.arcs {
+            if arc.weight == 0 && arc.kind != PetriBuilderArcKind::Reset {
+                return Err(PetriNetBuildingError::ZeroWeight { from: arc.source, to: arc.target });
+            }
+            let (place_id,transition_id,is_preset) = match (place_name_to_id.get(&arc.source),transition_name_to_id.get(&arc.source)) {
+                (Some(place_id),_) => {
+                    match transition_name_to_id.get(&arc.target) {
+                        Some(transition_id) => (*place_id,*transition_id,true),
+                        None => {
+                            if place_name_to_id.contains_key(&arc.target) {
+                                return Err(PetriNetBuildingError::ArcMustConnectPlaceAndTransition { from: arc.source, to: arc.target });
+                            }
+                            return Err(PetriNetBuildingError::UnknownTransition(arc.target));
+                        }
+                    }
+                },
+                (None,Some(transition_id)) => {
+                    match place_name_to_id.get(&arc.target) {
+                        Some(place_id) => (*place_id,*transition_id,false),
+                        None => {
+                            if transition_name_to_id.contains_key(&arc.target) {
+                                return Err(PetriNetBuildingError::ArcMustConnectPlaceAndTransition { from: arc.source, to: arc.target });
+                            }
+                            return Err(PetriNetBuildingError::UnknownPlace(arc.target));
+                        }
+                    }
+                },
+                (None,None) => {
+                    return Err(PetriNetBuildingError::UnknownName(arc.source));
+                }
+            };
+            // read and inhibitor arcs go from a place to a transition, reset arcs the other way around
+            let expects_preset = match arc.kind {
+                PetriBuilderArcKind::Normal => is_preset,
+                PetriBuilderArcKind::Read | PetriBuilderArcKind::Inhibitor => true,
+                PetriBuilderArcKind::Reset => false
+            };
+            if expects_preset != is_preset {
+                return Err(PetriNetBuildingError::ArcMustConnectPlaceAndTransition { from: arc.source, to: arc.target });
+            }
+            let transition : &mut PetriTransition = transitions.get_mut(transition_id).unwrap();
+            match arc.kind {
+                PetriBuilderArcKind::Normal => {
+                    if is_preset {
+                        *transition.preset_tokens.entry(place_id).or_insert(0) += arc.weight;
+                    } else {
+                        *transition.postset_tokens.entry(place_id).or_insert(0) += arc.weight;
+                    }
+                },
+                PetriBuilderArcKind::Read => {
+                    *transition.read_tokens.entry(place_id).or_insert(0) += arc.weight;
+                },
+                PetriBuilderArcKind::Inhibitor => {
+                    transition.inhibitor_tokens.insert(place_id, arc.weight);
+                },
+                PetriBuilderArcKind::Reset => {
+                    transition.reset_places.insert(place_id);
+                }
+            }
+        }
+        // ***
+        let mut initial_tokens = BTreeMap::new();
+        for (place_name,num_toks) in self.initial_tokens {
+            match place_name_to_id.get(&place_name) {
+                None => {
+                    return Err(PetriNetBuildingError::UnknownPlace(place_name));
+                },
+                Some(place_id) => {
+                    if num_toks > 0 {
+                        initial_tokens.insert(*place_id, num_toks);
+                    } else {
+                        initial_tokens.remove(place_id);
+                    }
+                }
+            }
+        }
+        Ok((PetriNet::new(places, transitions),Marking::new(initial_tokens)))
+    }
+}
@@ -0,0 +1,95 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+/// a stable identifier of a place
+/// contrary to the index of the place in the PetriNet (which is used in markings and transitions)
+/// it is not shifted when other places are removed (e.g., by reductions)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PetriPlaceId(pub(crate) usize);
+
+/// a stable identifier of a transition (see PetriPlaceId)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PetriTransitionId(pub(crate) usize);
+
+impl std::fmt::Display for PetriPlaceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p#{}", self.0)
+    }
+}
+
+impl std::fmt::Display for PetriTransitionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "t#{}", self.0)
+    }
+}
+
+
+/// associates stable identifiers to the (dense) indices of the places or of the transitions of a net
+///
+/// stable identifiers are allocated in increasing order and never reused
+/// so that they remain sorted and the index of an identifier can be found by binary search
+///
+/// there is exactly one identifier per node, as nodes are only added or removed via the methods of the net
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct PetriStableIds {
+    stable_ids : Vec<usize>,
+    next_stable_id : usize
+}
+
+impl PetriStableIds {
+
+    pub(crate) fn with_len(num_nodes : usize) -> Self {
+        Self { stable_ids : (0..num_nodes).collect(), next_stable_id : num_nodes }
+    }
+
+    /// allocates the identifier of a node that is added at the end
+    pub(crate) fn push(&mut self) -> usize {
+        let stable_id = self.next_stable_id;
+        self.stable_ids.push(stable_id);
+        self.next_stable_id += 1;
+        stable_id
+    }
+
+    pub(crate) fn remove(&mut self, index : usize) {
+        self.stable_ids.remove(index);
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn len(&self) -> usize {
+        self.stable_ids.len()
+    }
+
+    /// whether the identifiers are sorted, distinct and below the next identifier to be allocated
+    #[cfg(feature = "serde")]
+    pub(crate) fn is_well_formed(&self) -> bool {
+        self.stable_ids.windows(2).all(|pair| pair[0] < pair[1])
+            && self.stable_ids.last().is_none_or(|stable_id| *stable_id < self.next_stable_id)
+    }
+
+    pub(crate) fn get_stable_id(&self, index : usize) -> Option<usize> {
+        self.stable_ids.get(index).cloned()
+    }
+
+    pub(crate) fn get_index(&self, stable_id : usize) -> Option<usize> {
+        self.stable_ids.binary_search(&stable_id).ok()
+    }
+}
@@ -0,0 +1,180 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::model::ids::{PetriPlaceId, PetriTransitionId};
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+
+
+
+
+/// a sparse matrix with one row per place and one column per transition
+/// only its non-zero entries are stored, both by row and by column
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PetriSparseMatrix<T> {
+    rows : Vec<BTreeMap<usize,T>>,
+    columns : Vec<BTreeMap<usize,T>>
+}
+
+impl<T : Copy + Default + PartialEq> PetriSparseMatrix<T> {
+    pub fn new(num_rows : usize, num_columns : usize) -> Self {
+        Self { rows : vec![BTreeMap::new(); num_rows], columns : vec![BTreeMap::new(); num_columns] }
+    }
+
+    /// sets an entry (a zero value removes it)
+    pub fn set(&mut self, row : usize, column : usize, value : T) {
+        if value == T::default() {
+            self.rows[row].remove(&column);
+            self.columns[column].remove(&row);
+        } else {
+            self.rows[row].insert(column, value);
+            self.columns[column].insert(row, value);
+        }
+    }
+
+    pub fn get(&self, row : usize, column : usize) -> T {
+        match self.rows.get(row).and_then(|entries| entries.get(&column)) {
+            Some(value) => *value,
+            None => T::default()
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// the non-zero entries of a row, by increasing column
+    pub fn iter_row(&self, row : usize) -> impl Iterator<Item=(&usize,&T)> {
+        self.rows[row].iter()
+    }
+
+    /// the non-zero entries of a column, by increasing row
+    pub fn iter_column(&self, column : usize) -> impl Iterator<Item=(&usize,&T)> {
+        self.columns[column].iter()
+    }
+
+    /// all the non-zero entries, as (row, column, value), by increasing row then column
+    pub fn iter_entries(&self) -> impl Iterator<Item=(usize,usize,T)> {
+        self.rows.iter().enumerate()
+            .flat_map(|(row,entries)| entries.iter().map(move |(column,value)| (row,*column,*value)))
+    }
+}
+
+
+/// the Pre, Post and incidence (C = Post - Pre) matrices of a net
+/// whose rows are the places and whose columns are the transitions of the net (by index)
+///
+/// only the preset and postset arcs are represented:
+/// read and inhibitor arcs do not move tokens and reset arcs have no linear counterpart
+/// so the state equation is only a necessary condition for reachability, which is not even sound in the presence of reset arcs
+pub struct PetriIncidenceMatrices {
+    pub pre : PetriSparseMatrix<u32>,
+    pub post : PetriSparseMatrix<u32>,
+    pub incidence : PetriSparseMatrix<i64>,
+    /// the stable identifier of the place of each row
+    pub row_places : Vec<PetriPlaceId>,
+    /// the stable identifier of the transition of each column
+    pub column_transitions : Vec<PetriTransitionId>,
+    place_to_row : HashMap<PetriPlaceId,usize>,
+    transition_to_column : HashMap<PetriTransitionId,usize>
+}
+
+impl PetriIncidenceMatrices {
+    pub fn from_net(petri_net : &PetriNet) -> Self {
+        let num_places = petri_net.get_places().len();
+        let num_transitions = petri_net.get_transitions().len();
+        let mut pre = PetriSparseMatrix::new(num_places, num_transitions);
+        let mut post = PetriSparseMatrix::new(num_places, num_transitions);
+        let mut incidence = PetriSparseMatrix::new(num_places, num_transitions);
+        for (transition_id,transition) in petri_net.get_transitions().iter().enumerate() {
+            for (place_id,num_toks) in transition.iter_preset_tokens() {
+                pre.set(*place_id, transition_id, *num_toks);
+            }
+            for (place_id,num_toks) in transition.iter_postset_tokens() {
+                post.set(*place_id, transition_id, *num_toks);
+            }
+        }
+        for (transition_id,transition) in petri_net.get_transitions().iter().enumerate() {
+            let places = transition.iter_preset_tokens().chain(transition.iter_postset_tokens()).map(|(place_id,_)| *place_id);
+            for place_id in places {
+                let effect = post.get(place_id, transition_id) as i64 - pre.get(place_id, transition_id) as i64;
+                incidence.set(place_id, transition_id, effect);
+            }
+        }
+        let row_places : Vec<PetriPlaceId> = (0..num_places)
+            .map(|place_id| petri_net.get_place_stable_id(place_id).unwrap())
+            .collect();
+        let column_transitions : Vec<PetriTransitionId> = (0..num_transitions)
+            .map(|transition_id| petri_net.get_transition_stable_id(transition_id).unwrap())
+            .collect();
+        let place_to_row = row_places.iter().enumerate().map(|(row,place)| (*place,row)).collect();
+        let transition_to_column = column_transitions.iter().enumerate().map(|(column,transition)| (*transition,column)).collect();
+        Self { pre, post, incidence, row_places, column_transitions, place_to_row, transition_to_column }
+    }
+
+    /// the row of a place
+    pub fn get_row(&self, place_stable_id : PetriPlaceId) -> Option<usize> {
+        self.place_to_row.get(&place_stable_id).cloned()
+    }
+
+    /// the column of a transition
+    pub fn get_column(&self, transition_stable_id : PetriTransitionId) -> Option<usize> {
+        self.transition_to_column.get(&transition_stable_id).cloned()
+    }
+
+    /// the change in the number of tokens of each place (i.e., C.σ)
+    /// caused by firing the transitions as many times as given by a Parikh vector σ (from transition ids to numbers of firings)
+    pub fn get_parikh_vector_effect(&self, parikh_vector : &HashMap<usize,u32>) -> Vec<i64> {
+        let mut effect = vec![0; self.incidence.num_rows()];
+        for (transition_id,num_firings) in parikh_vector {
+            for (place_id,place_effect) in self.incidence.iter_column(*transition_id) {
+                effect[*place_id] += place_effect * (*num_firings as i64);
+            }
+        }
+        effect
+    }
+
+    /// the marking M = M0 + C.σ given by the state equation
+    /// for an initial marking M0 and a Parikh vector σ (from transition ids to numbers of firings)
+    ///
+    /// returns None if some place would have a negative number of tokens
+    /// (in which case no firing sequence with that Parikh vector exists)
+    /// the converse does not hold: the resulting marking may not be reachable via such a firing sequence
+    pub fn apply_state_equation(&self, initial_marking : &Marking, parikh_vector : &HashMap<usize,u32>) -> Option<Marking> {
+        let effect = self.get_parikh_vector_effect(parikh_vector);
+        let mut tokens = BTreeMap::new();
+        for (place_id,place_effect) in effect.into_iter().enumerate() {
+            let initial_toks = match initial_marking.get_num_toks_at_place(&place_id) {
+                Some(toks) => *toks as i64,
+                None => 0
+            };
+            let num_toks = initial_toks + place_effect;
+            if num_toks < 0 {
+                return None;
+            }
+            if num_toks > 0 {
+                tokens.insert(place_id, num_toks as u32);
+            }
+        }
+        Some(Marking::new(tokens))
+    }
+}
@@ -0,0 +1,96 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+/// static firing interval of a transition in a Time Petri Net
+/// once the transition is enabled, it cannot fire before "earliest" time units have elapsed
+/// and it must fire (unless it is disabled in the meantime) before "latest" time units have elapsed
+/// (a "latest" of None stands for +∞)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedFiringInterval", into = "SerializedFiringInterval"))]
+pub struct PetriFiringInterval {
+    earliest : u32,
+    latest   : Option<u32>
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
+pub enum PetriFiringIntervalError {
+    #[error("the firing interval [{},{}] is empty", .earliest, .latest)]
+    EmptyInterval{earliest:u32,latest:u32}
+}
+
+impl PetriFiringInterval {
+    /// fails if the interval is empty i.e., if "latest" is smaller than "earliest"
+    pub fn try_new(earliest: u32, latest: Option<u32>) -> Result<Self,PetriFiringIntervalError> {
+        if let Some(latest) = latest 
+            && latest < earliest {
+            return Err(PetriFiringIntervalError::EmptyInterval { earliest, latest });
+        }
+        Ok(Self { earliest, latest })
+    }
+
+    pub fn get_earliest(&self) -> u32 {
+        self.earliest
+    }
+
+    /// None stands for +∞
+    pub fn get_latest(&self) -> Option<u32> {
+        self.latest
+    }
+}
+
+/// deserialized intervals go through "try_new" so that empty intervals are rejected
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedFiringInterval {
+    earliest : u32,
+    latest   : Option<u32>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedFiringInterval> for PetriFiringInterval {
+    type Error = PetriFiringIntervalError;
+
+    fn try_from(value: SerializedFiringInterval) -> Result<Self, Self::Error> {
+        Self::try_new(value.earliest, value.latest)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<PetriFiringInterval> for SerializedFiringInterval {
+    fn from(value: PetriFiringInterval) -> Self {
+        Self { earliest: value.earliest, latest: value.latest }
+    }
+}
+
+/// untimed transitions may fire at any moment once enabled i.e., [0,+∞)
+impl Default for PetriFiringInterval {
+    fn default() -> Self {
+        Self { earliest: 0, latest: None }
+    }
+}
+
+impl std::fmt::Display for PetriFiringInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.latest {
+            Some(latest) => write!(f, "[{},{}]", self.earliest, latest),
+            None => write!(f, "[{},∞)", self.earliest)
+        }
+    }
+}
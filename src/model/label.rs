@@ -16,6 +16,7 @@ limitations under the License.
 
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PetriStateLabel {
     pub label : String
 }
@@ -33,6 +34,7 @@ impl std::fmt::Display for PetriStateLabel {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PetriTransitionLabel {
     pub label : String 
 }
@@ -18,6 +18,7 @@ use std::collections::BTreeMap;
 
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Marking {
     // place id to number of tokens
     // use BTreeMap so one can derive the Hash of Marking
@@ -34,6 +35,10 @@ impl Marking {
     pub fn iter_tokens(&self) -> impl Iterator<Item=(&usize,&u32)> {
         self.tokens.iter()
     }
+    /// whether each place contains at least as many tokens as in the other marking
+    pub fn covers(&self, other : &Marking) -> bool {
+        other.tokens.iter().all(|(place_id,toks)| self.tokens.get(place_id).is_some_and(|own| own >= toks) || *toks == 0)
+    }
 }
 
 
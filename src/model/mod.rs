@@ -20,3 +20,9 @@ pub mod label;
 pub mod marking;
 pub mod transition;
 pub mod net;
+pub mod interval;
+pub mod stochastic;
+pub mod builder;
+pub mod ids;
+pub mod incidence;
+pub mod omega;
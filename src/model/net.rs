@@ -14,49 +14,211 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::{BTreeMap, HashMap}, sync::Arc};
 
-use crate::model::{label::{PetriStateLabel, PetriTransitionLabel}, transition::PetriTransition};
+use crate::model::{ids::{PetriPlaceId, PetriStableIds, PetriTransitionId}, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, transition::{shift_place_ids_after_removal, PetriTransition}};
 
 
 
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// places and transitions are identified by their (dense) indices in the "places" and "transitions" vectors
+/// which are used in markings and in the firing code
+/// these indices are shifted when places or transitions are removed (e.g., by reductions)
+/// so each place and transition also has a stable identifier (see PetriPlaceId and PetriTransitionId)
+///
+/// places and transitions are only added or removed via "add_place", "add_transition", "remove_place" and "remove_transition"
+/// so that stable identifiers are kept in sync
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "crate::util::serialization::net::SerializedPetriNet", try_from = "crate::util::serialization::net::SerializedPetriNet")
+)]
 pub struct PetriNet {
-    pub places      : Vec<Option<Rc<PetriStateLabel>>>,
-    pub transitions : Vec<PetriTransition> 
+    places      : Vec<Option<Arc<PetriStateLabel>>>,
+    transitions : Vec<PetriTransition>,
+    /// maps place ids to the maximal number of tokens these places may contain (strong capacity semantics)
+    /// places that do not appear have an unbounded capacity
+    pub(crate) place_capacities : HashMap<usize,u32>,
+    pub(crate) place_stable_ids : PetriStableIds,
+    pub(crate) transition_stable_ids : PetriStableIds
 }
 
+/// two nets are equal if they have the same structure, regardless of the stable identifiers of their nodes
+impl PartialEq for PetriNet {
+    fn eq(&self, other: &Self) -> bool {
+        self.places == other.places && self.transitions == other.transitions && self.place_capacities == other.place_capacities
+    }
+}
+
+impl Eq for PetriNet {}
+
 impl PetriNet {
 
     pub fn remove_place(&mut self, place_to_remove_id : usize) {
         for transition in self.transitions.iter_mut() {
             transition.remove_place(place_to_remove_id);
         }
+        self.place_capacities.remove(&place_to_remove_id);
+        shift_place_ids_after_removal(&mut self.place_capacities, place_to_remove_id);
+        self.place_stable_ids.remove(place_to_remove_id);
         self.places.remove(place_to_remove_id);
     }
 
-    pub fn new(places: Vec<Option<Rc<PetriStateLabel>>>, transitions: Vec<PetriTransition>) -> Self {
-        Self { places, transitions }
+    /// removes a transition, shifting the indices of the following transitions
+    pub fn remove_transition(&mut self, transition_to_remove_id : usize) -> PetriTransition {
+        self.transition_stable_ids.remove(transition_to_remove_id);
+        self.transitions.remove(transition_to_remove_id)
+    }
+
+    pub fn new(places: Vec<Option<Arc<PetriStateLabel>>>, transitions: Vec<PetriTransition>) -> Self {
+        let place_stable_ids = PetriStableIds::with_len(places.len());
+        let transition_stable_ids = PetriStableIds::with_len(transitions.len());
+        Self { places, transitions, place_capacities : HashMap::new(), place_stable_ids, transition_stable_ids }
     }
     
     pub fn new_empty() -> Self {
-        Self { places:Vec::new(), transitions:Vec::new() }
+        Self::new(Vec::new(), Vec::new())
+    }
+
+    /// builds a net from its parts, which are expected to be consistent with one another
+    /// (e.g., when deserializing, see "util::serialization::net")
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts(
+        places : Vec<Option<Arc<PetriStateLabel>>>,
+        transitions : Vec<PetriTransition>,
+        place_capacities : HashMap<usize,u32>,
+        place_stable_ids : PetriStableIds,
+        transition_stable_ids : PetriStableIds
+    ) -> Self {
+        Self { places, transitions, place_capacities, place_stable_ids, transition_stable_ids }
+    }
+
+    /// the labels of the places, indexed by place id
+    pub fn get_places(&self) -> &[Option<Arc<PetriStateLabel>>] {
+        &self.places
+    }
+
+    /// the labels of the places, which can be modified in place
+    /// (places are added or removed via "add_place" and "remove_place")
+    pub fn get_places_mut(&mut self) -> &mut [Option<Arc<PetriStateLabel>>] {
+        &mut self.places
+    }
+
+    /// the transitions, indexed by transition id
+    pub fn get_transitions(&self) -> &[PetriTransition] {
+        &self.transitions
+    }
+
+    /// the transitions, which can be modified in place
+    /// (transitions are added or removed via "add_transition" and "remove_transition")
+    pub fn get_transitions_mut(&mut self) -> &mut [PetriTransition] {
+        &mut self.transitions
+    }
+
+    /// the stable identifier of the place at a certain index
+    pub fn get_place_stable_id(&self, place_id : usize) -> Option<PetriPlaceId> {
+        self.place_stable_ids.get_stable_id(place_id).map(PetriPlaceId)
+    }
+
+    /// the current index of a place, or None if it has been removed
+    pub fn get_place_index(&self, place_stable_id : PetriPlaceId) -> Option<usize> {
+        self.place_stable_ids.get_index(place_stable_id.0)
+    }
+
+    /// the stable identifier of the transition at a certain index
+    pub fn get_transition_stable_id(&self, transition_id : usize) -> Option<PetriTransitionId> {
+        self.transition_stable_ids.get_stable_id(transition_id).map(PetriTransitionId)
+    }
+
+    /// the current index of a transition, or None if it has been removed
+    pub fn get_transition_index(&self, transition_stable_id : PetriTransitionId) -> Option<usize> {
+        self.transition_stable_ids.get_index(transition_stable_id.0)
+    }
+
+    pub fn get_place(&self, place_stable_id : PetriPlaceId) -> Option<&Option<Arc<PetriStateLabel>>> {
+        self.get_place_index(place_stable_id).map(|place_id| &self.places[place_id])
     }
 
-    pub fn add_place(&mut self, place_label : Option<Rc<PetriStateLabel>>) -> usize {
+    pub fn get_transition(&self, transition_stable_id : PetriTransitionId) -> Option<&PetriTransition> {
+        self.get_transition_index(transition_stable_id).map(|tr_id| &self.transitions[tr_id])
+    }
+
+    pub fn get_transition_mut(&mut self, transition_stable_id : PetriTransitionId) -> Option<&mut PetriTransition> {
+        self.get_transition_index(transition_stable_id).map(|tr_id| &mut self.transitions[tr_id])
+    }
+
+    /// sets the capacities of some places
+    /// the initial marking is expected to respect these capacities
+    pub fn with_place_capacities(mut self, place_capacities : HashMap<usize,u32>) -> Self {
+        self.place_capacities = place_capacities;
+        self
+    }
+
+    pub fn get_place_capacity(&self, place_id : &usize) -> Option<u32> {
+        self.place_capacities.get(place_id).cloned()
+    }
+
+    pub fn iter_place_capacities(&self) -> impl Iterator<Item=(&usize,&u32)> {
+        self.place_capacities.iter()
+    }
+
+    /// whether the transition is enabled and firing it would not increase
+    /// the number of tokens in a place beyond its capacity (strong capacity semantics)
+    /// priorities are not taken into account
+    pub fn is_transition_enabled(&self, transition_id : usize, marking : &Marking) -> bool {
+        let transition = self.transitions.get(transition_id).unwrap();
+        if self.place_capacities.is_empty() {
+            return transition.is_enabled(marking);
+        }
+        match transition.try_fire(self.places.len(), marking) {
+            None => {
+                false
+            },
+            Some(new_marking) => {
+                self.place_capacities.iter().all(|(place_id,capacity)| {
+                    let before = *marking.get_num_toks_at_place(place_id).unwrap_or(&0);
+                    let after = *new_marking.get_num_toks_at_place(place_id).unwrap_or(&0);
+                    after <= *capacity || after <= before
+                })
+            }
+        }
+    }
+
+    pub fn add_place(&mut self, place_label : Option<Arc<PetriStateLabel>>) -> usize {
         let state_id = self.places.len();
+        self.place_stable_ids.push();
         self.places.push(place_label);
         state_id
     }
 
     pub fn add_transition(&mut self, transition : PetriTransition) -> usize {
         let tr_id = self.transitions.len();
+        self.transition_stable_ids.push();
         self.transitions.push(transition);
         tr_id
     }
 
-    pub fn relabel_places(&mut self, relabbelling : HashMap<PetriStateLabel, Option<Rc<PetriStateLabel>>>) {
+    /// returns the ids of the transitions which may fire from the given marking
+    /// i.e., the enabled transitions which have the maximal priority among all the enabled transitions
+    pub fn get_fireable_transitions(&self, marking : &Marking) -> Vec<usize> {
+        let enabled : Vec<usize> = (0..self.transitions.len())
+            .filter(|tr_id| self.is_transition_enabled(*tr_id, marking))
+            .collect();
+        match enabled.iter().map(|tr_id| self.transitions[*tr_id].get_priority()).max() {
+            None => {
+                enabled
+            },
+            Some(max_priority) => {
+                enabled.into_iter()
+                    .filter(|tr_id| self.transitions[*tr_id].get_priority() == max_priority)
+                    .collect()
+            }
+        }
+    }
+
+    #[allow(clippy::collapsible_if)]
+    pub fn relabel_places(&mut self, relabbelling : HashMap<PetriStateLabel, Option<Arc<PetriStateLabel>>>) {
         let mut new_places = vec![];
         for place in self.places.drain(..) {
             let mut replaced = false;
@@ -73,16 +235,13 @@ impl PetriNet {
         self.places = new_places;
     }
 
-    pub fn relabel_transitions(&mut self, relabbelling : HashMap<PetriTransitionLabel, Option<Rc<PetriTransitionLabel>>>) {
+    pub fn relabel_transitions(&mut self, relabbelling : HashMap<PetriTransitionLabel, Option<Arc<PetriTransitionLabel>>>) {
         let mut new_transitions = vec![];
         for transition in self.transitions.drain(..) {
             if let Some(x) = &transition.transition_label {
                 if let Some(new_lab) = relabbelling.get(x) {
-                    let new_transition = PetriTransition::new(
-                        new_lab.clone(), 
-                        transition.preset_tokens, 
-                        transition.postset_tokens
-                    );
+                    let mut new_transition = transition.clone();
+                    new_transition.transition_label = new_lab.clone();
                     new_transitions.push(new_transition);
                 } else {
                     new_transitions.push(transition);
@@ -94,4 +253,56 @@ impl PetriNet {
         self.transitions = new_transitions;
     }
 
+    /// compiles the place capacities away
+    /// by adding, for each place with a capacity k, a complementary place
+    /// such that the sum of the tokens in both places is always k
+    ///
+    /// the resulting net has no capacities but the same behavior (for initial markings which respect the capacities)
+    /// returns the new net, the new initial marking and a map from the places with capacities to their complementary places
+    pub fn compile_capacities_into_complementary_places(
+        &self, 
+        initial_marking : &Marking
+    ) -> (PetriNet,Marking,HashMap<usize,usize>) {
+        // the places and transitions of the original net keep their stable identifiers
+        let mut new_net = self.clone();
+        new_net.place_capacities = HashMap::new();
+        let mut new_tokens : BTreeMap<usize,u32> = initial_marking.tokens.clone();
+        let mut complementary_places = HashMap::new();
+        // iterates in the order of the place ids so that the complementary places are added deterministically
+        let capacities : BTreeMap<usize,u32> = self.place_capacities.iter().map(|(x,y)| (*x,*y)).collect();
+        for (place_id,capacity) in capacities {
+            let complementary_label = self.places.get(place_id).unwrap().as_ref().map(
+                |lab| Arc::new(PetriStateLabel::new(format!("{}_complement", lab.label)))
+            );
+            let complementary_id = new_net.add_place(complementary_label);
+            complementary_places.insert(place_id, complementary_id);
+            let initial_toks = *initial_marking.get_num_toks_at_place(&place_id).unwrap_or(&0);
+            if capacity > initial_toks {
+                new_tokens.insert(complementary_id, capacity - initial_toks);
+            }
+            for transition in new_net.transitions.iter_mut() {
+                let consumed = *transition.preset_tokens.get(&place_id).unwrap_or(&0);
+                let produced = *transition.postset_tokens.get(&place_id).unwrap_or(&0);
+                if transition.reset_places.contains(&place_id) {
+                    // after firing, the place contains exactly the produced tokens
+                    if produced > capacity {
+                        // the complementary place never contains more than "capacity" tokens
+                        // so that the transition is never fireable
+                        transition.preset_tokens.insert(complementary_id, capacity + 1);
+                    } else {
+                        transition.reset_places.insert(complementary_id);
+                        if capacity > produced {
+                            transition.postset_tokens.insert(complementary_id, capacity - produced);
+                        }
+                    }
+                } else if produced > consumed {
+                    transition.preset_tokens.insert(complementary_id, produced - consumed);
+                } else if consumed > produced {
+                    transition.postset_tokens.insert(complementary_id, consumed - produced);
+                }
+            }
+        }
+        (new_net,Marking::new(new_tokens),complementary_places)
+    }
+
 }
\ No newline at end of file
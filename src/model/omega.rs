@@ -0,0 +1,136 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+
+use crate::model::marking::Marking;
+use crate::model::transition::PetriTransition;
+
+
+
+
+/// a number of tokens which is either finite or ω, standing for arbitrarily many tokens
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum OmegaTokens {
+    Finite(u32),
+    Omega
+}
+
+impl OmegaTokens {
+    pub fn is_omega(&self) -> bool {
+        matches!(self, OmegaTokens::Omega)
+    }
+
+    /// whether it is at least a finite number of tokens
+    pub fn covers(&self, num_toks : u32) -> bool {
+        match self {
+            OmegaTokens::Finite(toks) => *toks >= num_toks,
+            OmegaTokens::Omega => true
+        }
+    }
+}
+
+impl std::fmt::Display for OmegaTokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OmegaTokens::Finite(toks) => write!(f, "{}", toks),
+            OmegaTokens::Omega => write!(f, "ω")
+        }
+    }
+}
+
+
+/// an extended marking in which places may contain ω tokens (see the coverability graph)
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct OmegaMarking {
+    // place id to number of tokens
+    // places without tokens do not appear
+    pub(crate) tokens : BTreeMap<usize,OmegaTokens>
+}
+
+impl OmegaMarking {
+    pub fn new(tokens : BTreeMap<usize,OmegaTokens>) -> Self {
+        let tokens = tokens.into_iter().filter(|(_,toks)| *toks != OmegaTokens::Finite(0)).collect();
+        Self { tokens }
+    }
+
+    pub fn from_marking(marking : &Marking) -> Self {
+        Self::new(marking.iter_tokens().map(|(place_id,toks)| (*place_id,OmegaTokens::Finite(*toks))).collect())
+    }
+
+    pub fn get_tokens_at_place(&self, place_id : &usize) -> OmegaTokens {
+        match self.tokens.get(place_id) {
+            Some(toks) => *toks,
+            None => OmegaTokens::Finite(0)
+        }
+    }
+
+    pub fn iter_tokens(&self) -> impl Iterator<Item=(&usize,&OmegaTokens)> {
+        self.tokens.iter()
+    }
+
+    /// the places which contain ω tokens
+    pub fn iter_omega_places(&self) -> impl Iterator<Item=&usize> {
+        self.tokens.iter().filter(|(_,toks)| toks.is_omega()).map(|(place_id,_)| place_id)
+    }
+
+    /// the corresponding marking, if no place contains ω tokens
+    pub fn to_marking(&self) -> Option<Marking> {
+        let mut tokens = BTreeMap::new();
+        for (place_id,toks) in &self.tokens {
+            match toks {
+                OmegaTokens::Finite(toks) => {tokens.insert(*place_id, *toks);},
+                OmegaTokens::Omega => {return None;}
+            }
+        }
+        Some(Marking::new(tokens))
+    }
+
+    /// whether each place contains at least as many tokens as in the other extended marking
+    pub fn covers(&self, other : &OmegaMarking) -> bool {
+        other.tokens.iter().all(|(place_id,toks)| self.get_tokens_at_place(place_id) >= *toks)
+    }
+
+    /// whether each place contains at least as many tokens as in the marking
+    pub fn covers_marking(&self, marking : &Marking) -> bool {
+        marking.iter_tokens().all(|(place_id,toks)| self.get_tokens_at_place(place_id).covers(*toks))
+    }
+
+    /// fires a transition, considering only its preset, postset and read arcs
+    /// (places with ω tokens keep ω tokens)
+    pub(crate) fn try_fire(&self, transition : &PetriTransition) -> Option<OmegaMarking> {
+        let is_enabled = transition.iter_preset_tokens()
+            .chain(transition.iter_read_tokens())
+            .all(|(place_id,req)| self.get_tokens_at_place(place_id).covers(*req));
+        if !is_enabled {
+            return None;
+        }
+        let mut tokens = self.tokens.clone();
+        for (place_id,req) in transition.iter_preset_tokens() {
+            if let Some(OmegaTokens::Finite(toks)) = tokens.get_mut(place_id) {
+                *toks -= *req;
+            }
+        }
+        for (place_id,produced) in transition.iter_postset_tokens() {
+            match tokens.get_mut(place_id) {
+                Some(OmegaTokens::Finite(toks)) => {*toks += *produced;},
+                Some(OmegaTokens::Omega) => {},
+                None => {tokens.insert(*place_id, OmegaTokens::Finite(*produced));}
+            }
+        }
+        Some(Self::new(tokens))
+    }
+}
@@ -0,0 +1,68 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+/// stochastic timing of a transition in a Generalized Stochastic Petri Net (GSPN)
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PetriStochasticTiming {
+    /// timed transition, which fires after an exponentially distributed delay with the given rate
+    Exponential(f64),
+    /// immediate transition, which fires in zero time
+    /// the weight is used to resolve conflicts between simultaneously enabled immediate transitions
+    Immediate(f64)
+}
+
+impl PetriStochasticTiming {
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, PetriStochasticTiming::Immediate(_))
+    }
+
+    /// the rate of a timed transition or the weight of an immediate transition
+    pub fn get_value(&self) -> f64 {
+        match self {
+            PetriStochasticTiming::Exponential(rate) => *rate,
+            PetriStochasticTiming::Immediate(weight) => *weight
+        }
+    }
+}
+
+/// transitions without stochastic timing are timed transitions with rate 1
+impl Default for PetriStochasticTiming {
+    fn default() -> Self {
+        PetriStochasticTiming::Exponential(1.0)
+    }
+}
+
+// rates and weights are compared bitwise so that transitions can still be compared and hashed
+impl PartialEq for PetriStochasticTiming {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_immediate() == other.is_immediate() && self.get_value().to_bits() == other.get_value().to_bits()
+    }
+}
+
+impl Eq for PetriStochasticTiming {}
+
+impl std::fmt::Display for PetriStochasticTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PetriStochasticTiming::Exponential(rate) => write!(f, "rate:{}", rate),
+            PetriStochasticTiming::Immediate(weight) => write!(f, "imm:{}", weight)
+        }
+    }
+}
@@ -14,61 +14,195 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{collections::{BTreeMap, HashMap}, rc::Rc};
+use std::{collections::{BTreeMap, HashMap, HashSet}, sync::Arc};
 
-use crate::model::{label::PetriTransitionLabel, marking::Marking};
+use crate::model::{interval::PetriFiringInterval, label::PetriTransitionLabel, marking::Marking, stochastic::PetriStochasticTiming};
 
 
 
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PetriTransition {
-    pub transition_label : Option<Rc<PetriTransitionLabel>>,
+    pub transition_label : Option<Arc<PetriTransitionLabel>>,
     pub(crate) preset_tokens    : HashMap<usize,u32>,
     pub(crate) postset_tokens   : HashMap<usize,u32>,
+    /// inhibitor arcs, from a place id to a threshold
+    /// the transition may only fire if the place contains strictly less tokens than the threshold
+    /// (e.g., a threshold of 1 tests for the emptiness of the place)
+    pub(crate) inhibitor_tokens : HashMap<usize,u32>,
+    /// reset arcs, i.e., the places that are emptied when the transition fires
+    /// (regardless of their content, after the tokens of the preset have been consumed
+    /// and before the tokens of the postset are produced)
+    pub(crate) reset_places : HashSet<usize>,
+    /// read (or test) arcs, from a place id to a number of tokens
+    /// the transition may only fire if the place contains at least that number of tokens
+    /// but, unlike for the preset, these tokens are not consumed
+    pub(crate) read_tokens : HashMap<usize,u32>,
+    /// the priority of the transition (the higher the value, the higher the priority)
+    /// whenever several transitions are enabled, only those with the maximal priority may fire
+    /// a transition without priority has the lowest priority (i.e., 0)
+    pub(crate) priority : Option<u32>,
+    /// the static firing interval of the transition, when considering the net as a Time Petri Net
+    /// a transition without firing interval may fire at any moment once enabled
+    pub(crate) firing_interval : Option<PetriFiringInterval>,
+    /// the rate or weight of the transition, when considering the net as a Generalized Stochastic Petri Net
+    /// a transition without stochastic timing is a timed transition with rate 1
+    pub(crate) stochastic_timing : Option<PetriStochasticTiming>,
 }
 
+
+pub(crate) fn shift_place_ids_after_removal(
+    place_to_num_toks : &mut HashMap<usize,u32>,
+    place_to_remove_id : usize
+) {
+    let mut new_place_to_num_toks = HashMap::new();
+    for (place_id, num_toks) in place_to_num_toks.drain() {
+        match usize::cmp(&place_id,&place_to_remove_id) {
+            std::cmp::Ordering::Less => {
+                new_place_to_num_toks.insert(place_id,num_toks);
+            },
+            std::cmp::Ordering::Equal => {
+                panic!("illegal removal of place that is referred to in transition");
+            },
+            std::cmp::Ordering::Greater => {
+                new_place_to_num_toks.insert(place_id - 1,num_toks);
+            }
+        }
+    }
+    *place_to_num_toks = new_place_to_num_toks;
+}
+
+fn shift_place_ids_in_set_after_removal(
+    places : &mut HashSet<usize>,
+    place_to_remove_id : usize
+) {
+    let mut new_places = HashSet::new();
+    for place_id in places.drain() {
+        match usize::cmp(&place_id,&place_to_remove_id) {
+            std::cmp::Ordering::Less => {
+                new_places.insert(place_id);
+            },
+            std::cmp::Ordering::Equal => {
+                panic!("illegal removal of place that is referred to in transition");
+            },
+            std::cmp::Ordering::Greater => {
+                new_places.insert(place_id - 1);
+            }
+        }
+    }
+    *places = new_places;
+}
+
+
 impl PetriTransition {
     pub fn remove_place(&mut self, place_to_remove_id : usize) {
-        let mut new_preset_tokens = HashMap::new();
-        for (preset_place_id, num_toks) in self.preset_tokens.drain() {
-            match usize::cmp(&preset_place_id,&place_to_remove_id) {
-                std::cmp::Ordering::Less => {
-                    new_preset_tokens.insert(preset_place_id,num_toks);
-                },
-                std::cmp::Ordering::Equal => {
-                    panic!("illegal removal of place that is referred to in transition");
-                },
-                std::cmp::Ordering::Greater => {
-                    new_preset_tokens.insert(preset_place_id - 1,num_toks);
-                }
+        shift_place_ids_after_removal(&mut self.preset_tokens, place_to_remove_id);
+        shift_place_ids_after_removal(&mut self.postset_tokens, place_to_remove_id);
+        shift_place_ids_after_removal(&mut self.inhibitor_tokens, place_to_remove_id);
+        shift_place_ids_in_set_after_removal(&mut self.reset_places, place_to_remove_id);
+        shift_place_ids_after_removal(&mut self.read_tokens, place_to_remove_id);
+    }
+
+    /// the same transition in which each place id is replaced by its image through the given map
+    /// (arcs towards places having the same image are merged, adding up their weights
+    /// and keeping the most restrictive inhibitor threshold)
+    pub(crate) fn map_places(&self, place_map : impl Fn(usize) -> usize) -> PetriTransition {
+        let map_tokens = |place_to_num_toks : &HashMap<usize,u32>| {
+            let mut mapped = HashMap::new();
+            for (place_id,num_toks) in place_to_num_toks {
+                *mapped.entry(place_map(*place_id)).or_insert(0) += *num_toks;
             }
+            mapped
+        };
+        let mut inhibitor_tokens : HashMap<usize,u32> = HashMap::new();
+        for (place_id,threshold) in &self.inhibitor_tokens {
+            let entry = inhibitor_tokens.entry(place_map(*place_id)).or_insert(*threshold);
+            *entry = u32::min(*entry, *threshold);
         }
-        self.preset_tokens = new_preset_tokens;
-        // ***
-        let mut new_postset_tokens = HashMap::new();
-        for (postset_place_id, num_toks) in self.postset_tokens.drain() {
-            match usize::cmp(&postset_place_id,&place_to_remove_id) {
-                std::cmp::Ordering::Less => {
-                    new_postset_tokens.insert(postset_place_id,num_toks);
-                },
-                std::cmp::Ordering::Equal => {
-                    panic!("illegal removal of place that is referred to in transition");
-                },
-                std::cmp::Ordering::Greater => {
-                    new_postset_tokens.insert(postset_place_id - 1,num_toks);
-                }
-            }
+        Self {
+            transition_label : self.transition_label.clone(),
+            preset_tokens : map_tokens(&self.preset_tokens),
+            postset_tokens : map_tokens(&self.postset_tokens),
+            inhibitor_tokens,
+            reset_places : self.reset_places.iter().map(|place_id| place_map(*place_id)).collect(),
+            read_tokens : map_tokens(&self.read_tokens),
+            priority : self.priority,
+            firing_interval : self.firing_interval,
+            stochastic_timing : self.stochastic_timing
         }
-        self.postset_tokens = new_postset_tokens;
     }
 
     pub fn new(
-        transition_label : Option<Rc<PetriTransitionLabel>>,
+        transition_label : Option<Arc<PetriTransitionLabel>>,
         preset_tokens: HashMap<usize,u32>, 
         postset_tokens: HashMap<usize,u32>
     ) -> Self {
-        Self { transition_label, preset_tokens, postset_tokens }
+        Self { 
+            transition_label, 
+            preset_tokens, 
+            postset_tokens, 
+            inhibitor_tokens : HashMap::new(),
+            reset_places : HashSet::new(),
+            read_tokens : HashMap::new(),
+            priority : None,
+            firing_interval : None,
+            stochastic_timing : None
+        }
+    }
+
+    /// adds inhibitor arcs to the transition, from a place id to a threshold
+    pub fn with_inhibitor_tokens(mut self, inhibitor_tokens : HashMap<usize,u32>) -> Self {
+        self.inhibitor_tokens = inhibitor_tokens;
+        self
+    }
+
+    /// adds reset arcs to the transition, towards the places that it empties when firing
+    pub fn with_reset_places(mut self, reset_places : HashSet<usize>) -> Self {
+        self.reset_places = reset_places;
+        self
+    }
+
+    /// adds read arcs to the transition, from a place id to the number of tokens that it tests for
+    pub fn with_read_tokens(mut self, read_tokens : HashMap<usize,u32>) -> Self {
+        self.read_tokens = read_tokens;
+        self
+    }
+
+    /// sets the priority of the transition
+    pub fn with_priority(mut self, priority : u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn get_priority(&self) -> u32 {
+        self.priority.unwrap_or(0)
+    }
+
+    /// sets the static firing interval of the transition
+    pub fn with_firing_interval(mut self, firing_interval : PetriFiringInterval) -> Self {
+        self.firing_interval = Some(firing_interval);
+        self
+    }
+
+    pub fn get_firing_interval(&self) -> PetriFiringInterval {
+        self.firing_interval.unwrap_or_default()
+    }
+
+    /// sets the rate (for a timed transition) or the weight (for an immediate transition) of the transition
+    pub fn with_stochastic_timing(mut self, stochastic_timing : PetriStochasticTiming) -> Self {
+        self.stochastic_timing = Some(stochastic_timing);
+        self
+    }
+
+    pub fn get_stochastic_timing(&self) -> PetriStochasticTiming {
+        self.stochastic_timing.unwrap_or_default()
+    }
+
+    /// whether the transition has inhibitor, reset or read arcs,
+    /// i.e., arcs which do not belong to plain Place/Transition nets
+    pub fn has_extended_arcs(&self) -> bool {
+        !self.inhibitor_tokens.is_empty() || !self.reset_places.is_empty() || !self.read_tokens.is_empty()
     }
 
     pub fn number_of_preset_places(&self) -> usize {
@@ -78,6 +212,18 @@ impl PetriTransition {
     pub fn number_of_postset_places(&self) -> usize {
         self.postset_tokens.keys().len()
     }
+
+    pub fn number_of_inhibitor_places(&self) -> usize {
+        self.inhibitor_tokens.keys().len()
+    }
+
+    pub fn number_of_reset_places(&self) -> usize {
+        self.reset_places.len()
+    }
+
+    pub fn number_of_read_places(&self) -> usize {
+        self.read_tokens.keys().len()
+    }
     
     pub fn iter_preset_tokens(&self) -> impl Iterator<Item=(&usize,&u32)> {
         self.preset_tokens.iter()
@@ -87,10 +233,50 @@ impl PetriTransition {
         self.postset_tokens.iter()
     }
 
+    pub fn iter_inhibitor_tokens(&self) -> impl Iterator<Item=(&usize,&u32)> {
+        self.inhibitor_tokens.iter()
+    }
+
+    pub fn iter_reset_places(&self) -> impl Iterator<Item=&usize> {
+        self.reset_places.iter()
+    }
+
+    pub fn iter_read_tokens(&self) -> impl Iterator<Item=(&usize,&u32)> {
+        self.read_tokens.iter()
+    }
+
     pub fn unwrap(self) -> (impl Iterator<Item=(usize,u32)>,impl Iterator<Item=(usize,u32)>) {
         (self.preset_tokens.into_iter(),self.postset_tokens.into_iter())
     }
 
+    /// whether the transition is enabled in the given marking, regardless of priorities
+    pub fn is_enabled(&self, marking : &Marking) -> bool {
+        let toks_at = |place_id : &usize| {
+            match marking.get_num_toks_at_place(place_id) {
+                Some(toks) => {*toks},
+                None => {0}
+            }
+        };
+        self.preset_tokens.iter().all(|(place_id,req)| toks_at(place_id) >= *req)
+        && self.read_tokens.iter().all(|(place_id,req)| toks_at(place_id) >= *req)
+        && self.inhibitor_tokens.iter().all(|(place_id,threshold)| toks_at(place_id) < *threshold)
+    }
+
+    /// the marking obtained by only consuming the tokens of the preset
+    /// (i.e., the intermediate marking in between the consumption and the production of tokens)
+    pub(crate) fn consume_preset(&self, from_marking : &Marking) -> Marking {
+        let mut new_tokens = from_marking.tokens.clone();
+        for (place_id,req) in self.preset_tokens.iter() {
+            if let Some(toks) = new_tokens.get_mut(place_id) {
+                *toks = toks.saturating_sub(*req);
+                if *toks == 0 {
+                    new_tokens.remove(place_id);
+                }
+            }
+        }
+        Marking::new(new_tokens)
+    }
+
     pub fn try_fire(&self, net_place_num : usize,from_marking : &Marking) -> Option<Marking> {
         let mut new_tokens = BTreeMap::new();
         for place_id in 0..net_place_num {
@@ -98,6 +284,14 @@ impl PetriTransition {
                 Some(toks) => {*toks},
                 None => {0}
             };
+            if let Some(inhibitor_threshold) = self.inhibitor_tokens.get(&place_id) 
+                && toks_at_place_before_firing >= *inhibitor_threshold {
+                return None;
+            }
+            if let Some(read_toks) = self.read_tokens.get(&place_id) 
+                && toks_at_place_before_firing < *read_toks {
+                return None;
+            }
             let required_toks_at_place_to_fire = match self.preset_tokens.get(&place_id) {
                 Some(toks) => {*toks},
                 None => {0}
@@ -107,7 +301,11 @@ impl PetriTransition {
                     Some(toks) => {*toks},
                     None => {0}
                 };
-                let remaining_tokens = toks_at_place_before_firing - required_toks_at_place_to_fire + added_toks_at_place_after_firing;
+                let remaining_tokens = if self.reset_places.contains(&place_id) {
+                    added_toks_at_place_after_firing
+                } else {
+                    toks_at_place_before_firing - required_toks_at_place_to_fire + added_toks_at_place_after_firing
+                };
                 if remaining_tokens > 0 {
                     new_tokens.insert(place_id,remaining_tokens);
                 }
@@ -0,0 +1,108 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::transition::PetriTransition;
+use crate::model_checking::coverability::{check_monotonic_firing_rule, PetriCoverabilityGenerationError};
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PetriCoverabilityVerdict {
+    /// no reachable marking covers the target marking
+    NotCoverable,
+    /// firing these transitions from the initial marking leads to a marking which covers the target marking
+    Coverable{firing_sequence:Vec<usize>}
+}
+
+
+/// the minimal marking from which firing the transition leads to a marking covering the given one
+fn get_minimal_predecessor(transition : &PetriTransition, marking : &Marking) -> Marking {
+    let mut tokens : BTreeMap<usize,u32> = BTreeMap::new();
+    for (place_id,toks) in marking.iter_tokens() {
+        let produced = transition.iter_postset_tokens()
+            .find(|(post_place_id,_)| *post_place_id == place_id)
+            .map(|(_,produced)| *produced)
+            .unwrap_or(0);
+        tokens.insert(*place_id, toks.saturating_sub(produced));
+    }
+    for (place_id,req) in transition.iter_preset_tokens() {
+        *tokens.entry(*place_id).or_insert(0) += *req;
+    }
+    for (place_id,req) in transition.iter_read_tokens() {
+        let toks = tokens.entry(*place_id).or_insert(0);
+        *toks = u32::max(*toks, *req);
+    }
+    tokens.retain(|_,toks| *toks > 0);
+    Marking::new(tokens)
+}
+
+
+/// checks whether a marking covering the target (partial) marking is reachable from the initial marking
+/// using the backward reachability algorithm of well-structured transition systems
+///
+/// the set of markings from which the target can be covered is upward-closed
+/// and is computed as a finite basis of minimal markings, starting from the target marking
+/// and adding the minimal predecessors of the basis elements until a fixpoint is reached
+/// (which terminates as there are no infinite antichains of markings)
+///
+/// each element of the basis remembers the transition which leads towards the target
+/// so that a witness firing sequence can be given as soon as the initial marking covers an element of the basis
+pub fn check_coverability(
+    petri : &PetriNet,
+    initial_marking : &Marking,
+    target_marking : &Marking
+) -> Result<PetriCoverabilityVerdict,PetriCoverabilityGenerationError> {
+    check_monotonic_firing_rule(petri)?;
+    let target = Marking::new(target_marking.iter_tokens().filter(|(_,toks)| **toks > 0).map(|(p,t)| (*p,*t)).collect());
+    // all the markings which have been in the basis
+    // each with the transition to fire and the marking it leads to
+    let mut elements : Vec<(Marking,Option<(usize,usize)>)> = vec![(target,None)];
+    // ids of the elements which are currently in the basis
+    let mut basis : Vec<usize> = vec![0];
+    let mut queue = VecDeque::from([0]);
+    while let Some(element_id) = queue.pop_front() {
+        if !basis.contains(&element_id) {
+            // was removed from the basis by a smaller element
+            continue;
+        }
+        if initial_marking.covers(&elements[element_id].0) {
+            let mut firing_sequence = Vec::new();
+            let mut current = element_id;
+            while let Some((transition_id,next)) = elements[current].1 {
+                firing_sequence.push(transition_id);
+                current = next;
+            }
+            return Ok(PetriCoverabilityVerdict::Coverable { firing_sequence });
+        }
+        for (transition_id,transition) in petri.get_transitions().iter().enumerate() {
+            let predecessor = get_minimal_predecessor(transition, &elements[element_id].0);
+            if basis.iter().any(|id| predecessor.covers(&elements[*id].0)) {
+                continue;
+            }
+            basis.retain(|id| !elements[*id].0.covers(&predecessor));
+            let predecessor_id = elements.len();
+            elements.push((predecessor,Some((transition_id,element_id))));
+            basis.push(predecessor_id);
+            queue.push_back(predecessor_id);
+        }
+    }
+    Ok(PetriCoverabilityVerdict::NotCoverable)
+}
@@ -0,0 +1,198 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{HashSet, VecDeque};
+
+use map_macro::btree_map;
+
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::omega::OmegaTokens;
+use crate::model_checking::backward_coverability::{check_coverability, PetriCoverabilityVerdict};
+use crate::model_checking::coverability::{check_monotonic_firing_rule, petri_to_coverability_graph, PetriCoverabilityGenerationError};
+use crate::model_checking::to_kripke::{PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement};
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PetriPlaceBoundsError {
+    /// the firing rule of the net is not monotonic for a certain reason
+    /// so that its reachable markings are enumerated, which may not terminate without a k-safeness requirement
+    MissingSafenessRequirement(PetriCoverabilityGenerationError),
+    /// the enumeration of the reachable markings failed
+    Enumeration(PetriKripkeGenerationError)
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PetriPlaceBound {
+    /// the maximal number of tokens of the place over all reachable markings
+    /// together with a reachable marking in which it is reached
+    Bounded{bound:u32, witness:Marking},
+    Unbounded
+}
+
+impl PetriPlaceBound {
+    pub fn get_bound(&self) -> Option<u32> {
+        match self {
+            PetriPlaceBound::Bounded { bound, .. } => Some(*bound),
+            PetriPlaceBound::Unbounded => None
+        }
+    }
+}
+
+
+/// the exact bound of each place of a net
+pub struct PetriPlaceBounds {
+    /// bounds indexed by place id
+    pub bounds : Vec<PetriPlaceBound>
+}
+
+impl PetriPlaceBounds {
+    pub fn get_place_bound(&self, place_id : usize) -> &PetriPlaceBound {
+        &self.bounds[place_id]
+    }
+
+    pub fn iter_unbounded_places(&self) -> impl Iterator<Item=usize> + '_ {
+        self.bounds.iter().enumerate()
+            .filter(|(_,bound)| **bound == PetriPlaceBound::Unbounded)
+            .map(|(place_id,_)| place_id)
+    }
+
+    /// the smallest k for which the net is k-safe, if it is bounded
+    pub fn get_safeness(&self) -> Option<u32> {
+        let mut safeness = 0;
+        for bound in &self.bounds {
+            safeness = u32::max(safeness, bound.get_bound()?);
+        }
+        Some(safeness)
+    }
+}
+
+
+/// computes the exact bound of each place over the markings reachable from the initial marking
+///
+/// place capacities are first compiled into complementary places (which leaves nets without capacities unchanged)
+/// then, if the resulting net has a monotonic firing rule (no inhibitor or reset arcs and no distinct priorities)
+/// the unbounded places and the bounds of the other places are read on the coverability graph
+/// and the witnesses of the bounds are obtained by replaying the firing sequences found by backward coverability
+///
+/// for other nets, the reachable markings are enumerated exhaustively
+/// and, as boundedness is undecidable e.g., with inhibitor arcs,
+/// a k-safeness requirement is mandatory to stop the enumeration as for "petri_to_kripke"
+pub fn compute_place_bounds(
+    petri : &PetriNet,
+    initial_marking : &Marking,
+    req : &PetriKripkeGenerationSafenessRequirement
+) -> Result<PetriPlaceBounds,PetriPlaceBoundsError> {
+    let (compiled, compiled_marking, _) = petri.compile_capacities_into_complementary_places(initial_marking);
+    if let Err(reason) = check_monotonic_firing_rule(&compiled) {
+        if let PetriKripkeGenerationSafenessRequirement::No = req {
+            return Err(PetriPlaceBoundsError::MissingSafenessRequirement(reason));
+        }
+        return compute_place_bounds_by_enumeration(petri, initial_marking, req)
+            .map_err(PetriPlaceBoundsError::Enumeration);
+    }
+    let graph = petri_to_coverability_graph(&compiled, &compiled_marking).unwrap();
+    let mut bounds = Vec::new();
+    for place_id in 0..petri.get_places().len() {
+        let mut bound = 0;
+        let mut witness_node = 0;
+        let mut is_unbounded = false;
+        for (node_id,marking) in graph.markings.iter().enumerate() {
+            match marking.get_tokens_at_place(&place_id) {
+                OmegaTokens::Omega => {
+                    is_unbounded = true;
+                    break;
+                },
+                OmegaTokens::Finite(toks) => {
+                    if toks > bound {
+                        bound = toks;
+                        witness_node = node_id;
+                    }
+                }
+            }
+        }
+        if is_unbounded {
+            bounds.push(PetriPlaceBound::Unbounded);
+            continue;
+        }
+        // the node itself is a reachable marking if it does not contain ω tokens
+        // otherwise a reachable marking with as many tokens in the place is found backwards
+        let compiled_witness = match graph.markings[witness_node].to_marking() {
+            Some(marking) => marking,
+            None => {
+                let target = Marking::new(btree_map! {place_id=>bound});
+                match check_coverability(&compiled, &compiled_marking, &target).unwrap() {
+                    PetriCoverabilityVerdict::Coverable { firing_sequence } => {
+                        let mut marking = compiled_marking.clone();
+                        for transition_id in firing_sequence {
+                            marking = compiled.get_transitions()[transition_id].try_fire(compiled.get_places().len(), &marking).unwrap();
+                        }
+                        marking
+                    },
+                    PetriCoverabilityVerdict::NotCoverable => {
+                        unreachable!("the finite values of the coverability graph are reachable")
+                    }
+                }
+            }
+        };
+        // the complementary places come after the places of the original net
+        let witness = Marking::new(
+            compiled_witness.iter_tokens()
+                .filter(|(place_id,_)| **place_id < petri.get_places().len())
+                .map(|(place_id,toks)| (*place_id,*toks))
+                .collect()
+        );
+        bounds.push(PetriPlaceBound::Bounded { bound, witness });
+    }
+    Ok(PetriPlaceBounds { bounds })
+}
+
+
+fn compute_place_bounds_by_enumeration(
+    petri : &PetriNet,
+    initial_marking : &Marking,
+    req : &PetriKripkeGenerationSafenessRequirement
+) -> Result<PetriPlaceBounds,PetriKripkeGenerationError> {
+    let mut bounds : Vec<(u32,Marking)> = (0..petri.get_places().len())
+        .map(|place_id| (*initial_marking.get_num_toks_at_place(&place_id).unwrap_or(&0), initial_marking.clone()))
+        .collect();
+    let mut visited = HashSet::from([initial_marking.clone()]);
+    let mut queue = VecDeque::from([initial_marking.clone()]);
+    while let Some(marking) = queue.pop_front() {
+        for transition_id in petri.get_fireable_transitions(&marking) {
+            let new_marking = petri.get_transitions()[transition_id].try_fire(petri.get_places().len(), &marking).unwrap();
+            if visited.contains(&new_marking) {
+                continue;
+            }
+            req.check(&new_marking, transition_id)?;
+            for (place_id,toks) in new_marking.iter_tokens() {
+                if *toks > bounds[*place_id].0 {
+                    bounds[*place_id] = (*toks, new_marking.clone());
+                }
+            }
+            visited.insert(new_marking.clone());
+            queue.push_back(new_marking);
+        }
+    }
+    Ok(
+        PetriPlaceBounds {
+            bounds : bounds.into_iter().map(|(bound,witness)| PetriPlaceBound::Bounded { bound, witness }).collect()
+        }
+    )
+}
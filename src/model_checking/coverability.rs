@@ -0,0 +1,169 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeSet, HashMap};
+
+use itertools::Itertools;
+
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::omega::{OmegaMarking, OmegaTokens};
+
+
+
+
+/// coverability analyses rely on the monotonicity of the firing rule
+/// (i.e., a transition which can fire from a marking can fire from any larger marking)
+/// which does not hold in the presence of the following features
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PetriCoverabilityGenerationError {
+    InhibitorArc{transition_id:usize},
+    ResetArc{transition_id:usize},
+    /// transitions have distinct priorities
+    Priorities,
+    /// places with capacities may be compiled away beforehand into complementary places
+    PlaceCapacity{place_id:usize}
+}
+
+
+/// the Karp-Miller coverability graph of a net
+/// whose nodes are extended markings, in which places that may contain arbitrarily many tokens contain ω tokens
+///
+/// every reachable marking is covered by a node of the graph
+/// and every node of the graph is the limit of an increasing sequence of reachable markings
+pub struct PetriCoverabilityGraph {
+    pub markings : Vec<OmegaMarking>,
+    /// for each node, its outgoing edges, as pairs of a transition id and a target node
+    pub edges : Vec<Vec<(usize,usize)>>
+}
+
+impl PetriCoverabilityGraph {
+    /// the places which may contain arbitrarily many tokens
+    pub fn get_unbounded_places(&self) -> BTreeSet<usize> {
+        self.markings.iter().flat_map(|marking| marking.iter_omega_places().cloned()).collect()
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.markings.iter().all(|marking| marking.iter_omega_places().next().is_none())
+    }
+
+    /// the maximal number of tokens of a place over all reachable markings, if it is bounded
+    pub fn get_place_bound(&self, place_id : &usize) -> Option<u32> {
+        let mut bound = 0;
+        for marking in &self.markings {
+            match marking.get_tokens_at_place(place_id) {
+                OmegaTokens::Finite(toks) => {bound = u32::max(bound, toks);},
+                OmegaTokens::Omega => {return None;}
+            }
+        }
+        Some(bound)
+    }
+
+    /// whether a reachable marking covers the given marking
+    pub fn is_coverable(&self, marking : &Marking) -> bool {
+        self.markings.iter().any(|node| node.covers_marking(marking))
+    }
+
+    /// whether a reachable marking contains at least a certain number of tokens in a place
+    pub fn can_place_reach(&self, place_id : usize, num_toks : u32) -> bool {
+        self.markings.iter().any(|node| node.get_tokens_at_place(&place_id).covers(num_toks))
+    }
+}
+
+
+/// checks that the firing rule of the net is monotonic
+pub(crate) fn check_monotonic_firing_rule(petri : &PetriNet) -> Result<(),PetriCoverabilityGenerationError> {
+    for (transition_id,transition) in petri.get_transitions().iter().enumerate() {
+        if transition.number_of_inhibitor_places() > 0 {
+            return Err(PetriCoverabilityGenerationError::InhibitorArc { transition_id });
+        }
+        if transition.number_of_reset_places() > 0 {
+            return Err(PetriCoverabilityGenerationError::ResetArc { transition_id });
+        }
+    }
+    if !petri.get_transitions().iter().map(|transition| transition.get_priority()).all_equal() {
+        return Err(PetriCoverabilityGenerationError::Priorities);
+    }
+    if let Some(place_id) = petri.iter_place_capacities().map(|(place_id,_)| *place_id).min() {
+        return Err(PetriCoverabilityGenerationError::PlaceCapacity { place_id });
+    }
+    Ok(())
+}
+
+
+/// builds the Karp-Miller coverability graph of a net from an initial marking
+/// which, contrary to the reachability graph, is always finite
+///
+/// it is built as the Karp-Miller tree, except that a marking which already appears in the graph is not explored again:
+/// whenever a marking strictly covers one of its ancestors in the tree,
+/// the places in which it has strictly more tokens are set to ω (as they can be pumped arbitrarily)
+pub fn petri_to_coverability_graph(
+    petri : &PetriNet,
+    initial_marking : &Marking
+) -> Result<PetriCoverabilityGraph,PetriCoverabilityGenerationError> {
+    check_monotonic_firing_rule(petri)?;
+    let initial = OmegaMarking::from_marking(initial_marking);
+    let mut markings = vec![initial.clone()];
+    let mut edges = vec![Vec::new()];
+    // the parent of each node in the underlying Karp-Miller tree
+    let mut parents : Vec<Option<usize>> = vec![None];
+    let mut nodes_map = HashMap::from([(initial,0)]);
+    let mut queue = vec![0];
+    while let Some(node_id) = queue.pop() {
+        for (transition_id,transition) in petri.get_transitions().iter().enumerate() {
+            let mut target = match markings[node_id].try_fire(transition) {
+                None => {continue;},
+                Some(target) => target
+            };
+            // acceleration, until no ancestor is strictly covered
+            loop {
+                let mut accelerated = false;
+                let mut ancestor = Some(node_id);
+                while let Some(ancestor_id) = ancestor {
+                    let ancestor_marking = &markings[ancestor_id];
+                    if target != *ancestor_marking && target.covers(ancestor_marking) {
+                        let pumped_places : Vec<usize> = target.iter_tokens()
+                            .filter(|(place_id,toks)| !toks.is_omega() && **toks > ancestor_marking.get_tokens_at_place(place_id))
+                            .map(|(place_id,_)| *place_id)
+                            .collect();
+                        for place_id in pumped_places {
+                            target.tokens.insert(place_id, OmegaTokens::Omega);
+                            accelerated = true;
+                        }
+                    }
+                    ancestor = parents[ancestor_id];
+                }
+                if !accelerated {
+                    break;
+                }
+            }
+            let target_id = match nodes_map.get(&target) {
+                Some(target_id) => *target_id,
+                None => {
+                    let target_id = markings.len();
+                    markings.push(target.clone());
+                    edges.push(Vec::new());
+                    parents.push(Some(node_id));
+                    nodes_map.insert(target, target_id);
+                    queue.push(target_id);
+                    target_id
+                }
+            };
+            edges[node_id].push((transition_id,target_id));
+        }
+    }
+    Ok(PetriCoverabilityGraph { markings, edges })
+}
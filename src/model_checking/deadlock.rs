@@ -0,0 +1,116 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use crate::model::label::PetriTransitionLabel;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model_checking::to_kripke::{PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement};
+
+
+
+
+/// a dead marking (in which no transition is fireable) together with a shortest firing sequence leading to it
+pub struct PetriDeadlockWitness {
+    /// the transitions fired from the initial marking
+    pub firing_sequence : Vec<usize>,
+    /// the markings along the firing sequence, from the initial marking to the dead marking
+    pub markings : Vec<Marking>
+}
+
+impl PetriDeadlockWitness {
+    pub fn get_dead_marking(&self) -> &Marking {
+        self.markings.last().unwrap()
+    }
+
+    pub fn get_labelled_firing_sequence(&self, petri_net : &PetriNet) -> Vec<Option<Arc<PetriTransitionLabel>>> {
+        self.firing_sequence.iter()
+            .map(|transition_id| petri_net.get_transitions()[*transition_id].transition_label.clone())
+            .collect()
+    }
+
+    /// a textual representation of the firing sequence e.g., "lock.unlock.lock"
+    /// in which unlabelled transitions are referred to by their stable identifiers
+    pub fn repr_with_labels(&self, petri_net : &PetriNet) -> String {
+        self.firing_sequence.iter()
+            .map(|transition_id| {
+                match &petri_net.get_transitions()[*transition_id].transition_label {
+                    Some(label) => label.label.clone(),
+                    None => petri_net.get_transition_stable_id(*transition_id).unwrap().to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+}
+
+
+/// searches the reachable markings breadth-first for dead markings
+/// (in which no transition is fireable, taking capacities into account)
+/// and gives a shortest firing sequence leading to each of them
+///
+/// if "stop_at_first" is set, the search stops at the first (hence closest) dead marking
+/// otherwise all the dead markings are found, by increasing distance from the initial marking
+///
+/// as for "petri_to_kripke", the k-safeness requirement can be used to stop the search on unbounded nets
+pub fn find_deadlocks(
+    petri : &PetriNet,
+    initial_marking : Marking,
+    stop_at_first : bool,
+    req : &PetriKripkeGenerationSafenessRequirement
+) -> Result<Vec<PetriDeadlockWitness>,PetriKripkeGenerationError> {
+    let mut markings = vec![initial_marking.clone()];
+    // for each explored marking, the transition and marking from which it was first reached
+    let mut parents : Vec<Option<(usize,usize)>> = vec![None];
+    let mut markings_ids = HashMap::from([(initial_marking,0)]);
+    let mut queue = VecDeque::from([0]);
+    let mut witnesses = Vec::new();
+    while let Some(marking_id) = queue.pop_front() {
+        let fireable = petri.get_fireable_transitions(&markings[marking_id]);
+        if fireable.is_empty() {
+            let mut firing_sequence = Vec::new();
+            let mut path = vec![marking_id];
+            let mut current = marking_id;
+            while let Some((transition_id,parent_id)) = parents[current] {
+                firing_sequence.push(transition_id);
+                path.push(parent_id);
+                current = parent_id;
+            }
+            firing_sequence.reverse();
+            let path_markings = path.into_iter().rev().map(|id| markings[id].clone()).collect();
+            witnesses.push(PetriDeadlockWitness { firing_sequence, markings : path_markings });
+            if stop_at_first {
+                break;
+            }
+            continue;
+        }
+        for transition_id in fireable {
+            let new_marking = petri.get_transitions()[transition_id].try_fire(petri.get_places().len(), &markings[marking_id]).unwrap();
+            if markings_ids.contains_key(&new_marking) {
+                continue;
+            }
+            req.check(&new_marking, transition_id)?;
+            let new_id = markings.len();
+            markings_ids.insert(new_marking.clone(), new_id);
+            markings.push(new_marking);
+            parents.push(Some((transition_id,marking_id)));
+            queue.push_back(new_id);
+        }
+    }
+    Ok(witnesses)
+}
@@ -0,0 +1,211 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use map_macro::hash_set;
+
+use crate::model::label::PetriTransitionLabel;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer};
+
+
+
+
+/// liveness levels of a transition, from the weakest to the strongest
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum PetriLivenessLevel {
+    /// L0 : the transition can never fire
+    Dead,
+    /// L1 : the transition can fire at least once (quasi-liveness)
+    L1Live,
+    /// L3 : the transition can fire infinitely often in some firing sequence
+    L3Live,
+    /// L4 : from any reachable marking, the transition can eventually fire
+    Live
+}
+
+impl std::fmt::Display for PetriLivenessLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PetriLivenessLevel::Dead => write!(f, "L0"),
+            PetriLivenessLevel::L1Live => write!(f, "L1"),
+            PetriLivenessLevel::L3Live => write!(f, "L3"),
+            PetriLivenessLevel::Live => write!(f, "L4")
+        }
+    }
+}
+
+
+pub struct PetriLivenessAnalysis {
+    /// the liveness level of each transition, indexed by transition id
+    pub levels : Vec<PetriLivenessLevel>,
+    /// the labels of the transitions which can fire at least once
+    pub quasi_live_labels : BTreeSet<Arc<PetriTransitionLabel>>,
+    /// the labels such that, from any reachable marking, a transition with that label can eventually fire
+    pub live_labels : BTreeSet<Arc<PetriTransitionLabel>>
+}
+
+impl PetriLivenessAnalysis {
+    pub fn get_transition_level(&self, transition_id : usize) -> PetriLivenessLevel {
+        self.levels[transition_id]
+    }
+
+    pub fn is_quasi_live(&self) -> bool {
+        self.levels.iter().all(|level| *level >= PetriLivenessLevel::L1Live)
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.levels.iter().all(|level| *level == PetriLivenessLevel::Live)
+    }
+}
+
+
+/// the strongly connected components of a graph (Tarjan's algorithm, without recursion)
+/// given as the component id of each node
+fn get_strongly_connected_components(edges : &[Vec<(usize,usize)>]) -> Vec<usize> {
+    let nodes_num = edges.len();
+    let mut indices : Vec<Option<usize>> = vec![None; nodes_num];
+    let mut lowlinks = vec![0; nodes_num];
+    let mut on_stack = vec![false; nodes_num];
+    let mut stack = Vec::new();
+    let mut components = vec![0; nodes_num];
+    let mut components_num = 0;
+    let mut next_index = 0;
+    for root in 0..nodes_num {
+        if indices[root].is_some() {
+            continue;
+        }
+        // each frame holds a node and the position of the next edge to explore
+        let mut call_stack = vec![(root,0)];
+        indices[root] = Some(next_index);
+        lowlinks[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((node,edge_pos)) = call_stack.pop() {
+            if let Some((_,target)) = edges[node].get(edge_pos) {
+                call_stack.push((node,edge_pos + 1));
+                match indices[*target] {
+                    None => {
+                        indices[*target] = Some(next_index);
+                        lowlinks[*target] = next_index;
+                        next_index += 1;
+                        stack.push(*target);
+                        on_stack[*target] = true;
+                        call_stack.push((*target,0));
+                    },
+                    Some(target_index) => {
+                        if on_stack[*target] {
+                            lowlinks[node] = usize::min(lowlinks[node], target_index);
+                        }
+                    }
+                }
+                continue;
+            }
+            // all the edges of the node have been explored
+            if let Some((parent,_)) = call_stack.last() {
+                lowlinks[*parent] = usize::min(lowlinks[*parent], lowlinks[node]);
+            }
+            if Some(lowlinks[node]) == indices[node] {
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    components[member] = components_num;
+                    if member == node {
+                        break;
+                    }
+                }
+                components_num += 1;
+            }
+        }
+    }
+    components
+}
+
+
+/// classifies the transitions of a net according to their liveness level
+/// using the strongly connected components of its reachability graph (as explored by "petri_to_kripke")
+///
+/// a transition is L3-live if it labels an edge within a strongly connected component
+/// and it is live if it labels an edge within each terminal strongly connected component
+/// (every reachable marking leads to a terminal component, from which all its edges can be taken again)
+pub fn compute_liveness_levels(
+    petri : &PetriNet,
+    initial_marking : Marking,
+    req : &PetriKripkeGenerationSafenessRequirement
+) -> Result<PetriLivenessAnalysis,PetriKripkeGenerationError> {
+    let kripke = petri_to_kripke(
+        petri, 
+        initial_marking, 
+        &PetriKripkeStateProducer::new(hash_set!{}), 
+        req
+    )?;
+    // without tagged transitions, there is exactly one Kripke state per reachable marking
+    let markings : Vec<Marking> = kripke.states.into_iter().map(|st| st.value_in_domain.marking).collect();
+    let markings_ids : HashMap<&Marking,usize> = markings.iter().enumerate().map(|(id,m)| (m,id)).collect();
+    let edges : Vec<Vec<(usize,usize)>> = markings.iter().map(|marking| {
+        petri.get_fireable_transitions(marking).into_iter().map(|tr_id| {
+            let target = petri.get_transitions()[tr_id].try_fire(petri.get_places().len(), marking).unwrap();
+            (tr_id, *markings_ids.get(&target).unwrap())
+        }).collect()
+    }).collect();
+    let components = get_strongly_connected_components(&edges);
+    let components_num = components.iter().max().map_or(0, |max| max + 1);
+    // a component is terminal if no edge leaves it
+    let mut is_terminal = vec![true; components_num];
+    // the transitions which label an edge within each component
+    let mut internal_transitions : Vec<BTreeSet<usize>> = vec![BTreeSet::new(); components_num];
+    let mut fired_transitions = BTreeSet::new();
+    for (source,source_edges) in edges.iter().enumerate() {
+        for (tr_id,target) in source_edges {
+            fired_transitions.insert(*tr_id);
+            if components[source] == components[*target] {
+                internal_transitions[components[source]].insert(*tr_id);
+            } else {
+                is_terminal[components[source]] = false;
+            }
+        }
+    }
+    let terminal_components : Vec<usize> = (0..components_num).filter(|comp| is_terminal[*comp]).collect();
+    let levels : Vec<PetriLivenessLevel> = (0..petri.get_transitions().len()).map(|tr_id| {
+        if terminal_components.iter().all(|comp| internal_transitions[*comp].contains(&tr_id)) {
+            PetriLivenessLevel::Live
+        } else if internal_transitions.iter().any(|internal| internal.contains(&tr_id)) {
+            PetriLivenessLevel::L3Live
+        } else if fired_transitions.contains(&tr_id) {
+            PetriLivenessLevel::L1Live
+        } else {
+            PetriLivenessLevel::Dead
+        }
+    }).collect();
+    // ***
+    let get_label = |tr_id : &usize| petri.get_transitions()[*tr_id].transition_label.clone();
+    let quasi_live_labels = fired_transitions.iter().filter_map(get_label).collect();
+    let live_labels = match terminal_components.split_first() {
+        None => BTreeSet::new(),
+        Some((first,others)) => {
+            let mut live_labels : BTreeSet<Arc<PetriTransitionLabel>> = internal_transitions[*first].iter().filter_map(get_label).collect();
+            for comp in others {
+                let labels : BTreeSet<Arc<PetriTransitionLabel>> = internal_transitions[*comp].iter().filter_map(get_label).collect();
+                live_labels.retain(|label| labels.contains(label));
+            }
+            live_labels
+        }
+    };
+    Ok(PetriLivenessAnalysis { levels, quasi_live_labels, live_labels })
+}
@@ -18,6 +18,13 @@ limitations under the License.
 pub mod state;
 pub mod to_kripke;
 pub mod props;
+pub mod state_class;
+pub mod to_state_class_graph;
 
 
 
+pub mod coverability;
+pub mod backward_coverability;
+pub mod bounds;
+pub mod liveness;
+pub mod deadlock;
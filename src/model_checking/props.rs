@@ -14,11 +14,11 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use citreelo::kripke::AtomicProposition;
 
-use crate::{model::{label::PetriTransitionLabel, marking::Marking}, model_checking::state::PetriKripkeState};
+use crate::{model::{label::PetriTransitionLabel, marking::Marking}, model_checking::{state::PetriKripkeState, state_class::PetriStateClass}};
 
 
 
@@ -66,25 +66,43 @@ impl TokensCountRelation {
 #[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
 pub enum BuiltinPetriAtomicProposition {
     TokensCount(TokensCountRelation, TokensCountAtom, TokensCountAtom),
-    PreviousTransitionLabelMustBe(Rc<PetriTransitionLabel>)
+    PreviousTransitionLabelMustBe(Arc<PetriTransitionLabel>)
 }
 
 
 
-impl AtomicProposition<PetriKripkeState> for BuiltinPetriAtomicProposition {
-    fn is_satisfied_on_state_domain(&self, state_domain : &PetriKripkeState) -> bool {
+impl BuiltinPetriAtomicProposition {
+    pub fn is_satisfied_on_marking(
+        &self, 
+        marking : &Marking, 
+        previous_tagged_transition_label : &Option<Arc<PetriTransitionLabel>>
+    ) -> bool {
         match self {
             BuiltinPetriAtomicProposition::TokensCount(rel, left, right) => {
-                let left_int = left.interpret_as_u32(&state_domain.marking);
-                let right_int = right.interpret_as_u32(&state_domain.marking);
+                let left_int = left.interpret_as_u32(marking);
+                let right_int = right.interpret_as_u32(marking);
                 rel.eval(left_int,right_int)
             },
             BuiltinPetriAtomicProposition::PreviousTransitionLabelMustBe(transition_label) => {
-                match &state_domain.previous_tagged_transition_label {
+                match previous_tagged_transition_label {
                     Some(previous_transition_label) => previous_transition_label == transition_label,
                     None => false,
                 }
             },
         }
     }
+}
+
+
+impl AtomicProposition<PetriKripkeState> for BuiltinPetriAtomicProposition {
+    fn is_satisfied_on_state_domain(&self, state_domain : &PetriKripkeState) -> bool {
+        self.is_satisfied_on_marking(&state_domain.marking, &state_domain.previous_tagged_transition_label)
+    }
+}
+
+
+impl AtomicProposition<PetriStateClass> for BuiltinPetriAtomicProposition {
+    fn is_satisfied_on_state_domain(&self, state_domain : &PetriStateClass) -> bool {
+        self.is_satisfied_on_marking(&state_domain.marking, &state_domain.previous_tagged_transition_label)
+    }
 }
\ No newline at end of file
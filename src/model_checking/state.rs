@@ -14,7 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::model::{label::PetriTransitionLabel, marking::Marking};
 
@@ -23,6 +23,7 @@ use crate::model::{label::PetriTransitionLabel, marking::Marking};
 
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PetriKripkeState {
     pub marking : Marking,
     /// when generating a Kripke state from a Petri Net,
@@ -30,11 +31,11 @@ pub struct PetriKripkeState {
     /// when generating the state space, after having fired once such transition,
     /// the target Kripke state will have this "previous_tagged_transition_label" attributed filled accordingly
     /// this allows a limited form of "past-tense" temporal reasoning
-    pub previous_tagged_transition_label : Option<Rc<PetriTransitionLabel>>
+    pub previous_tagged_transition_label : Option<Arc<PetriTransitionLabel>>
 }
 
 impl PetriKripkeState {
-    pub fn new(marking: Marking, previous_tagged_transition_label: Option<Rc<PetriTransitionLabel>>) -> Self {
+    pub fn new(marking: Marking, previous_tagged_transition_label: Option<Arc<PetriTransitionLabel>>) -> Self {
         Self { marking, previous_tagged_transition_label }
     }
 }
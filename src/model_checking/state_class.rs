@@ -0,0 +1,231 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use crate::model::{interval::PetriFiringInterval, label::PetriTransitionLabel, marking::Marking, net::PetriNet};
+
+
+
+
+fn add_bounds(left : Option<i64>, right : Option<i64>) -> Option<i64> {
+    match (left,right) {
+        (Some(x),Some(y)) => Some(x + y),
+        _ => None
+    }
+}
+
+fn is_tighter(candidate : Option<i64>, current : Option<i64>) -> bool {
+    match (candidate,current) {
+        (Some(x),Some(y)) => x < y,
+        (Some(_),None) => true,
+        (None,_) => false
+    }
+}
+
+
+/// the firing domain of a state class of a Time Petri Net
+/// i.e., the set of possible firing times of the enabled transitions,
+/// relative to the moment at which the state class was entered
+///
+/// it is encoded as a Difference Bound Matrix (DBM) in canonical form
+///
+/// see "Modeling and Verification of Time Dependent Systems Using Time Petri Nets" (Berthelot & Diaz, 1991)
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct PetriFiringDomain {
+    /// the ids of the enabled transitions, in increasing order
+    /// the transition at position i is associated to the variable of index i+1 in the DBM
+    pub(crate) transitions : Vec<usize>,
+    /// bounds[i][j] is an upper bound on θ_i - θ_j (None stands for +∞)
+    /// the variable of index 0 is the reference, which is always equal to 0
+    pub(crate) bounds : Vec<Vec<Option<i64>>>
+}
+
+impl PetriFiringDomain {
+
+    /// returns None if the domain is empty
+    fn from_static_intervals(intervals : Vec<(usize,PetriFiringInterval)>) -> Option<Self> {
+        let size = intervals.len() + 1;
+        let mut bounds = vec![vec![None;size];size];
+        for (index,row) in bounds.iter_mut().enumerate() {
+            row[index] = Some(0);
+        }
+        let mut transitions = Vec::new();
+        for (pos,(tr_id,interval)) in intervals.into_iter().enumerate() {
+            transitions.push(tr_id);
+            bounds[pos + 1][0] = interval.get_latest().map(i64::from);
+            bounds[0][pos + 1] = Some(-i64::from(interval.get_earliest()));
+        }
+        let mut domain = Self { transitions, bounds };
+        if domain.canonicalize() {
+            Some(domain)
+        } else {
+            None
+        }
+    }
+
+    /// the firing domain of the initial state class,
+    /// in which every enabled transition is associated to its static firing interval
+    /// returns None if it is empty
+    pub fn initial(petri : &PetriNet, initial_marking : &Marking) -> Option<Self> {
+        let intervals = petri.get_transitions().iter().enumerate()
+            .filter(|(tr_id,_)| petri.is_transition_enabled(*tr_id, initial_marking))
+            .map(|(tr_id,transition)| (tr_id, transition.get_firing_interval()))
+            .collect();
+        Self::from_static_intervals(intervals)
+    }
+
+    /// tightens all the bounds (Floyd-Warshall)
+    /// returns false if the domain is empty
+    fn canonicalize(&mut self) -> bool {
+        let size = self.bounds.len();
+        for k in 0..size {
+            for i in 0..size {
+                for j in 0..size {
+                    let through_k = add_bounds(self.bounds[i][k], self.bounds[k][j]);
+                    if is_tighter(through_k, self.bounds[i][j]) {
+                        self.bounds[i][j] = through_k;
+                    }
+                }
+            }
+        }
+        (0..size).all(|i| self.bounds[i][i].is_some_and(|x| x >= 0))
+    }
+
+    fn get_variable_index(&self, transition_id : usize) -> Option<usize> {
+        self.transitions.binary_search(&transition_id).ok().map(|pos| pos + 1)
+    }
+
+    pub fn iter_enabled_transitions(&self) -> impl Iterator<Item=&usize> {
+        self.transitions.iter()
+    }
+
+    /// the interval of possible firing times of an enabled transition
+    /// (a "latest" of None stands for +∞)
+    pub fn get_firing_times(&self, transition_id : usize) -> Option<(i64,Option<i64>)> {
+        let index = self.get_variable_index(transition_id)?;
+        let earliest = -self.bounds[0][index].unwrap();
+        Some((earliest, self.bounds[index][0]))
+    }
+
+    /// the domain restricted to the firing times at which
+    /// the transition fires before (or at the same time as) all the other enabled transitions
+    /// returns None if the transition cannot fire first
+    fn constrain_to_fire_first(&self, fired_index : usize) -> Option<Self> {
+        let mut constrained = self.clone();
+        for other_index in 1..self.bounds.len() {
+            if is_tighter(Some(0), constrained.bounds[fired_index][other_index]) {
+                constrained.bounds[fired_index][other_index] = Some(0);
+            }
+        }
+        if constrained.canonicalize() {
+            Some(constrained)
+        } else {
+            None
+        }
+    }
+
+    /// whether the transition is firable from the state class (marking + this domain)
+    /// i.e., it is enabled and it can fire before all the other enabled transitions
+    pub fn is_firable(&self, transition_id : usize) -> bool {
+        match self.get_variable_index(transition_id) {
+            None => false,
+            Some(index) => self.constrain_to_fire_first(index).is_some()
+        }
+    }
+
+    /// computes the marking and firing domain of the state class reached by firing the transition
+    /// returns None if the transition is not firable or if the resulting firing domain is empty
+    pub fn fire(
+        &self,
+        petri : &PetriNet,
+        marking : &Marking,
+        transition_id : usize
+    ) -> Option<(Marking,Self)> {
+        let fired_index = self.get_variable_index(transition_id)?;
+        let constrained = self.constrain_to_fire_first(fired_index)?;
+        let fired_transition = petri.get_transitions().get(transition_id).unwrap();
+        let new_marking = fired_transition.try_fire(petri.get_places().len(), marking)?;
+        let intermediate_marking = fired_transition.consume_preset(marking);
+        // an enabled transition is persistent if it was enabled before firing
+        // and remained enabled in the intermediate marking (the fired transition is never persistent)
+        // for persistent transitions, the firing times are shifted by the firing time of the fired transition
+        // the other enabled transitions are newly enabled and are associated to their static firing interval
+        let mut new_transitions = Vec::new();
+        let mut old_indices = Vec::new();
+        for tr_id in 0..petri.get_transitions().len() {
+            if petri.is_transition_enabled(tr_id, &new_marking) {
+                new_transitions.push(tr_id);
+                let is_persistent = tr_id != transition_id && petri.is_transition_enabled(tr_id, &intermediate_marking);
+                old_indices.push(
+                    if is_persistent {
+                        self.get_variable_index(tr_id)
+                    } else {
+                        None
+                    }
+                );
+            }
+        }
+        let size = new_transitions.len() + 1;
+        let mut bounds = vec![vec![None;size];size];
+        for (index,row) in bounds.iter_mut().enumerate() {
+            row[index] = Some(0);
+        }
+        for i in 1..size {
+            match old_indices[i - 1] {
+                Some(old_i) => {
+                    // θ'_i = θ_i - θ_f
+                    bounds[i][0] = constrained.bounds[old_i][fired_index];
+                    bounds[0][i] = constrained.bounds[fired_index][old_i];
+                    for j in 1..size {
+                        if let Some(old_j) = old_indices[j - 1] {
+                            bounds[i][j] = constrained.bounds[old_i][old_j];
+                        }
+                    }
+                },
+                None => {
+                    let interval = petri.get_transitions().get(new_transitions[i - 1]).unwrap().get_firing_interval();
+                    bounds[i][0] = interval.get_latest().map(i64::from);
+                    bounds[0][i] = Some(-i64::from(interval.get_earliest()));
+                }
+            }
+        }
+        let mut new_domain = Self { transitions : new_transitions, bounds };
+        if new_domain.canonicalize() {
+            Some((new_marking,new_domain))
+        } else {
+            None
+        }
+    }
+}
+
+
+
+/// a state class of a Time Petri Net i.e., a marking together with the firing domain of its enabled transitions
+/// it plays the same role as the PetriKripkeState for untimed nets
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct PetriStateClass {
+    pub marking : Marking,
+    pub firing_domain : PetriFiringDomain,
+    /// see the PetriKripkeState
+    pub previous_tagged_transition_label : Option<Arc<PetriTransitionLabel>>
+}
+
+impl PetriStateClass {
+    pub fn new(marking: Marking, firing_domain: PetriFiringDomain, previous_tagged_transition_label: Option<Arc<PetriTransitionLabel>>) -> Self {
+        Self { marking, firing_domain, previous_tagged_transition_label }
+    }
+}
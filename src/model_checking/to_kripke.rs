@@ -16,6 +16,7 @@ limitations under the License.
 
 
 use std::collections::{HashSet};
+use std::sync::Arc;
 
 use citreelo::kripke::{KripkeState, KripkeStructure};
 use map_macro::hash_map;
@@ -35,6 +36,20 @@ pub enum PetriKripkeGenerationSafenessRequirement {
     KSafeness(u32)
 }
 
+impl PetriKripkeGenerationSafenessRequirement {
+    /// checks the requirement on a marking that has been reached by firing a certain transition
+    pub fn check(&self, marking : &Marking, transition_id : usize) -> Result<(),PetriKripkeGenerationError> {
+        if let PetriKripkeGenerationSafenessRequirement::KSafeness(k) = self {
+            for (place_id,num_toks) in marking.iter_tokens() {
+                if num_toks > k {
+                    return Err(PetriKripkeGenerationError::KSafenessViolation { place_id: *place_id, transition_id })
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum PetriKripkeGenerationError {
     /// k-safeness is violated on a certain place after having fired a certain transition
@@ -51,6 +66,21 @@ impl PetriKripkeStateProducer {
         Self { tagged_transition_labels }
     }
 
+    /// the label of the transition, if it is one of the tagged transition labels
+    #[allow(clippy::collapsible_match)]
+    pub fn get_previous_transition_tag(&self, transition : &PetriTransition) -> Option<Arc<PetriTransitionLabel>> {
+        match &transition.transition_label {
+            None => {None},
+            Some(lab_ref) => {
+                if self.tagged_transition_labels.contains(lab_ref) {
+                    Some(lab_ref.clone())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
     pub fn try_reach_new_state(
         &self,
         net_place_num : usize,
@@ -60,23 +90,8 @@ impl PetriKripkeStateProducer {
         req : &PetriKripkeGenerationSafenessRequirement
     ) -> Result<Option<PetriKripkeState>,PetriKripkeGenerationError> {
         if let Some(new_marking) = transition.try_fire(net_place_num, &initial.marking) {
-            if let PetriKripkeGenerationSafenessRequirement::KSafeness(k) = req {
-                for (place_id,num_toks) in new_marking.iter_tokens() {
-                    if num_toks > k {
-                        return Err(PetriKripkeGenerationError::KSafenessViolation { place_id: *place_id, transition_id })
-                    }
-                }
-            }
-            let previous_transition_tag_id = match &transition.transition_label {
-                None => {None},
-                Some(lab_ref) => {
-                    if self.tagged_transition_labels.contains(lab_ref) {
-                        Some(lab_ref.clone())
-                    } else {
-                        None
-                    }
-                }
-            };
+            req.check(&new_marking, transition_id)?;
+            let previous_transition_tag_id = self.get_previous_transition_tag(transition);
             Ok(Some(
                 PetriKripkeState::new(new_marking, previous_transition_tag_id)
             ))
@@ -103,10 +118,12 @@ pub fn petri_to_kripke(
         let queue = vec![initial_state];
         (states,states_map,queue)
     };
-    let net_num_places = petri.places.len();
+    let net_num_places = petri.get_places().len();
     while let Some(origin_state) = queue.pop() {
         let origin_state_id = *states_map.get(&origin_state).unwrap();
-        for (transition_id,transition) in petri.transitions.iter().enumerate() {
+        // only the enabled transitions with maximal priority may fire
+        for transition_id in petri.get_fireable_transitions(&origin_state.marking) {
+            let transition = petri.get_transitions().get(transition_id).unwrap();
             if let Some(target_state) = state_producer.try_reach_new_state(
                 net_num_places, 
                 &origin_state, 
@@ -0,0 +1,92 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use citreelo::kripke::{KripkeState, KripkeStructure};
+use map_macro::hash_map;
+
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model_checking::state_class::{PetriFiringDomain, PetriStateClass};
+use crate::model_checking::to_kripke::{PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer};
+
+
+
+
+/// generates the state class graph of a Time Petri Net (Berthelot & Diaz)
+/// in which each Kripke state is a state class i.e., a marking together with the firing domain of its enabled transitions
+///
+/// the firing intervals of the transitions are taken into account, but their priorities are not
+///
+/// the state class graph is finite if and only if the net is bounded
+/// so the k-safeness requirement can be used to stop the generation as for "petri_to_kripke"
+pub fn petri_to_state_class_graph(
+    petri : &PetriNet,
+    initial_marking : Marking,
+    state_producer : &PetriKripkeStateProducer,
+    req : &PetriKripkeGenerationSafenessRequirement
+) -> Result<KripkeStructure<PetriStateClass>,PetriKripkeGenerationError> {
+    let (mut states, mut states_map, mut queue) = {
+        // the static firing intervals are never empty and are independent from one another
+        let initial_domain = PetriFiringDomain::initial(petri, &initial_marking)
+            .expect("the initial firing domain is not empty");
+        let initial_state = PetriStateClass::new(initial_marking, initial_domain, None);
+        let states_map = hash_map!{
+            initial_state.clone() => 0
+        };
+        let states = vec![
+            KripkeState::new(initial_state.clone(),Vec::new())
+        ];
+        let queue = vec![initial_state];
+        (states,states_map,queue)
+    };
+    while let Some(origin_state) = queue.pop() {
+        let origin_state_id = *states_map.get(&origin_state).unwrap();
+        let enabled_transitions : Vec<usize> = origin_state.firing_domain.iter_enabled_transitions().cloned().collect();
+        for transition_id in enabled_transitions {
+            if let Some((new_marking,new_domain)) = origin_state.firing_domain.fire(
+                petri,
+                &origin_state.marking,
+                transition_id
+            ) {
+                req.check(&new_marking, transition_id)?;
+                let transition = petri.get_transitions().get(transition_id).unwrap();
+                let target_state = PetriStateClass::new(
+                    new_marking,
+                    new_domain,
+                    state_producer.get_previous_transition_tag(transition)
+                );
+                let target_state_id = match states_map.get(&target_state) {
+                    None => {
+                        let id = states.len();
+                        states.push(KripkeState::new(target_state.clone(), Vec::new()));
+                        states_map.insert(target_state.clone(), id);
+                        queue.push(target_state);
+                        id
+                    },
+                    Some(id) => {
+                        *id
+                    }
+                };
+                let origin_state = states.get_mut(origin_state_id).unwrap();
+                if !origin_state.outgoing_transitions_targets.contains(&target_state_id) {
+                    origin_state.outgoing_transitions_targets.push(target_state_id);
+                }
+            }
+        }
+    }
+    Ok(KripkeStructure::new(states))
+}
@@ -0,0 +1,233 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use map_macro::hash_set;
+
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model::stochastic::PetriStochasticTiming;
+use crate::model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer};
+use crate::performance::linear::solve_linear_system;
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PetriCtmcGenerationError {
+    /// the generation of the reachability graph failed
+    ReachabilityGraph(PetriKripkeGenerationError),
+    /// some vanishing markings only lead to one another through immediate transitions,
+    /// so that time cannot progress (timeless trap)
+    TimelessTrap,
+    /// the immediate transitions that are fireable in a vanishing marking all have a zero weight
+    /// so that the probability of firing each of them is undefined
+    ZeroImmediateWeights{marking:Marking},
+    /// the rate of a timed transition is not a positive finite number
+    /// or the weight of an immediate transition is not a non-negative finite number
+    InvalidStochasticTiming{transition_id:usize}
+}
+
+
+/// the Continuous Time Markov Chain (CTMC) underlying a Generalized Stochastic Petri Net (GSPN)
+/// its states are the tangible markings of the net i.e., those in which no immediate transition is fireable
+pub struct PetriCtmc {
+    /// the tangible markings of the net, which are the states of the CTMC
+    pub tangible_markings : Vec<Marking>,
+    /// the probability of starting in each tangible marking
+    /// (if the initial marking is vanishing, several tangible markings may be reached in zero time)
+    pub initial_distribution : Vec<f64>,
+    /// for each tangible marking, the rates towards the other tangible markings
+    /// (self loops are not included as they do not change the state of the CTMC)
+    pub rates : Vec<HashMap<usize,f64>>,
+    /// for each tangible marking, the rate at which each transition of the net fires while in that marking
+    /// (the firings of immediate transitions when going through vanishing markings are included)
+    pub(crate) firing_rates : Vec<HashMap<usize,f64>>,
+    pub(crate) places_num : usize,
+    pub(crate) transitions_num : usize
+}
+
+
+/// the transitions that may fire from a marking, with their rate or weight and the id of the marking they lead to
+struct PetriMarkingSuccessors {
+    is_vanishing : bool,
+    successors : Vec<(usize,f64,usize)>
+}
+
+impl PetriMarkingSuccessors {
+    /// in a vanishing marking, only immediate transitions may fire (they have priority over timed transitions)
+    fn new(petri : &PetriNet, marking : &Marking, markings_ids : &HashMap<&Marking,usize>) -> Self {
+        let fireable = petri.get_fireable_transitions(marking);
+        let is_vanishing = fireable.iter().any(
+            |tr_id| petri.get_transitions().get(*tr_id).unwrap().get_stochastic_timing().is_immediate()
+        );
+        let mut successors = Vec::new();
+        for tr_id in fireable {
+            let transition = petri.get_transitions().get(tr_id).unwrap();
+            let timing = transition.get_stochastic_timing();
+            if timing.is_immediate() == is_vanishing {
+                let target_marking = transition.try_fire(petri.get_places().len(), marking).unwrap();
+                successors.push((tr_id, timing.get_value(), *markings_ids.get(&target_marking).unwrap()));
+            }
+        }
+        Self { is_vanishing, successors }
+    }
+}
+
+
+/// derives the CTMC of a GSPN from its reachability graph (as explored by "petri_to_kripke")
+///
+/// the rates of timed transitions and the weights of immediate transitions are given by their stochastic timing
+/// (transitions without stochastic timing are timed transitions of rate 1)
+/// rates must be positive and finite and weights must be non-negative and finite
+/// (otherwise an "InvalidStochasticTiming" error is returned)
+/// the weights of the immediate transitions that are fireable in the same marking must not all be zero
+/// (otherwise a "ZeroImmediateWeights" error is returned)
+///
+/// vanishing markings (in which immediate transitions are fireable) are eliminated:
+/// the probability of reaching each tangible marking from a vanishing marking is computed
+/// and the rates of the timed transitions leading to the vanishing marking are distributed accordingly
+pub fn petri_to_ctmc(
+    petri : &PetriNet,
+    initial_marking : Marking,
+    req : &PetriKripkeGenerationSafenessRequirement
+) -> Result<PetriCtmc,PetriCtmcGenerationError> {
+    for (transition_id,transition) in petri.get_transitions().iter().enumerate() {
+        let is_valid = match transition.get_stochastic_timing() {
+            PetriStochasticTiming::Exponential(rate) => rate.is_finite() && rate > 0.0,
+            PetriStochasticTiming::Immediate(weight) => weight.is_finite() && weight >= 0.0
+        };
+        if !is_valid {
+            return Err(PetriCtmcGenerationError::InvalidStochasticTiming { transition_id });
+        }
+    }
+    let kripke = match petri_to_kripke(
+        petri, 
+        initial_marking, 
+        &PetriKripkeStateProducer::new(hash_set!{}), 
+        req
+    ) {
+        Err(e) => {
+            return Err(PetriCtmcGenerationError::ReachabilityGraph(e));
+        },
+        Ok(k) => {k}
+    };
+    // without tagged transitions, there is exactly one Kripke state per reachable marking
+    // and the initial marking is that of the first state
+    let markings : Vec<Marking> = kripke.states.into_iter().map(|st| st.value_in_domain.marking).collect();
+    let markings_ids : HashMap<&Marking,usize> = markings.iter().enumerate().map(|(id,m)| (m,id)).collect();
+    let markings_successors : Vec<PetriMarkingSuccessors> = markings.iter()
+        .map(|m| PetriMarkingSuccessors::new(petri, m, &markings_ids))
+        .collect();
+    // separates tangible and vanishing markings
+    let mut tangible_markings = Vec::new();
+    let mut vanishing_ids = Vec::new();
+    // for each marking, its index among the tangible or among the vanishing markings
+    let mut local_ids = Vec::new();
+    for (marking_id,marking) in markings.iter().enumerate() {
+        if markings_successors[marking_id].is_vanishing {
+            local_ids.push(vanishing_ids.len());
+            vanishing_ids.push(marking_id);
+        } else {
+            local_ids.push(tangible_markings.len());
+            tangible_markings.push(marking.clone());
+        }
+    }
+    let tangible_num = tangible_markings.len();
+    let transitions_num = petri.get_transitions().len();
+    // for each vanishing marking, the probability of eventually reaching each tangible marking
+    // followed by the expected number of firings of each transition before reaching a tangible marking
+    // it is the solution of (I - P_VV).X = B
+    // where P_VV gives the probabilities of going from a vanishing marking to another
+    let absorption = if vanishing_ids.is_empty() {
+        Vec::new()
+    } else {
+        let vanishing_num = vanishing_ids.len();
+        let mut a = vec![vec![0.0;vanishing_num];vanishing_num];
+        let mut b = vec![vec![0.0;tangible_num + transitions_num];vanishing_num];
+        for (local_id,marking_id) in vanishing_ids.iter().enumerate() {
+            a[local_id][local_id] += 1.0;
+            let successors = &markings_successors[*marking_id].successors;
+            let total_weight : f64 = successors.iter().map(|(_,weight,_)| weight).sum();
+            if total_weight == 0.0 {
+                return Err(PetriCtmcGenerationError::ZeroImmediateWeights { marking: markings[*marking_id].clone() });
+            }
+            for (tr_id,weight,target_id) in successors {
+                let probability = weight / total_weight;
+                b[local_id][tangible_num + tr_id] += probability;
+                if markings_successors[*target_id].is_vanishing {
+                    a[local_id][local_ids[*target_id]] -= probability;
+                } else {
+                    b[local_id][local_ids[*target_id]] += probability;
+                }
+            }
+        }
+        match solve_linear_system(a, b) {
+            None => {
+                return Err(PetriCtmcGenerationError::TimelessTrap);
+            },
+            Some(x) => {x}
+        }
+    };
+    // builds the rates between tangible markings
+    let mut rates = vec![HashMap::new();tangible_num];
+    let mut firing_rates = vec![HashMap::new();tangible_num];
+    for (marking_id,marking_successors) in markings_successors.iter().enumerate() {
+        if marking_successors.is_vanishing {
+            continue;
+        }
+        let origin_id = local_ids[marking_id];
+        for (tr_id,rate,target_id) in &marking_successors.successors {
+            *firing_rates[origin_id].entry(*tr_id).or_insert(0.0) += rate;
+            if markings_successors[*target_id].is_vanishing {
+                let row = &absorption[local_ids[*target_id]];
+                for (tangible_id,probability) in row.iter().take(tangible_num).enumerate() {
+                    if tangible_id != origin_id && *probability > 0.0 {
+                        *rates[origin_id].entry(tangible_id).or_insert(0.0) += rate * probability;
+                    }
+                }
+                for (other_tr_id,expected_firings) in row.iter().skip(tangible_num).enumerate() {
+                    if *expected_firings > 0.0 {
+                        *firing_rates[origin_id].entry(other_tr_id).or_insert(0.0) += rate * expected_firings;
+                    }
+                }
+            } else {
+                let tangible_id = local_ids[*target_id];
+                if tangible_id != origin_id {
+                    *rates[origin_id].entry(tangible_id).or_insert(0.0) += rate;
+                }
+            }
+        }
+    }
+    let initial_distribution = if markings_successors[0].is_vanishing {
+        absorption[local_ids[0]].iter().take(tangible_num).cloned().collect()
+    } else {
+        let mut distribution = vec![0.0;tangible_num];
+        distribution[local_ids[0]] = 1.0;
+        distribution
+    };
+    Ok(
+        PetriCtmc {
+            tangible_markings,
+            initial_distribution,
+            rates,
+            firing_rates,
+            places_num : petri.get_places().len(),
+            transitions_num
+        }
+    )
+}
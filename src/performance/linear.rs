@@ -0,0 +1,61 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+/// solves the linear system A.X = B with Gaussian elimination and partial pivoting
+/// where A is a square matrix and B has an arbitrary number of columns (one per right-hand side)
+/// returns None if A is singular
+pub(crate) fn solve_linear_system(
+    mut a : Vec<Vec<f64>>,
+    mut b : Vec<Vec<f64>>
+) -> Option<Vec<Vec<f64>>> {
+    let size = a.len();
+    for col in 0..size {
+        let pivot_row = (col..size).max_by(
+            |x,y| a[*x][col].abs().total_cmp(&a[*y][col].abs())
+        )?;
+        if a[pivot_row][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot_row);
+        b.swap(col, pivot_row);
+        for row in (col + 1)..size {
+            let factor = a[row][col] / a[col][col];
+            if factor != 0.0 {
+                let (upper,lower) = a.split_at_mut(row);
+                for (x,pivot_x) in lower[0].iter_mut().zip(upper[col].iter()).skip(col) {
+                    *x -= factor * pivot_x;
+                }
+                let (upper,lower) = b.split_at_mut(row);
+                for (x,pivot_x) in lower[0].iter_mut().zip(upper[col].iter()) {
+                    *x -= factor * pivot_x;
+                }
+            }
+        }
+    }
+    for row in (0..size).rev() {
+        for k in 0..b[row].len() {
+            let mut value = b[row][k];
+            for other in (row + 1)..size {
+                value -= a[row][other] * b[other][k];
+            }
+            b[row][k] = value / a[row][row];
+        }
+    }
+    Some(b)
+}
@@ -0,0 +1,140 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::performance::ctmc::PetriCtmc;
+use crate::performance::linear::solve_linear_system;
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PetriCtmcSolvingError {
+    /// the CTMC has several recurrent classes, so that the steady-state distribution depends on the initial one
+    NoUniqueSteadyState,
+    /// the time instant of a transient analysis is negative or not finite
+    InvalidTimeInstant
+}
+
+
+/// performance measures of a GSPN, either at steady-state or at a given time instant
+#[derive(Debug, PartialEq, Clone)]
+pub struct PetriPerformanceMeasures {
+    /// the probability of each tangible marking (in the order of the tangible markings of the CTMC)
+    pub marking_probabilities : Vec<f64>,
+    /// the expected number of tokens in each place
+    pub expected_tokens : Vec<f64>,
+    /// the throughput of each transition i.e., its mean number of firings per unit of time
+    pub throughputs : Vec<f64>
+}
+
+
+/// when uniformizing, the time horizon is split into steps so that the Poisson weights do not underflow
+const MAX_UNIFORMIZATION_STEP_RATE : f64 = 32.0;
+const UNIFORMIZATION_PRECISION : f64 = 1e-12;
+
+
+impl PetriCtmc {
+
+    /// computes the performance measures associated to a probability distribution over the tangible markings
+    pub fn get_measures(&self, marking_probabilities : Vec<f64>) -> PetriPerformanceMeasures {
+        let mut expected_tokens = vec![0.0;self.places_num];
+        let mut throughputs = vec![0.0;self.transitions_num];
+        for (tangible_id,probability) in marking_probabilities.iter().enumerate() {
+            for (place_id,num_toks) in self.tangible_markings[tangible_id].iter_tokens() {
+                expected_tokens[*place_id] += probability * (*num_toks as f64);
+            }
+            for (tr_id,rate) in &self.firing_rates[tangible_id] {
+                throughputs[*tr_id] += probability * rate;
+            }
+        }
+        PetriPerformanceMeasures { marking_probabilities, expected_tokens, throughputs }
+    }
+
+    fn get_exit_rates(&self) -> Vec<f64> {
+        self.rates.iter().map(|targets| targets.values().sum()).collect()
+    }
+
+    /// solves the global balance equations π.Q = 0 with Σπ = 1
+    /// which requires the CTMC to have a single recurrent class
+    pub fn solve_steady_state(&self) -> Result<PetriPerformanceMeasures,PetriCtmcSolvingError> {
+        let size = self.tangible_markings.len();
+        let exit_rates = self.get_exit_rates();
+        // transposed generator, in which the last equation is replaced by the normalization constraint
+        let mut a = vec![vec![0.0;size];size];
+        for (origin_id,targets) in self.rates.iter().enumerate() {
+            a[origin_id][origin_id] -= exit_rates[origin_id];
+            for (target_id,rate) in targets {
+                a[*target_id][origin_id] += rate;
+            }
+        }
+        let mut b = vec![vec![0.0];size];
+        if let Some(last) = a.last_mut() {
+            *last = vec![1.0;size];
+            b[size - 1][0] = 1.0;
+        }
+        match solve_linear_system(a, b) {
+            None => {
+                Err(PetriCtmcSolvingError::NoUniqueSteadyState)
+            },
+            Some(x) => {
+                let probabilities = x.into_iter().map(|row| row[0].max(0.0)).collect();
+                Ok(self.get_measures(probabilities))
+            }
+        }
+    }
+
+    /// computes the distribution over tangible markings at a given time (uniformization)
+    /// starting from the initial distribution
+    pub fn solve_transient(&self, time : f64) -> Result<PetriPerformanceMeasures,PetriCtmcSolvingError> {
+        if !time.is_finite() || time < 0.0 {
+            return Err(PetriCtmcSolvingError::InvalidTimeInstant);
+        }
+        let exit_rates = self.get_exit_rates();
+        let uniformization_rate = exit_rates.iter().cloned().fold(0.0, f64::max);
+        let mut probabilities = self.initial_distribution.clone();
+        if uniformization_rate > 0.0 && time > 0.0 {
+            let steps_num = (uniformization_rate * time / MAX_UNIFORMIZATION_STEP_RATE).ceil().max(1.0);
+            let step_rate = uniformization_rate * time / steps_num;
+            for _ in 0..(steps_num as usize) {
+                // Σ_k Poisson(k,q.Δt) π.P^k with P = I + Q/q
+                let mut weight = (-step_rate).exp();
+                let mut cumulated_weight = weight;
+                let mut term = probabilities.clone();
+                let mut accumulated : Vec<f64> = term.iter().map(|p| p * weight).collect();
+                let mut k = 0.0;
+                while cumulated_weight < 1.0 - UNIFORMIZATION_PRECISION && weight > 0.0 {
+                    k += 1.0;
+                    let mut next_term : Vec<f64> = term.iter().zip(exit_rates.iter())
+                        .map(|(p,exit_rate)| p * (1.0 - exit_rate / uniformization_rate))
+                        .collect();
+                    for (origin_id,targets) in self.rates.iter().enumerate() {
+                        for (target_id,rate) in targets {
+                            next_term[*target_id] += term[origin_id] * rate / uniformization_rate;
+                        }
+                    }
+                    term = next_term;
+                    weight *= step_rate / k;
+                    cumulated_weight += weight;
+                    for (acc,p) in accumulated.iter_mut().zip(term.iter()) {
+                        *acc += p * weight;
+                    }
+                }
+                probabilities = accumulated;
+            }
+        }
+        Ok(self.get_measures(probabilities))
+    }
+}
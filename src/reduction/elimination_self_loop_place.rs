@@ -33,6 +33,7 @@ impl SelfLoopPlace {
 
 
 
+#[allow(clippy::collapsible_if)]
 pub fn find_and_simplify_self_loop_place(
     petri_net : &mut PetriNet,
     petri_info : &mut PetriNetInfo,
@@ -67,7 +68,7 @@ pub fn find_and_simplify_self_loop_place(
             {
                 let place_info = petri_info.places_info.get(self_loop_place.place_id).unwrap();
                 for tr_id in place_info.incoming_transitions.keys() {
-                    let transition = petri_net.transitions.get_mut(*tr_id).unwrap();
+                    let transition = petri_net.get_transitions_mut().get_mut(*tr_id).unwrap();
                     // remove the place from the preset and postset of the transition 
                     transition.preset_tokens.remove(&self_loop_place.place_id);
                     transition.postset_tokens.remove(&self_loop_place.place_id);
@@ -86,10 +87,12 @@ pub fn find_and_simplify_self_loop_place(
 
 /// a self-loop place is a place p such that:
 /// - it has the empty place label
+/// - it is not connected to inhibitor, reset or read arcs
 /// - its map of outgoing transitions to the number of tokens they take is the same as 
 ///   its map of incoming transitions to the number of tokens they give
 /// - the number of tokens in p in the initial marking is greater that the maximum number of tokens taken by any of its outgoing transitions
 /// - let's remark that it also eliminate dead places (i.e. no outgoing nor incoming transitions)
+#[allow(clippy::collapsible_if)]
 fn find_self_loop_place(
     petri_net : &PetriNet, 
     petri_info : &PetriNetInfo,
@@ -100,8 +103,12 @@ fn find_self_loop_place(
         if place_info.outgoing_transitions != place_info.incoming_transitions {
             continue 'iter_places;
         }
+        // the place must not be connected to inhibitor, reset or read arcs
+        if place_info.is_connected_to_extended_arcs() {
+            continue 'iter_places;
+        }
         // the place, that will be deleted, must contain the empty place label
-        let place_label = petri_net.places.get(place_id).unwrap();
+        let place_label = petri_net.get_places().get(place_id).unwrap();
         if place_label.is_some() {
             continue 'iter_places;
         }
@@ -35,9 +35,9 @@ pub fn find_and_simplify_self_loop_transition(
     petri_info : &mut PetriNetInfo,
     _initial_markings : &mut Option<Marking>
 ) -> bool {
-    if let Some(self_loop_transition) = find_self_loop_transition(petri_net) {
+    if let Some(self_loop_transition) = find_self_loop_transition(petri_net, petri_info) {
         // we remove the self_loop_transition
-        petri_net.transitions.remove(self_loop_transition.transition_id);
+        petri_net.remove_transition(self_loop_transition.transition_id);
         petri_info.remove_transition(self_loop_transition.transition_id);
         true
     } else {
@@ -48,12 +48,22 @@ pub fn find_and_simplify_self_loop_transition(
 /// a self-loop transition is a transition t such that:
 /// - it has the empty label
 /// - its preset and its postset is the same (same places, same numbers of tokens)
+/// - it has no reset arcs (otherwise firing it may change the marking)
+/// - none of the places in its preset are connected to inhibitor, reset or read arcs
 ///   let's remark that it also eliminate dead transitions (i.e. both preset and postset empty)
 fn find_self_loop_transition(
-    petri_net : &PetriNet
+    petri_net : &PetriNet,
+    petri_info : &PetriNetInfo
 ) -> Option<SelfLoopTransition> {
-    for (transition_id, transition) in petri_net.transitions.iter().enumerate() {
-        if transition.transition_label.is_none() && transition.postset_tokens == transition.preset_tokens {
+    'iter_transitions : for (transition_id, transition) in petri_net.get_transitions().iter().enumerate() {
+        if transition.transition_label.is_none() 
+            && transition.postset_tokens == transition.preset_tokens 
+            && transition.reset_places.is_empty() {
+            for place_id in transition.preset_tokens.keys() {
+                if petri_info.is_place_connected_to_extended_arcs(place_id) {
+                    continue 'iter_transitions;
+                }
+            }
             return Some(SelfLoopTransition::new(transition_id));
         }
     }
@@ -65,12 +65,12 @@ pub fn find_and_simplify_parallel_places(
             let place_to_remove_info = petri_info.places_info.get(parallel_places.place2_id).unwrap();
             // we remove the place from the postset of all incoming transitions
             for (incoming_tx_id,_) in place_to_remove_info.incoming_transitions.iter() {
-                let incoming_transition = petri_net.transitions.get_mut(*incoming_tx_id).unwrap();
+                let incoming_transition = petri_net.get_transitions_mut().get_mut(*incoming_tx_id).unwrap();
                 incoming_transition.postset_tokens.remove(&parallel_places.place2_id);    
             }
             // we remove the place from the preset of all outgoing transitions
             for (outgoing_tx_id,_) in place_to_remove_info.outgoing_transitions.iter() {
-                let outgoing_transition = petri_net.transitions.get_mut(*outgoing_tx_id).unwrap();
+                let outgoing_transition = petri_net.get_transitions_mut().get_mut(*outgoing_tx_id).unwrap();
                 outgoing_transition.preset_tokens.remove(&parallel_places.place2_id);   
             }
         }
@@ -89,6 +89,7 @@ pub fn find_and_simplify_parallel_places(
 /// - p1 and p2 have the same number of tokens in the initial marking
 /// - p1 and p2 have the same set of incoming transitions from which they take the same number of tokens
 /// - p1 and p2 have the same set of outgoing transitions to which they give the same number of tokens
+/// - neither p1 nor p2 are connected to inhibitor, reset or read arcs
 fn find_parallel_places(
     petri_net : &PetriNet, 
     petri_info : &PetriNetInfo,
@@ -97,6 +98,10 @@ fn find_parallel_places(
     'iter_pairs_of_places : for pl_pair in petri_info.places_info.iter().enumerate().combinations(2) {
         let (place_id1, place_info1) = pl_pair.first().unwrap();
         let (place_id2, place_info2) = pl_pair.get(1).unwrap();
+        // neither p1 nor p2 must be connected to inhibitor, reset or read arcs
+        if place_info1.is_connected_to_extended_arcs() || place_info2.is_connected_to_extended_arcs() {
+            continue 'iter_pairs_of_places;
+        }
         // p1 and p2 have the same set of incoming transitions from which they take the same number of tokens
         if place_info1.incoming_transitions != place_info2.incoming_transitions {
             continue 'iter_pairs_of_places;
@@ -106,8 +111,8 @@ fn find_parallel_places(
             continue 'iter_pairs_of_places;
         }
         // the two places must have the same place label
-        let place_label1 = petri_net.places.get(*place_id1).unwrap();
-        let place_label2 = petri_net.places.get(*place_id2).unwrap();
+        let place_label1 = petri_net.get_places().get(*place_id1).unwrap();
+        let place_label2 = petri_net.get_places().get(*place_id2).unwrap();
         if place_label1 != place_label2 {
             continue 'iter_pairs_of_places;
         }
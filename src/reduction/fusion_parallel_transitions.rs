@@ -43,7 +43,7 @@ pub fn find_and_simplify_parallel_transitions(
     if let Some(parallel_transitions) = find_parallel_transitions(petri_net) {
         // we simply delete any of the two transitions (let's delete the second)
         
-        petri_net.transitions.remove(parallel_transitions.tx2_id);
+        petri_net.remove_transition(parallel_transitions.tx2_id);
         petri_info.remove_transition(parallel_transitions.tx2_id);
         true 
     } else {
@@ -55,10 +55,11 @@ pub fn find_and_simplify_parallel_transitions(
 /// - both t1 and t2 have the same label
 /// - both t1 and t2 have the same preset map
 /// - both t1 and t2 have the same postset map
+/// - both t1 and t2 have the same inhibitor, reset and read arcs
 fn find_parallel_transitions(
     petri_net : &PetriNet
 ) -> Option<ParallelTransitionsPair> {
-    'iter_pairs_of_transitions : for tx_pair in petri_net.transitions.iter().enumerate().combinations(2) {
+    'iter_pairs_of_transitions : for tx_pair in petri_net.get_transitions().iter().enumerate().combinations(2) {
         let (tx_id1,tx1) = tx_pair.first().unwrap();
         let (tx_id2, tx2) = tx_pair.get(1).unwrap();
         if tx1.transition_label != tx2.transition_label {
@@ -70,6 +71,15 @@ fn find_parallel_transitions(
         if tx1.postset_tokens != tx2.postset_tokens {
             continue 'iter_pairs_of_transitions;
         }
+        if tx1.inhibitor_tokens != tx2.inhibitor_tokens {
+            continue 'iter_pairs_of_transitions;
+        }
+        if tx1.reset_places != tx2.reset_places {
+            continue 'iter_pairs_of_transitions;
+        }
+        if tx1.read_tokens != tx2.read_tokens {
+            continue 'iter_pairs_of_transitions;
+        }
         return Some(
             ParallelTransitionsPair::new(
                 *tx_id1,
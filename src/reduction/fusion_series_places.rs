@@ -71,10 +71,10 @@ pub fn find_and_simplify_series_places(
         }
 
         // remove the transition
-        petri_net.transitions.remove(series_place.transition_id);
+        petri_net.remove_transition(series_place.transition_id);
         petri_info.remove_transition(series_place.transition_id);
         // make all the transitions that target the origin_place instead target the target_place
-        for transition in petri_net.transitions.iter_mut() {
+        for transition in petri_net.get_transitions_mut().iter_mut() {
             if let Some(toks_to_origin) = transition.postset_tokens.remove(&series_place.origin_place_id) {
                 match transition.postset_tokens.get_mut(&series_place.target_place_id) {
                     Some(toks_to_target) => {
@@ -115,6 +115,7 @@ pub fn find_and_simplify_series_places(
 /// - t is the only transition which accepts tokens from p1 
 /// - the label of t is the empty label
 /// - the places p1 and p2 contain the same label
+/// - neither p1 nor p2 are connected to inhibitor, reset or read arcs and t has no inhibitor, reset or read arcs
 /// 
 /// see https://ieeexplore.ieee.org/document/24143 
 fn find_series_places(
@@ -124,8 +125,12 @@ fn find_series_places(
     // find an origin place with only one outgoing transition
     'iter_places : for (origin_place_id,origin_place_info) in petri_info.places_info.iter().enumerate() {
         if origin_place_info.outgoing_transitions.len() == 1 {
+            // the origin place must not be connected to inhibitor, reset or read arcs
+            if origin_place_info.is_connected_to_extended_arcs() {
+                continue 'iter_places;
+            }
             let transition_id = origin_place_info.outgoing_transitions.keys().next().unwrap();
-            let transition = petri_net.transitions.get(*transition_id).unwrap();
+            let transition = petri_net.get_transitions().get(*transition_id).unwrap();
             // the transition must not have a label
             if transition.transition_label.is_some() {
                 continue 'iter_places;
@@ -137,19 +142,27 @@ fn find_series_places(
             if transition.number_of_postset_places() != 1 {
                 continue 'iter_places;
             }
+            // the transition must not have inhibitor, reset or read arcs
+            if transition.has_extended_arcs() {
+                continue 'iter_places;
+            }
             // the target of the outgoing transition must not be the same place as its origin
             // otherwise we should consider the rule "elimination of self loop transition"
             let target_place_id = transition.postset_tokens.keys().next().unwrap();
             if *target_place_id == origin_place_id {
                 continue 'iter_places;
             }
+            // the target place must not be connected to inhibitor, reset or read arcs
+            if petri_info.is_place_connected_to_extended_arcs(target_place_id) {
+                continue 'iter_places;
+            }
             // the transition must take and produce only 1 token
             let num_input_toks = transition.preset_tokens.get(&origin_place_id).unwrap();
             let num_output_toks = transition.postset_tokens.get(target_place_id).unwrap();
             if *num_input_toks == 1 && *num_output_toks == 1 {
                 // the origin and target places must have the same label
-                let origin_place_label = petri_net.places.get(origin_place_id).unwrap();
-                let target_place_label = petri_net.places.get(*target_place_id).unwrap();
+                let origin_place_label = petri_net.get_places().get(origin_place_id).unwrap();
+                let target_place_label = petri_net.get_places().get(*target_place_id).unwrap();
                 if origin_place_label == target_place_label {
                     return Some(
                         SeriesPlacesPair::new(
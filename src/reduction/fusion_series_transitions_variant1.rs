@@ -63,13 +63,13 @@ pub fn find_and_simplify_series_transitions_variant1(
         }
 
         // remove the succeeding transition
-        let succeeding_transition = petri_net.transitions.remove(series_transitions.succeeding_transition_id);
+        let succeeding_transition = petri_net.remove_transition(series_transitions.succeeding_transition_id);
         petri_info.remove_transition(series_transitions.succeeding_transition_id);
         if series_transitions.succeeding_transition_id < series_transitions.preceding_transition_id {
             series_transitions.preceding_transition_id -= 1;
         }
 
-        let preceding_transition = petri_net.transitions.get_mut(series_transitions.preceding_transition_id).unwrap();
+        let preceding_transition = petri_net.get_transitions_mut().get_mut(series_transitions.preceding_transition_id).unwrap();
         // remove the place from the postset of the preceeding transition 
         preceding_transition.postset_tokens.remove(&series_transitions.place_id);
         // add to the preceeding transition postset all the places that the succeeding transition targets
@@ -94,6 +94,8 @@ pub fn find_and_simplify_series_transitions_variant1(
 /// - t2 only accepts tokens from p
 /// - p only accepts tokens from t1
 /// - p only feeds tokens to t2
+/// - t2 has no inhibitor, reset or read arcs and neither p nor the places targetted by t2 are connected to inhibitor, reset or read arcs
+#[allow(clippy::collapsible_if)]
 fn find_series_transitions_variant1(
     petri_net : &PetriNet, 
     petri_info : &PetriNetInfo,
@@ -102,6 +104,10 @@ fn find_series_transitions_variant1(
     'iter_places : for (place_id,place_info) in petri_info.places_info.iter().enumerate() {
         // find a place with only one incoming transition and one outgoing transition
         if (place_info.outgoing_transitions.len() == 1) && (place_info.incoming_transitions.len() == 1) {
+            // the place must not be connected to inhibitor, reset or read arcs
+            if place_info.is_connected_to_extended_arcs() {
+                continue 'iter_places;
+            }
             // as the place, if it matches all requirements, will be deleted, it must not contain tokens in the initial marking 
             if let Some(marks) = initial_markings {
                 if let Some(num_toks) = marks.get_num_toks_at_place(&place_id) {
@@ -111,7 +117,7 @@ fn find_series_transitions_variant1(
                 }
             }
             let outgoing_transition_id = place_info.outgoing_transitions.keys().next().unwrap();
-            let outgoing_transition = petri_net.transitions.get(*outgoing_transition_id).unwrap();
+            let outgoing_transition = petri_net.get_transitions().get(*outgoing_transition_id).unwrap();
             // the outgoing transition must have no label
             if outgoing_transition.transition_label.is_some() {
                 continue 'iter_places;
@@ -120,13 +126,23 @@ fn find_series_transitions_variant1(
             if outgoing_transition.preset_tokens.len() != 1 {
                 continue 'iter_places;
             }
+            // the outgoing transition must not have inhibitor, reset or read arcs
+            // and the places it targets must not be connected to inhibitor, reset or read arcs
+            if outgoing_transition.has_extended_arcs() {
+                continue 'iter_places;
+            }
+            for target_place_id in outgoing_transition.postset_tokens.keys() {
+                if petri_info.is_place_connected_to_extended_arcs(target_place_id) {
+                    continue 'iter_places;
+                }
+            }
             // the outgoing and incoming transitions must not be the same
             // otherwise we should consider the rule "elimination of self loop places"
             let incoming_transition_id = place_info.incoming_transitions.keys().next().unwrap();
             if incoming_transition_id == outgoing_transition_id {
                 continue 'iter_places;
             }
-            let incoming_transition = petri_net.transitions.get(*incoming_transition_id).unwrap();
+            let incoming_transition = petri_net.get_transitions().get(*incoming_transition_id).unwrap();
             {
                 let num_toks_to_t2 = outgoing_transition.preset_tokens.get(&place_id).unwrap();
                 // the incoming transition must put the correct number of tokens in p 
@@ -137,7 +153,7 @@ fn find_series_transitions_variant1(
             }
             {
                 // the place, that will be deleted, must contain the empty label
-                let place_label = petri_net.places.get(place_id).unwrap();
+                let place_label = petri_net.get_places().get(place_id).unwrap();
                 if place_label.is_some() {
                     continue 'iter_places;
                 }
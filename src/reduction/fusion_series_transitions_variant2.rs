@@ -63,13 +63,13 @@ pub fn find_and_simplify_series_transitions_variant2(
         }
 
         // remove the preceding transition
-        let preceding_transition = petri_net.transitions.remove(series_transitions.preceding_transition_id);
+        let preceding_transition = petri_net.remove_transition(series_transitions.preceding_transition_id);
         petri_info.remove_transition(series_transitions.preceding_transition_id);
         if series_transitions.preceding_transition_id < series_transitions.succeeding_transition_id {
             series_transitions.succeeding_transition_id -= 1;
         }
 
-        let succeeding_transition = petri_net.transitions.get_mut(series_transitions.succeeding_transition_id).unwrap();
+        let succeeding_transition = petri_net.get_transitions_mut().get_mut(series_transitions.succeeding_transition_id).unwrap();
         // remove the place from the preset of the succeeding transition 
         succeeding_transition.preset_tokens.remove(&series_transitions.place_id);
         // add to the succeeding transition preset all the places from which the preceding transition takes tokens
@@ -94,6 +94,8 @@ pub fn find_and_simplify_series_transitions_variant2(
 /// - t1 only feeds tokens to p
 /// - p only accepts tokens from t1
 /// - p only feeds tokens to t2
+/// - t1 has no inhibitor, reset or read arcs and neither p nor the places from which t1 takes tokens are connected to inhibitor, reset or read arcs
+#[allow(clippy::collapsible_if)]
 fn find_series_transitions_variant2(
     petri_net : &PetriNet, 
     petri_info : &PetriNetInfo,
@@ -102,6 +104,10 @@ fn find_series_transitions_variant2(
     'iter_places : for (place_id,place_info) in petri_info.places_info.iter().enumerate() {
         // find a place with only one incoming transition and one outgoing transition
         if (place_info.outgoing_transitions.len() == 1) && (place_info.incoming_transitions.len() == 1) {
+            // the place must not be connected to inhibitor, reset or read arcs
+            if place_info.is_connected_to_extended_arcs() {
+                continue 'iter_places;
+            }
             // as the place, if it matches all requirements, will be deleted, it must not contain tokens in the initial marking 
             if let Some(marks) = initial_markings {
                 if let Some(num_toks) = marks.get_num_toks_at_place(&place_id) {
@@ -111,7 +117,7 @@ fn find_series_transitions_variant2(
                 }
             }
             let incoming_transition_id = place_info.incoming_transitions.keys().next().unwrap();
-            let incoming_transition = petri_net.transitions.get(*incoming_transition_id).unwrap();
+            let incoming_transition = petri_net.get_transitions().get(*incoming_transition_id).unwrap();
             // the incoming transition must have no label
             if incoming_transition.transition_label.is_some() {
                 continue 'iter_places;
@@ -120,13 +126,23 @@ fn find_series_transitions_variant2(
             if incoming_transition.postset_tokens.len() != 1 {
                 continue 'iter_places;
             }
+            // the incoming transition must not have inhibitor, reset or read arcs
+            // and the places from which it takes tokens must not be connected to inhibitor, reset or read arcs
+            if incoming_transition.has_extended_arcs() {
+                continue 'iter_places;
+            }
+            for origin_place_id in incoming_transition.preset_tokens.keys() {
+                if petri_info.is_place_connected_to_extended_arcs(origin_place_id) {
+                    continue 'iter_places;
+                }
+            }
             // the outgoing and incoming transitions must not be the same
             // otherwise we should consider the rule "elimination of self loop places"
             let outgoing_transition_id = place_info.outgoing_transitions.keys().next().unwrap();
             if incoming_transition_id == outgoing_transition_id {
                 continue 'iter_places;
             }
-            let outgoing_transition = petri_net.transitions.get(*outgoing_transition_id).unwrap();
+            let outgoing_transition = petri_net.get_transitions().get(*outgoing_transition_id).unwrap();
             {
                 let num_toks_from_t1 = incoming_transition.postset_tokens.get(&place_id).unwrap();
                 // the outgoing transition must take the correct number of tokens from p 
@@ -137,7 +153,7 @@ fn find_series_transitions_variant2(
             }
             {
                 // the place, that will be deleted, must contain the empty label
-                let place_label = petri_net.places.get(place_id).unwrap();
+                let place_label = petri_net.get_places().get(place_id).unwrap();
                 if place_label.is_some() {
                     continue 'iter_places;
                 }
@@ -15,7 +15,7 @@ limitations under the License.
 */
 
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::model::net::PetriNet;
 
@@ -25,12 +25,76 @@ use crate::model::net::PetriNet;
 pub struct PetriPlaceInfo {
     pub incoming_transitions : HashMap<usize,u32>,
     pub outgoing_transitions : HashMap<usize,u32>,
+    /// transitions that are connected to the place via an inhibitor arc, with the arc's threshold
+    pub inhibited_transitions : HashMap<usize,u32>,
+    /// transitions that empty the place via a reset arc
+    pub resetting_transitions : HashSet<usize>,
+    /// transitions that test the place via a read arc, with the number of tokens tested for
+    pub reading_transitions : HashMap<usize,u32>,
 }
 
 impl PetriPlaceInfo {
-    pub fn new(incoming_transitions: HashMap<usize,u32>, outgoing_transitions: HashMap<usize,u32>) -> Self {
-        Self { incoming_transitions, outgoing_transitions }
+    pub fn new(
+        incoming_transitions: HashMap<usize,u32>, 
+        outgoing_transitions: HashMap<usize,u32>,
+        inhibited_transitions: HashMap<usize,u32>,
+        resetting_transitions: HashSet<usize>,
+        reading_transitions: HashMap<usize,u32>
+    ) -> Self {
+        Self { incoming_transitions, outgoing_transitions, inhibited_transitions, resetting_transitions, reading_transitions }
     }
+
+    /// places that are connected to inhibitor, reset or read arcs must not be touched by the reduction rules
+    /// given that their exact number of tokens matters for the enabledness of the inhibited and reading transitions
+    /// and that the effect of resetting transitions does not depend on the flow of tokens
+    pub fn is_connected_to_extended_arcs(&self) -> bool {
+        !self.inhibited_transitions.is_empty() 
+        || !self.resetting_transitions.is_empty() 
+        || !self.reading_transitions.is_empty()
+    }
+}
+
+
+fn shift_transition_ids_after_removal(
+    transition_to_num_toks : &mut HashMap<usize,u32>,
+    tr_to_remove_id : usize
+) {
+    let mut new_transition_to_num_toks = HashMap::new();
+    for (tr_id, num_toks) in transition_to_num_toks.drain() {
+        match usize::cmp(&tr_id,&tr_to_remove_id) {
+            std::cmp::Ordering::Less => {
+                new_transition_to_num_toks.insert(tr_id,num_toks);
+            },
+            std::cmp::Ordering::Equal => {
+                // we remove the transition
+            },
+            std::cmp::Ordering::Greater => {
+                new_transition_to_num_toks.insert(tr_id - 1,num_toks);
+            }
+        }
+    }
+    *transition_to_num_toks = new_transition_to_num_toks;
+}
+
+fn shift_transition_ids_in_set_after_removal(
+    transitions : &mut HashSet<usize>,
+    tr_to_remove_id : usize
+) {
+    let mut new_transitions = HashSet::new();
+    for tr_id in transitions.drain() {
+        match usize::cmp(&tr_id,&tr_to_remove_id) {
+            std::cmp::Ordering::Less => {
+                new_transitions.insert(tr_id);
+            },
+            std::cmp::Ordering::Equal => {
+                // we remove the transition
+            },
+            std::cmp::Ordering::Greater => {
+                new_transitions.insert(tr_id - 1);
+            }
+        }
+    }
+    *transitions = new_transitions;
 }
 
 
@@ -42,10 +106,12 @@ pub struct PetriNetInfo {
 impl PetriNetInfo {
     pub fn from_petri_net(net : &PetriNet) -> Self {
         let mut places_info : Vec<PetriPlaceInfo> = Vec::new();
-        for _ in &net.places {
-            places_info.push(PetriPlaceInfo::new(HashMap::new(),HashMap::new()));
+        for _ in net.get_places() {
+            places_info.push(
+                PetriPlaceInfo::new(HashMap::new(),HashMap::new(),HashMap::new(),HashSet::new(),HashMap::new())
+            );
         }
-        for (tr_id,transition) in net.transitions.iter().enumerate() {
+        for (tr_id,transition) in net.get_transitions().iter().enumerate() {
             for (preset_place_id,num_toks) in transition.iter_preset_tokens() {
                 let preset_place_info = places_info.get_mut(*preset_place_id).unwrap();
                 preset_place_info.outgoing_transitions.insert(tr_id, *num_toks);
@@ -54,48 +120,34 @@ impl PetriNetInfo {
                 let postset_place_info = places_info.get_mut(*postset_place_id).unwrap();
                 postset_place_info.incoming_transitions.insert(tr_id, *num_toks);
             }
+            for (inhibitor_place_id,threshold) in transition.iter_inhibitor_tokens() {
+                let inhibitor_place_info = places_info.get_mut(*inhibitor_place_id).unwrap();
+                inhibitor_place_info.inhibited_transitions.insert(tr_id, *threshold);
+            }
+            for reset_place_id in transition.iter_reset_places() {
+                let reset_place_info = places_info.get_mut(*reset_place_id).unwrap();
+                reset_place_info.resetting_transitions.insert(tr_id);
+            }
+            for (read_place_id,num_toks) in transition.iter_read_tokens() {
+                let read_place_info = places_info.get_mut(*read_place_id).unwrap();
+                read_place_info.reading_transitions.insert(tr_id, *num_toks);
+            }
         }
         Self { places_info }
     }
 
+    pub fn is_place_connected_to_extended_arcs(&self, place_id : &usize) -> bool {
+        self.places_info.get(*place_id).unwrap().is_connected_to_extended_arcs()
+    }
+
     pub fn remove_transition(&mut self, tr_to_remove_id : usize) {
         for place_info in self.places_info.iter_mut() {
-            let mut new_incoming_transitions = HashMap::new();
-            for (incoming_tr_id, num_toks) in place_info.incoming_transitions.drain() {
-                match usize::cmp(&incoming_tr_id,&tr_to_remove_id) {
-                    std::cmp::Ordering::Less => {
-                        new_incoming_transitions.insert(incoming_tr_id,num_toks);
-                    },
-                    std::cmp::Ordering::Equal => {
-                        // we remove the transition
-                    },
-                    std::cmp::Ordering::Greater => {
-                        new_incoming_transitions.insert(incoming_tr_id - 1,num_toks);
-                    }
-                }
-            }
-            place_info.incoming_transitions = new_incoming_transitions;
-            // ***
-            let mut new_outgoing_transitions = HashMap::new();
-            for (outgoing_tr_id, num_toks) in place_info.outgoing_transitions.drain() {
-                match usize::cmp(&outgoing_tr_id,&tr_to_remove_id) {
-                    std::cmp::Ordering::Less => {
-                        new_outgoing_transitions.insert(outgoing_tr_id,num_toks);
-                    },
-                    std::cmp::Ordering::Equal => {
-                        // we remove the transition
-                    },
-                    std::cmp::Ordering::Greater => {
-                        new_outgoing_transitions.insert(outgoing_tr_id - 1,num_toks);
-                    }
-                }
-            }
-            place_info.outgoing_transitions = new_outgoing_transitions;
+            shift_transition_ids_after_removal(&mut place_info.incoming_transitions, tr_to_remove_id);
+            shift_transition_ids_after_removal(&mut place_info.outgoing_transitions, tr_to_remove_id);
+            shift_transition_ids_after_removal(&mut place_info.inhibited_transitions, tr_to_remove_id);
+            shift_transition_ids_in_set_after_removal(&mut place_info.resetting_transitions, tr_to_remove_id);
+            shift_transition_ids_after_removal(&mut place_info.reading_transitions, tr_to_remove_id);
         }
     }
 
 }
-
-
-
-
@@ -14,6 +14,8 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use itertools::Itertools;
+
 use crate::model::{marking::Marking, net::PetriNet};
 use crate::reduction::info::PetriNetInfo;
 use crate::reduction::fusion_series_places::find_and_simplify_series_places;
@@ -26,10 +28,24 @@ use crate::reduction::fusion_parallel_transitions::find_and_simplify_parallel_tr
 
 
 
+/// the reduction rules are not sound in the presence of transitions with distinct priorities
+/// (removing or fusing transitions changes which transitions are blocked by higher priority ones)
+/// nor in the presence of firing intervals or stochastic timings (fusing transitions changes the timing of the net)
+/// nor in the presence of place capacities (which may be compiled away beforehand into complementary places)
+/// so such nets are left untouched
 pub fn reduce_petri_net(
     petri_net : &mut PetriNet,
     initial_markings : &mut Option<Marking>
 ) {
+    if !petri_net.get_transitions().iter().map(|tr| tr.get_priority()).all_equal() {
+        return;
+    }
+    if petri_net.get_transitions().iter().any(|tr| tr.firing_interval.is_some() || tr.stochastic_timing.is_some()) {
+        return;
+    }
+    if !petri_net.place_capacities.is_empty() {
+        return;
+    }
 
     let mut petri_info = PetriNetInfo::from_petri_net(petri_net);
 
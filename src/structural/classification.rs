@@ -0,0 +1,224 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+
+use crate::model::net::PetriNet;
+use crate::structural::flow::PetriFlowRelation;
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum PetriNetClass {
+    /// all the arcs have weight 1
+    Ordinary,
+    /// no place is both an input and an output place of the same transition
+    Pure,
+    /// an ordinary net in which every transition has exactly one input place and one output place
+    StateMachine,
+    /// an ordinary net in which every place has exactly one input transition and one output transition
+    MarkedGraph,
+    /// an ordinary net in which, whenever a place has several output transitions, it is their only input place
+    FreeChoice,
+    /// an ordinary net in which any two places which share an output transition have the same output transitions
+    ExtendedFreeChoice,
+    /// an ordinary net in which, for any two places which share an output transition,
+    /// the output transitions of one of them are output transitions of the other
+    AsymmetricChoice
+}
+
+impl PetriNetClass {
+    pub fn all() -> Vec<PetriNetClass> {
+        vec![
+            PetriNetClass::Ordinary,
+            PetriNetClass::Pure,
+            PetriNetClass::StateMachine,
+            PetriNetClass::MarkedGraph,
+            PetriNetClass::FreeChoice,
+            PetriNetClass::ExtendedFreeChoice,
+            PetriNetClass::AsymmetricChoice
+        ]
+    }
+}
+
+impl std::fmt::Display for PetriNetClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PetriNetClass::Ordinary => write!(f, "ordinary"),
+            PetriNetClass::Pure => write!(f, "pure"),
+            PetriNetClass::StateMachine => write!(f, "state machine"),
+            PetriNetClass::MarkedGraph => write!(f, "marked graph"),
+            PetriNetClass::FreeChoice => write!(f, "free-choice"),
+            PetriNetClass::ExtendedFreeChoice => write!(f, "extended free-choice"),
+            PetriNetClass::AsymmetricChoice => write!(f, "asymmetric choice")
+        }
+    }
+}
+
+
+/// a witness of the fact that a net does not belong to a class,
+/// which consists of an offending place and/or transition:
+/// - Ordinary : an arc of weight greater than 1 between the place and the transition
+/// - Pure : the place is both an input and an output place of the transition
+/// - StateMachine : the transition does not have exactly one input place and one output place
+///   (the place being one of its input or output places in excess, if any)
+/// - MarkedGraph : the place does not have exactly one input transition and one output transition
+///   (the transition being one of its input or output transitions in excess, if any)
+/// - FreeChoice : the place has several output transitions, including the transition, which has other input places
+/// - ExtendedFreeChoice : the transition is not an output transition of the place
+///   although it has an input place which shares an output transition with the place
+/// - AsymmetricChoice : same as for ExtendedFreeChoice, the place moreover having an output transition
+///   which is not an output transition of that other place
+///
+/// the witness of the violation of a class that requires the net to be ordinary
+/// may also be that of the violation of Ordinary
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PetriClassViolation {
+    pub place_id : Option<usize>,
+    pub transition_id : Option<usize>
+}
+
+impl PetriClassViolation {
+    pub fn new(place_id : Option<usize>, transition_id : Option<usize>) -> Self {
+        Self { place_id, transition_id }
+    }
+}
+
+
+/// the classes to which a net belongs, with a witness for each class to which it does not belong
+pub struct PetriNetClassification {
+    pub violations : BTreeMap<PetriNetClass,PetriClassViolation>
+}
+
+impl PetriNetClassification {
+    pub fn is_of_class(&self, class : PetriNetClass) -> bool {
+        !self.violations.contains_key(&class)
+    }
+
+    pub fn get_violation(&self, class : PetriNetClass) -> Option<&PetriClassViolation> {
+        self.violations.get(&class)
+    }
+
+    /// the classes to which the net belongs
+    pub fn get_classes(&self) -> Vec<PetriNetClass> {
+        PetriNetClass::all().into_iter().filter(|class| self.is_of_class(*class)).collect()
+    }
+}
+
+
+fn find_non_ordinary_arc(petri_net : &PetriNet) -> Option<PetriClassViolation> {
+    for (transition_id,transition) in petri_net.get_transitions().iter().enumerate() {
+        let mut arcs = transition.iter_preset_tokens()
+            .chain(transition.iter_postset_tokens())
+            .chain(transition.iter_read_tokens())
+            .filter(|(_,weight)| **weight > 1)
+            .map(|(place_id,_)| *place_id);
+        if let Some(place_id) = arcs.next() {
+            return Some(PetriClassViolation::new(Some(place_id), Some(transition_id)));
+        }
+    }
+    None
+}
+
+fn find_self_loop(flow : &PetriFlowRelation) -> Option<PetriClassViolation> {
+    for (transition_id,preset) in flow.transition_presets.iter().enumerate() {
+        if let Some(place_id) = preset.intersection(&flow.transition_postsets[transition_id]).next() {
+            return Some(PetriClassViolation::new(Some(*place_id), Some(transition_id)));
+        }
+    }
+    None
+}
+
+fn find_non_state_machine_transition(flow : &PetriFlowRelation) -> Option<PetriClassViolation> {
+    for (transition_id,preset) in flow.transition_presets.iter().enumerate() {
+        let postset = &flow.transition_postsets[transition_id];
+        if preset.len() != 1 || postset.len() != 1 {
+            let place_in_excess = preset.iter().nth(1).or(postset.iter().nth(1)).cloned();
+            return Some(PetriClassViolation::new(place_in_excess, Some(transition_id)));
+        }
+    }
+    None
+}
+
+fn find_non_marked_graph_place(flow : &PetriFlowRelation) -> Option<PetriClassViolation> {
+    for (place_id,preset) in flow.place_presets.iter().enumerate() {
+        let postset = &flow.place_postsets[place_id];
+        if preset.len() != 1 || postset.len() != 1 {
+            let transition_in_excess = preset.iter().nth(1).or(postset.iter().nth(1)).cloned();
+            return Some(PetriClassViolation::new(Some(place_id), transition_in_excess));
+        }
+    }
+    None
+}
+
+fn find_non_free_choice(flow : &PetriFlowRelation) -> Option<PetriClassViolation> {
+    for (place_id,postset) in flow.place_postsets.iter().enumerate() {
+        if postset.len() > 1 {
+            for transition_id in postset {
+                if flow.transition_presets[*transition_id].len() > 1 {
+                    return Some(PetriClassViolation::new(Some(place_id), Some(*transition_id)));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// finds places p and q which share an output transition and such that q has an output transition t which is not one of p
+/// (and, if "asymmetric" is set, such that p also has an output transition which is not one of q)
+fn find_non_matching_choice(flow : &PetriFlowRelation, asymmetric : bool) -> Option<PetriClassViolation> {
+    for (place_id,postset) in flow.place_postsets.iter().enumerate() {
+        for (other_place_id,other_postset) in flow.place_postsets.iter().enumerate() {
+            if place_id == other_place_id || postset.is_disjoint(other_postset) {
+                continue;
+            }
+            if asymmetric && postset.is_subset(other_postset) {
+                continue;
+            }
+            if let Some(transition_id) = other_postset.difference(postset).next() {
+                return Some(PetriClassViolation::new(Some(place_id), Some(*transition_id)));
+            }
+        }
+    }
+    None
+}
+
+
+/// classifies a net according to its structure
+///
+/// the flow of tokens is that of the preset and postset arcs, read arcs being considered as self-loops
+/// (inhibitor and reset arcs are ignored)
+pub fn classify_petri_net(petri_net : &PetriNet) -> PetriNetClassification {
+    let flow = PetriFlowRelation::from_net(petri_net);
+    let non_ordinary_arc = find_non_ordinary_arc(petri_net);
+    let mut violations = BTreeMap::new();
+    for class in PetriNetClass::all() {
+        let violation = match class {
+            PetriNetClass::Ordinary => non_ordinary_arc,
+            PetriNetClass::Pure => find_self_loop(&flow),
+            PetriNetClass::StateMachine => non_ordinary_arc.or_else(|| find_non_state_machine_transition(&flow)),
+            PetriNetClass::MarkedGraph => non_ordinary_arc.or_else(|| find_non_marked_graph_place(&flow)),
+            PetriNetClass::FreeChoice => non_ordinary_arc.or_else(|| find_non_free_choice(&flow)),
+            PetriNetClass::ExtendedFreeChoice => non_ordinary_arc.or_else(|| find_non_matching_choice(&flow, false)),
+            PetriNetClass::AsymmetricChoice => non_ordinary_arc.or_else(|| find_non_matching_choice(&flow, true))
+        };
+        if let Some(violation) = violation {
+            violations.insert(class, violation);
+        }
+    }
+    PetriNetClassification { violations }
+}
@@ -0,0 +1,130 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+
+
+
+
+fn gcd(a : i64, b : i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// a row of the tableau of the Farkas algorithm
+/// i.e., a non-negative combination of the rows of the original matrix together with the resulting row
+#[derive(PartialEq, Eq, Clone)]
+struct FarkasRow {
+    remaining : Vec<i64>,
+    combination : Vec<i64>
+}
+
+impl FarkasRow {
+    fn combine(&self, self_factor : i64, other : &FarkasRow, other_factor : i64) -> Self {
+        let add = |left : &[i64], right : &[i64]| -> Vec<i64> {
+            left.iter().zip(right.iter()).map(|(l,r)| self_factor * l + other_factor * r).collect()
+        };
+        let mut row = Self {
+            remaining : add(&self.remaining, &other.remaining),
+            combination : add(&self.combination, &other.combination)
+        };
+        let divisor = row.remaining.iter().chain(row.combination.iter()).fold(0, |acc,value| gcd(acc, *value));
+        if divisor > 1 {
+            row.remaining.iter_mut().chain(row.combination.iter_mut()).for_each(|value| *value /= divisor);
+        }
+        row
+    }
+
+    fn support(&self) -> Vec<usize> {
+        self.combination.iter().enumerate()
+            .filter(|(_,value)| **value != 0)
+            .map(|(index,_)| index)
+            .collect()
+    }
+}
+
+/// whether a sorted support is strictly included in another one
+fn is_strict_subset(smaller : &[usize], larger : &[usize]) -> bool {
+    smaller.len() < larger.len() && smaller.iter().all(|index| larger.binary_search(index).is_ok())
+}
+
+fn keep_minimal_supports(rows : Vec<FarkasRow>) -> Vec<FarkasRow> {
+    let mut unique_rows : Vec<FarkasRow> = Vec::new();
+    for row in rows {
+        if !unique_rows.contains(&row) {
+            unique_rows.push(row);
+        }
+    }
+    let supports : Vec<Vec<usize>> = unique_rows.iter().map(|row| row.support()).collect();
+    unique_rows.into_iter().enumerate()
+        .filter(|(index,_)| !supports.iter().any(|other| is_strict_subset(other, &supports[*index])))
+        .map(|(_,row)| row)
+        .collect()
+}
+
+
+/// computes, using the Farkas algorithm, the minimal semi-positive vectors y (y ≥ 0, y ≠ 0) such that y.A = 0
+/// where A is given as its list of rows, all of length "num_columns"
+///
+/// the vectors are given as maps from the indices of the rows to their (positive) coefficients
+/// and have minimal supports, their coefficients being coprime
+/// every semi-positive vector y such that y.A = 0 is a non-negative rational combination of these vectors
+pub(crate) fn compute_minimal_semi_positive_annullers(
+    matrix : &[Vec<i64>],
+    num_columns : usize
+) -> Vec<BTreeMap<usize,u64>> {
+    let num_rows = matrix.len();
+    let mut tableau : Vec<FarkasRow> = matrix.iter().enumerate()
+        .map(|(row_index,row)| {
+            let mut combination = vec![0; num_rows];
+            combination[row_index] = 1;
+            FarkasRow { remaining : row.clone(), combination }
+        })
+        .collect();
+    for column in 0..num_columns {
+        let mut next_tableau = Vec::new();
+        let mut positive_rows = Vec::new();
+        let mut negative_rows = Vec::new();
+        for row in tableau {
+            match row.remaining[column].cmp(&0) {
+                std::cmp::Ordering::Equal => next_tableau.push(row),
+                std::cmp::Ordering::Greater => positive_rows.push(row),
+                std::cmp::Ordering::Less => negative_rows.push(row)
+            }
+        }
+        for positive_row in &positive_rows {
+            for negative_row in &negative_rows {
+                let positive_factor = -negative_row.remaining[column];
+                let negative_factor = positive_row.remaining[column];
+                next_tableau.push(positive_row.combine(positive_factor, negative_row, negative_factor));
+            }
+        }
+        tableau = keep_minimal_supports(next_tableau);
+    }
+    let mut annullers : Vec<BTreeMap<usize,u64>> = tableau.into_iter()
+        .map(|row| {
+            row.combination.into_iter().enumerate()
+                .filter(|(_,value)| *value != 0)
+                .map(|(index,value)| (index, value as u64))
+                .collect()
+        })
+        .collect();
+    annullers.sort();
+    annullers
+}
@@ -0,0 +1,62 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeSet;
+
+use crate::model::net::PetriNet;
+
+
+
+
+/// the flow relation of a net, i.e., the presets and postsets of its places and transitions
+/// (in the sense of the underlying graph, regardless of the weights of the arcs)
+///
+/// read arcs are considered as self-loops (the tested tokens are consumed and produced back)
+/// whereas inhibitor and reset arcs are ignored
+pub(crate) struct PetriFlowRelation {
+    pub(crate) place_presets : Vec<BTreeSet<usize>>,
+    pub(crate) place_postsets : Vec<BTreeSet<usize>>,
+    pub(crate) transition_presets : Vec<BTreeSet<usize>>,
+    pub(crate) transition_postsets : Vec<BTreeSet<usize>>
+}
+
+impl PetriFlowRelation {
+    pub(crate) fn from_net(petri_net : &PetriNet) -> Self {
+        let mut place_presets = vec![BTreeSet::new(); petri_net.get_places().len()];
+        let mut place_postsets = vec![BTreeSet::new(); petri_net.get_places().len()];
+        let mut transition_presets = Vec::new();
+        let mut transition_postsets = Vec::new();
+        for (transition_id,transition) in petri_net.get_transitions().iter().enumerate() {
+            let preset : BTreeSet<usize> = transition.iter_preset_tokens()
+                .chain(transition.iter_read_tokens())
+                .map(|(place_id,_)| *place_id)
+                .collect();
+            let postset : BTreeSet<usize> = transition.iter_postset_tokens()
+                .chain(transition.iter_read_tokens())
+                .map(|(place_id,_)| *place_id)
+                .collect();
+            for place_id in &preset {
+                place_postsets[*place_id].insert(transition_id);
+            }
+            for place_id in &postset {
+                place_presets[*place_id].insert(transition_id);
+            }
+            transition_presets.push(preset);
+            transition_postsets.push(postset);
+        }
+        Self { place_presets, place_postsets, transition_presets, transition_postsets }
+    }
+}
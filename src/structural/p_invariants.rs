@@ -0,0 +1,113 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::model::incidence::PetriIncidenceMatrices;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::structural::farkas::compute_minimal_semi_positive_annullers;
+
+
+
+
+/// a semi-positive P-invariant of a net, i.e., a weighting y ≥ 0 of its places such that y.C = 0
+/// so that the weighted sum of the tokens in these places is the same in all the reachable markings
+#[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+pub struct PetriPlaceInvariant {
+    /// from place ids to their (positive) weights
+    pub weights : BTreeMap<usize,u64>
+}
+
+impl PetriPlaceInvariant {
+    pub fn new(weights : BTreeMap<usize,u64>) -> Self {
+        Self { weights }
+    }
+
+    /// the places with a positive weight
+    pub fn iter_support(&self) -> impl Iterator<Item=&usize> {
+        self.weights.keys()
+    }
+
+    /// the weighted sum of the tokens of a marking, which is preserved by the firing of any transition
+    /// (e.g., the sum for the initial marking holds in all reachable markings)
+    pub fn get_token_sum(&self, marking : &Marking) -> u64 {
+        self.weights.iter()
+            .map(|(place_id,weight)| {
+                match marking.get_num_toks_at_place(place_id) {
+                    Some(toks) => *weight * (*toks as u64),
+                    None => 0
+                }
+            })
+            .sum()
+    }
+
+    /// a bound on the number of tokens of a place of the support for all markings having a certain token sum
+    pub fn get_place_bound(&self, place_id : &usize, token_sum : u64) -> Option<u64> {
+        self.weights.get(place_id).map(|weight| token_sum / weight)
+    }
+}
+
+
+/// computes a generating set of the semi-positive P-invariants of a net,
+/// which consists of its minimal semi-positive P-invariants (having minimal supports and coprime weights)
+///
+/// read and inhibitor arcs do not move tokens so they are irrelevant
+/// whereas places which are emptied by reset arcs are not conserved, so they do not belong to any P-invariant
+pub fn compute_place_invariants(petri_net : &PetriNet) -> Vec<PetriPlaceInvariant> {
+    let matrices = PetriIncidenceMatrices::from_net(petri_net);
+    let num_transitions = petri_net.get_transitions().len();
+    let reset_places : HashSet<usize> = petri_net.get_transitions().iter()
+        .flat_map(|transition| transition.iter_reset_places().cloned())
+        .collect();
+    let rows : Vec<Vec<i64>> = (0..petri_net.get_places().len())
+        .map(|place_id| {
+            let mut row = vec![0; num_transitions];
+            for (transition_id,effect) in matrices.incidence.iter_row(place_id) {
+                row[*transition_id] = *effect;
+            }
+            row
+        })
+        .collect();
+    compute_minimal_semi_positive_annullers(&rows, num_transitions).into_iter()
+        .filter(|weights| weights.keys().all(|place_id| !reset_places.contains(place_id)))
+        .map(PetriPlaceInvariant::new)
+        .collect()
+}
+
+
+/// the minimal semi-positive P-invariants of a net together with the weighted token sum that each of them preserves
+/// from a certain initial marking
+pub fn compute_place_invariants_with_token_sums(
+    petri_net : &PetriNet,
+    initial_marking : &Marking
+) -> Vec<(PetriPlaceInvariant,u64)> {
+    compute_place_invariants(petri_net).into_iter()
+        .map(|invariant| {
+            let token_sum = invariant.get_token_sum(initial_marking);
+            (invariant,token_sum)
+        })
+        .collect()
+}
+
+
+/// whether every place of the net belongs to the support of a semi-positive P-invariant
+/// (i.e., the net is covered by a positive P-invariant)
+/// in which case the net is structurally bounded i.e., bounded for any initial marking
+pub fn is_covered_by_place_invariants(petri_net : &PetriNet) -> bool {
+    let invariants = compute_place_invariants(petri_net);
+    (0..petri_net.get_places().len()).all(|place_id| invariants.iter().any(|invariant| invariant.weights.contains_key(&place_id)))
+}
@@ -0,0 +1,185 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+
+
+
+fn gcd(a : i128, b : i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+
+/// an exact rational number, kept in normal form (coprime numerator and denominator, positive denominator)
+///
+/// the arithmetic operators panic on overflow, whereas the "checked_*" methods return None
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PetriRational {
+    numerator : i128,
+    denominator : i128
+}
+
+impl PetriRational {
+    /// panics if the denominator is zero or if the normal form overflows
+    pub fn new(numerator : i128, denominator : i128) -> Self {
+        assert!(denominator != 0, "rational with a zero denominator");
+        Self::checked_new(numerator, denominator).expect("rational overflow")
+    }
+
+    /// returns None if the denominator is zero or if the normal form overflows
+    pub fn checked_new(numerator : i128, denominator : i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator.checked_abs()?, denominator.checked_abs()?);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if denominator < 0 {
+            Some(Self { numerator : numerator.checked_neg()?, denominator : denominator.checked_neg()? })
+        } else {
+            Some(Self { numerator, denominator })
+        }
+    }
+
+    pub fn from_integer(value : i128) -> Self {
+        Self { numerator : value, denominator : 1 }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_integer(0)
+    }
+
+    pub fn get_numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn get_denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn floor(&self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    pub fn ceil(&self) -> i128 {
+        -(-self.numerator).div_euclid(self.denominator)
+    }
+
+    pub fn checked_add(self, other : Self) -> Option<Self> {
+        let left = self.numerator.checked_mul(other.denominator)?;
+        let right = other.numerator.checked_mul(self.denominator)?;
+        Self::checked_new(left.checked_add(right)?, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    pub fn checked_sub(self, other : Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other : Self) -> Option<Self> {
+        Self::checked_new(self.numerator.checked_mul(other.numerator)?, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    /// returns None if the divisor is zero
+    pub fn checked_div(self, other : Self) -> Option<Self> {
+        Self::checked_new(self.numerator.checked_mul(other.denominator)?, self.denominator.checked_mul(other.numerator)?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self { numerator : self.numerator.checked_neg()?, denominator : self.denominator })
+    }
+}
+
+impl Add for PetriRational {
+    type Output = Self;
+    fn add(self, other : Self) -> Self {
+        self.checked_add(other).expect("rational overflow")
+    }
+}
+
+impl Sub for PetriRational {
+    type Output = Self;
+    fn sub(self, other : Self) -> Self {
+        self.checked_sub(other).expect("rational overflow")
+    }
+}
+
+impl Mul for PetriRational {
+    type Output = Self;
+    fn mul(self, other : Self) -> Self {
+        self.checked_mul(other).expect("rational overflow")
+    }
+}
+
+impl Div for PetriRational {
+    type Output = Self;
+    fn div(self, other : Self) -> Self {
+        assert!(other.numerator != 0, "rational division by zero");
+        self.checked_div(other).expect("rational overflow")
+    }
+}
+
+impl Neg for PetriRational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.checked_neg().expect("rational overflow")
+    }
+}
+
+impl PartialOrd for PetriRational {
+    fn partial_cmp(&self, other : &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// compares a/b and c/d (with positive denominators) without overflowing
+/// by comparing their integer parts, then the inverses of their fractional parts (as in the Euclidean algorithm)
+fn compare_fractions(a : i128, b : i128, c : i128, d : i128) -> Ordering {
+    let integer_parts = a.div_euclid(b).cmp(&c.div_euclid(d));
+    if integer_parts != Ordering::Equal {
+        return integer_parts;
+    }
+    match (a.rem_euclid(b), c.rem_euclid(d)) {
+        (0, 0) => Ordering::Equal,
+        (0, _) => Ordering::Less,
+        (_, 0) => Ordering::Greater,
+        (left_rem, right_rem) => compare_fractions(d, right_rem, b, left_rem)
+    }
+}
+
+impl Ord for PetriRational {
+    fn cmp(&self, other : &Self) -> Ordering {
+        compare_fractions(self.numerator, self.denominator, other.numerator, other.denominator)
+    }
+}
+
+impl std::fmt::Display for PetriRational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
@@ -0,0 +1,246 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::structural::rational::PetriRational;
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum LinearRelation {
+    LowerOrEqual,
+    GreaterOrEqual,
+    Equal
+}
+
+/// a linear constraint "coefficients.x relation bound" over non-negative variables
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct LinearConstraint {
+    pub(crate) coefficients : Vec<PetriRational>,
+    pub(crate) relation : LinearRelation,
+    pub(crate) bound : PetriRational
+}
+
+impl LinearConstraint {
+    pub(crate) fn new(coefficients : Vec<PetriRational>, relation : LinearRelation, bound : PetriRational) -> Self {
+        Self { coefficients, relation, bound }
+    }
+}
+
+/// some intermediate value of the simplex algorithm does not fit in the rationals over i128
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct RationalOverflow;
+
+
+/// finds a solution x ≥ 0 of a system of linear constraints over the rationals, if any,
+/// using the first phase of the simplex algorithm (with Bland's rule, so that it terminates)
+/// i.e., by minimizing the sum of artificial variables which are added to the constraints
+pub(crate) fn find_non_negative_rational_solution(
+    num_variables : usize,
+    constraints : &[LinearConstraint]
+) -> Result<Option<Vec<PetriRational>>,RationalOverflow> {
+    let zero = PetriRational::zero();
+    let one = PetriRational::from_integer(1);
+    // the columns are those of the variables, then of the slack variables, then of the artificial variables
+    let num_slacks = constraints.iter().filter(|constraint| constraint.relation != LinearRelation::Equal).count();
+    let num_artificials = constraints.iter().filter(|constraint| {
+        let flipped = constraint.bound < zero;
+        match constraint.relation {
+            LinearRelation::LowerOrEqual => flipped,
+            LinearRelation::GreaterOrEqual => !flipped,
+            LinearRelation::Equal => true
+        }
+    }).count();
+    let num_columns = num_variables + num_slacks + num_artificials;
+    let mut rows : Vec<Vec<PetriRational>> = Vec::new();
+    let mut rhs : Vec<PetriRational> = Vec::new();
+    let mut basis : Vec<usize> = Vec::new();
+    let mut next_slack = num_variables;
+    let mut next_artificial = num_variables + num_slacks;
+    for constraint in constraints {
+        let mut row = vec![zero; num_columns];
+        row[..num_variables].copy_from_slice(&constraint.coefficients);
+        let mut bound = constraint.bound;
+        let mut relation = constraint.relation;
+        // ensures a non-negative right hand side
+        if bound < zero {
+            for value in row.iter_mut() {
+                *value = value.checked_neg().ok_or(RationalOverflow)?;
+            }
+            bound = bound.checked_neg().ok_or(RationalOverflow)?;
+            relation = match relation {
+                LinearRelation::LowerOrEqual => LinearRelation::GreaterOrEqual,
+                LinearRelation::GreaterOrEqual => LinearRelation::LowerOrEqual,
+                LinearRelation::Equal => LinearRelation::Equal
+            };
+        }
+        match relation {
+            LinearRelation::LowerOrEqual => {
+                row[next_slack] = one;
+                basis.push(next_slack);
+                next_slack += 1;
+            },
+            LinearRelation::GreaterOrEqual => {
+                row[next_slack] = -one;
+                next_slack += 1;
+                row[next_artificial] = one;
+                basis.push(next_artificial);
+                next_artificial += 1;
+            },
+            LinearRelation::Equal => {
+                row[next_artificial] = one;
+                basis.push(next_artificial);
+                next_artificial += 1;
+            }
+        }
+        rows.push(row);
+        rhs.push(bound);
+    }
+    // reduced costs of the objective (the sum of the artificial variables) and its opposite value
+    let is_artificial = |column : usize| column >= num_variables + num_slacks;
+    let mut reduced_costs = vec![zero; num_columns];
+    let mut opposite_objective = zero;
+    for (row_index,row) in rows.iter().enumerate() {
+        if is_artificial(basis[row_index]) {
+            for column in 0..num_columns {
+                if !is_artificial(column) {
+                    reduced_costs[column] = reduced_costs[column].checked_sub(row[column]).ok_or(RationalOverflow)?;
+                }
+            }
+            opposite_objective = opposite_objective.checked_sub(rhs[row_index]).ok_or(RationalOverflow)?;
+        }
+    }
+    // ***
+    while let Some(entering) = (0..num_columns).find(|column| reduced_costs[*column] < zero) {
+        let mut leaving : Option<(usize,PetriRational)> = None;
+        for (row_index,row) in rows.iter().enumerate() {
+            if row[entering] > zero {
+                let ratio = rhs[row_index].checked_div(row[entering]).ok_or(RationalOverflow)?;
+                let is_better = match leaving {
+                    None => true,
+                    Some((leaving_row,leaving_ratio)) => {
+                        ratio < leaving_ratio || (ratio == leaving_ratio && basis[row_index] < basis[leaving_row])
+                    }
+                };
+                if is_better {
+                    leaving = Some((row_index,ratio));
+                }
+            }
+        }
+        // the objective is bounded from below by 0 so there is always a leaving variable
+        let (pivot_row,_) = leaving.unwrap();
+        let pivot = rows[pivot_row][entering];
+        for value in rows[pivot_row].iter_mut() {
+            *value = value.checked_div(pivot).ok_or(RationalOverflow)?;
+        }
+        rhs[pivot_row] = rhs[pivot_row].checked_div(pivot).ok_or(RationalOverflow)?;
+        let normalized_row = rows[pivot_row].clone();
+        let normalized_rhs = rhs[pivot_row];
+        for row_index in 0..rows.len() {
+            if row_index != pivot_row {
+                let factor = rows[row_index][entering];
+                if factor != zero {
+                    for (value,pivot_value) in rows[row_index].iter_mut().zip(normalized_row.iter()) {
+                        *value = checked_sub_product(*value, factor, *pivot_value)?;
+                    }
+                    rhs[row_index] = checked_sub_product(rhs[row_index], factor, normalized_rhs)?;
+                }
+            }
+        }
+        let factor = reduced_costs[entering];
+        for (value,pivot_value) in reduced_costs.iter_mut().zip(normalized_row.iter()) {
+            *value = checked_sub_product(*value, factor, *pivot_value)?;
+        }
+        opposite_objective = checked_sub_product(opposite_objective, factor, normalized_rhs)?;
+        basis[pivot_row] = entering;
+    }
+    // ***
+    if opposite_objective != zero {
+        return Ok(None);
+    }
+    let mut solution = vec![zero; num_variables];
+    for (row_index,column) in basis.iter().enumerate() {
+        if *column < num_variables {
+            solution[*column] = rhs[row_index];
+        }
+    }
+    Ok(Some(solution))
+}
+
+/// value - factor * other
+fn checked_sub_product(value : PetriRational, factor : PetriRational, other : PetriRational) -> Result<PetriRational,RationalOverflow> {
+    factor.checked_mul(other)
+        .and_then(|product| value.checked_sub(product))
+        .ok_or(RationalOverflow)
+}
+
+
+/// the outcome of the search for an integer solution
+pub(crate) enum IntegerSearchOutcome {
+    /// there is no integer solution (not even a rational one)
+    Infeasible,
+    /// an integer solution
+    Integer(Vec<PetriRational>),
+    /// a rational solution, the search for an integer one having exceeded its budget or overflowed
+    Rational(Vec<PetriRational>),
+    /// the search for a rational solution overflowed
+    Overflow
+}
+
+/// finds a solution x ≥ 0 of a system of linear constraints over the integers
+/// by branch and bound over the rational solutions, exploring at most a certain number of nodes
+pub(crate) fn find_non_negative_integer_solution(
+    num_variables : usize,
+    constraints : &[LinearConstraint],
+    max_explored_nodes : usize
+) -> IntegerSearchOutcome {
+    let root_solution = match find_non_negative_rational_solution(num_variables, constraints) {
+        Err(RationalOverflow) => {return IntegerSearchOutcome::Overflow;},
+        Ok(None) => {return IntegerSearchOutcome::Infeasible;},
+        Ok(Some(solution)) => solution
+    };
+    let mut queue = vec![(constraints.to_vec(),root_solution.clone())];
+    let mut num_explored_nodes = 0;
+    while let Some((node_constraints,solution)) = queue.pop() {
+        let fractional = solution.iter().enumerate().find(|(_,value)| !value.is_integer());
+        let (variable,value) = match fractional {
+            None => {return IntegerSearchOutcome::Integer(solution);},
+            Some((variable,value)) => (variable,*value)
+        };
+        num_explored_nodes += 1;
+        if num_explored_nodes > max_explored_nodes {
+            return IntegerSearchOutcome::Rational(root_solution);
+        }
+        let mut unit = vec![PetriRational::zero(); num_variables];
+        unit[variable] = PetriRational::from_integer(1);
+        let branches = [
+            LinearConstraint::new(unit.clone(), LinearRelation::LowerOrEqual, PetriRational::from_integer(value.floor())),
+            LinearConstraint::new(unit, LinearRelation::GreaterOrEqual, PetriRational::from_integer(value.ceil()))
+        ];
+        for branch in branches {
+            let mut branch_constraints = node_constraints.clone();
+            branch_constraints.push(branch);
+            match find_non_negative_rational_solution(num_variables, &branch_constraints) {
+                Err(RationalOverflow) => {return IntegerSearchOutcome::Rational(root_solution);},
+                Ok(None) => {},
+                Ok(Some(branch_solution)) => {
+                    queue.push((branch_constraints,branch_solution));
+                }
+            }
+        }
+    }
+    IntegerSearchOutcome::Infeasible
+}
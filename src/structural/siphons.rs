@@ -0,0 +1,160 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::structural::flow::PetriFlowRelation;
+
+
+
+
+/// the largest siphon included in a set of places (possibly empty)
+/// a siphon is a set of places S such that every transition which produces tokens in S also consumes tokens from S
+/// so that once a siphon is empty, it remains empty
+fn get_maximal_siphon_within(flow : &PetriFlowRelation, places : &BTreeSet<usize>) -> BTreeSet<usize> {
+    let mut siphon = places.clone();
+    loop {
+        let removable = siphon.iter().find(|place_id| {
+            flow.place_presets[**place_id].iter().any(|transition_id| flow.transition_presets[*transition_id].is_disjoint(&siphon))
+        }).cloned();
+        match removable {
+            Some(place_id) => {siphon.remove(&place_id);},
+            None => {return siphon;}
+        }
+    }
+}
+
+/// the largest trap included in a set of places (possibly empty)
+/// a trap is a set of places S such that every transition which consumes tokens from S also produces tokens in S
+/// so that once a trap is marked, it remains marked
+fn get_maximal_trap_within(flow : &PetriFlowRelation, places : &BTreeSet<usize>) -> BTreeSet<usize> {
+    let mut trap = places.clone();
+    loop {
+        let removable = trap.iter().find(|place_id| {
+            flow.place_postsets[**place_id].iter().any(|transition_id| flow.transition_postsets[*transition_id].is_disjoint(&trap))
+        }).cloned();
+        match removable {
+            Some(place_id) => {trap.remove(&place_id);},
+            None => {return trap;}
+        }
+    }
+}
+
+/// a minimal siphon included in a non-empty siphon
+fn shrink_to_minimal_siphon(flow : &PetriFlowRelation, siphon : BTreeSet<usize>) -> BTreeSet<usize> {
+    let mut siphon = siphon;
+    'shrink: loop {
+        for place_id in siphon.iter() {
+            let mut smaller = siphon.clone();
+            smaller.remove(place_id);
+            let smaller_siphon = get_maximal_siphon_within(flow, &smaller);
+            if !smaller_siphon.is_empty() {
+                siphon = smaller_siphon;
+                continue 'shrink;
+            }
+        }
+        return siphon;
+    }
+}
+
+
+/// the largest siphon of the net that is included in a certain set of places (possibly empty)
+///
+/// the flow of tokens is that of the preset and postset arcs, read arcs being considered as self-loops
+/// (inhibitor and reset arcs are ignored)
+pub fn get_maximal_siphon(petri_net : &PetriNet, places : &BTreeSet<usize>) -> BTreeSet<usize> {
+    get_maximal_siphon_within(&PetriFlowRelation::from_net(petri_net), places)
+}
+
+/// the largest trap of the net that is included in a certain set of places (possibly empty)
+/// (see "get_maximal_siphon" for the flow of tokens that is considered)
+pub fn get_maximal_trap(petri_net : &PetriNet, places : &BTreeSet<usize>) -> BTreeSet<usize> {
+    get_maximal_trap_within(&PetriFlowRelation::from_net(petri_net), places)
+}
+
+
+/// enumerates the minimal (non-empty) siphons of the net, in increasing order
+/// (see "get_maximal_siphon" for the flow of tokens that is considered)
+///
+/// the enumeration finds a minimal siphon and then branches on the exclusion of each of its places
+/// given that any other minimal siphon misses at least one of them
+/// so it may take an exponential time, as there may be exponentially many minimal siphons
+pub fn compute_minimal_siphons(petri_net : &PetriNet) -> Vec<BTreeSet<usize>> {
+    let flow = PetriFlowRelation::from_net(petri_net);
+    let mut minimal_siphons = BTreeSet::new();
+    let mut explored_exclusions = HashSet::new();
+    let mut queue = vec![BTreeSet::new()];
+    while let Some(excluded) = queue.pop() {
+        if !explored_exclusions.insert(excluded.clone()) {
+            continue;
+        }
+        let allowed = (0..petri_net.get_places().len()).filter(|place_id| !excluded.contains(place_id)).collect();
+        let siphon = get_maximal_siphon_within(&flow, &allowed);
+        if siphon.is_empty() {
+            continue;
+        }
+        let minimal_siphon = shrink_to_minimal_siphon(&flow, siphon);
+        for place_id in &minimal_siphon {
+            let mut next_excluded = excluded.clone();
+            next_excluded.insert(*place_id);
+            queue.push(next_excluded);
+        }
+        minimal_siphons.insert(minimal_siphon);
+    }
+    minimal_siphons.into_iter().collect()
+}
+
+
+/// the maximal traps included in the minimal siphons of the net
+/// i.e., for each minimal siphon (in increasing order), the largest trap that it contains (possibly empty)
+pub fn compute_maximal_traps_of_minimal_siphons(petri_net : &PetriNet) -> Vec<(BTreeSet<usize>,BTreeSet<usize>)> {
+    let flow = PetriFlowRelation::from_net(petri_net);
+    compute_minimal_siphons(petri_net).into_iter()
+        .map(|siphon| {
+            let trap = get_maximal_trap_within(&flow, &siphon);
+            (siphon,trap)
+        })
+        .collect()
+}
+
+
+/// a minimal siphon which does not contain any initially marked trap
+/// so that it may be emptied, which may lead to a deadlock
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PetriUnprotectedSiphon {
+    pub siphon : BTreeSet<usize>,
+    /// the largest trap included in the siphon, which is not initially marked (and possibly empty)
+    pub maximal_trap : BTreeSet<usize>
+}
+
+
+/// checks the siphon-trap property (Commoner's condition) i.e., whether every minimal siphon contains an initially marked trap
+/// and returns the minimal siphons which do not
+///
+/// a siphon which contains a marked trap can never be emptied
+/// so if there are none, then no reachable marking is a deadlock, for any ordinary net (with arcs of weight 1)
+/// without inhibitor or reset arcs, and, for free-choice nets, this is moreover equivalent to liveness
+pub fn find_siphons_without_marked_trap(
+    petri_net : &PetriNet,
+    initial_marking : &Marking
+) -> Vec<PetriUnprotectedSiphon> {
+    compute_maximal_traps_of_minimal_siphons(petri_net).into_iter()
+        .filter(|(_,trap)| !trap.iter().any(|place_id| initial_marking.get_num_toks_at_place(place_id).is_some_and(|toks| *toks > 0)))
+        .map(|(siphon,maximal_trap)| PetriUnprotectedSiphon { siphon, maximal_trap })
+        .collect()
+}
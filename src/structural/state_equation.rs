@@ -0,0 +1,154 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+
+use crate::model::incidence::PetriIncidenceMatrices;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model_checking::props::{BuiltinPetriAtomicProposition, TokensCountAtom, TokensCountRelation};
+use crate::structural::rational::PetriRational;
+use crate::structural::simplex::{find_non_negative_integer_solution, IntegerSearchOutcome, LinearConstraint, LinearRelation};
+
+
+
+
+/// the maximal number of nodes explored by the branch and bound search for an integer solution of the state equation
+const MAX_EXPLORED_NODES : usize = 512;
+
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PetriStateEquationVerdict {
+    /// the state equation has no (integer) solution which satisfies the constraints
+    /// so that no reachable marking satisfies them
+    ProvablyUnreachable,
+    /// the state equation has a solution which satisfies the constraints
+    /// so that a reachable marking may satisfy them
+    /// the candidate Parikh vector (from transition ids to numbers of firings) is integral
+    /// unless the search for an integer solution has exceeded its budget, in which case it is a rational solution
+    /// (in any case, there may not be any firing sequence with that Parikh vector)
+    /// it is empty if the search overflowed before finding any solution, in which case the state equation may have no solution
+    Inconclusive{parikh_vector : BTreeMap<usize,PetriRational>}
+}
+
+
+/// a linear expression over the variables of the state equation, with a constant term
+struct LinearExpression {
+    coefficients : Vec<PetriRational>,
+    constant : PetriRational
+}
+
+impl LinearExpression {
+    fn subtract(mut self, other : LinearExpression) -> Self {
+        for (value,other_value) in self.coefficients.iter_mut().zip(other.coefficients) {
+            *value = *value - other_value;
+        }
+        self.constant = self.constant - other.constant;
+        self
+    }
+
+    /// the constraint "expression relation bound"
+    fn constrain(self, relation : LinearRelation, bound : i128) -> LinearConstraint {
+        let bound = PetriRational::from_integer(bound) - self.constant;
+        LinearConstraint::new(self.coefficients, relation, bound)
+    }
+}
+
+
+/// decides whether the state equation M = M0 + C.σ (with σ ≥ 0 integral) has a solution
+/// such that the marking M satisfies a conjunction of "TokensCount" constraints
+/// if it has none, then no reachable marking satisfies these constraints
+///
+/// the state equation is a relaxation of reachability, which is made coarser for extended nets so as to remain sound:
+/// - read and inhibitor arcs are ignored, as are the constraints on the previous tagged transition
+/// - each reset arc may remove any number of tokens from its place
+/// - each place with a capacity may not contain more tokens than its capacity (or its initial number of tokens, if higher)
+pub fn check_state_equation(
+    petri_net : &PetriNet,
+    initial_marking : &Marking,
+    constraints : &[BuiltinPetriAtomicProposition]
+) -> PetriStateEquationVerdict {
+    let matrices = PetriIncidenceMatrices::from_net(petri_net);
+    let num_transitions = petri_net.get_transitions().len();
+    // the variables are the numbers of firings of the transitions
+    // followed by the numbers of tokens removed by each reset arc
+    let reset_arcs : Vec<(usize,usize)> = petri_net.get_transitions().iter().enumerate()
+        .flat_map(|(transition_id,transition)| transition.iter_reset_places().map(move |place_id| (transition_id,*place_id)))
+        .collect();
+    let num_variables = num_transitions + reset_arcs.len();
+    let tokens_at = |place_id : usize| -> LinearExpression {
+        let mut coefficients = vec![PetriRational::zero(); num_variables];
+        for (transition_id,effect) in matrices.incidence.iter_row(place_id) {
+            coefficients[*transition_id] = PetriRational::from_integer(*effect as i128);
+        }
+        for (reset_index,(_,reset_place_id)) in reset_arcs.iter().enumerate() {
+            if *reset_place_id == place_id {
+                coefficients[num_transitions + reset_index] = PetriRational::from_integer(-1);
+            }
+        }
+        let initial_toks = initial_marking.get_num_toks_at_place(&place_id).cloned().unwrap_or(0);
+        LinearExpression { coefficients, constant : PetriRational::from_integer(initial_toks as i128) }
+    };
+    let atom_expression = |atom : &TokensCountAtom| -> LinearExpression {
+        match atom {
+            TokensCountAtom::RawInteger(raw_int) => {
+                LinearExpression {
+                    coefficients : vec![PetriRational::zero(); num_variables],
+                    constant : PetriRational::from_integer(*raw_int as i128)
+                }
+            },
+            TokensCountAtom::NumberOfTokensInPlace(place_id) => tokens_at(*place_id)
+        }
+    };
+    // ***
+    let mut linear_constraints = Vec::new();
+    for place_id in 0..petri_net.get_places().len() {
+        linear_constraints.push(tokens_at(place_id).constrain(LinearRelation::GreaterOrEqual, 0));
+        if let Some(capacity) = petri_net.get_place_capacity(&place_id) {
+            let initial_toks = initial_marking.get_num_toks_at_place(&place_id).cloned().unwrap_or(0);
+            let bound = u32::max(capacity, initial_toks) as i128;
+            linear_constraints.push(tokens_at(place_id).constrain(LinearRelation::LowerOrEqual, bound));
+        }
+    }
+    for constraint in constraints {
+        if let BuiltinPetriAtomicProposition::TokensCount(relation, left, right) = constraint {
+            let difference = atom_expression(left).subtract(atom_expression(right));
+            // numbers of tokens are integers so that strict inequalities can be tightened
+            let linear_constraint = match relation {
+                TokensCountRelation::StrictlyGreater => difference.constrain(LinearRelation::GreaterOrEqual, 1),
+                TokensCountRelation::GreaterOrEqual => difference.constrain(LinearRelation::GreaterOrEqual, 0),
+                TokensCountRelation::Equal => difference.constrain(LinearRelation::Equal, 0),
+                TokensCountRelation::LowerOrEqual => difference.constrain(LinearRelation::LowerOrEqual, 0),
+                TokensCountRelation::StrictlyLower => difference.constrain(LinearRelation::LowerOrEqual, -1)
+            };
+            linear_constraints.push(linear_constraint);
+        }
+    }
+    // ***
+    let solution = match find_non_negative_integer_solution(num_variables, &linear_constraints, MAX_EXPLORED_NODES) {
+        IntegerSearchOutcome::Infeasible => {
+            return PetriStateEquationVerdict::ProvablyUnreachable;
+        },
+        IntegerSearchOutcome::Overflow => {
+            return PetriStateEquationVerdict::Inconclusive { parikh_vector : BTreeMap::new() };
+        },
+        IntegerSearchOutcome::Integer(solution) | IntegerSearchOutcome::Rational(solution) => solution
+    };
+    let parikh_vector = solution.into_iter().take(num_transitions).enumerate()
+        .filter(|(_,value)| *value != PetriRational::zero())
+        .collect();
+    PetriStateEquationVerdict::Inconclusive { parikh_vector }
+}
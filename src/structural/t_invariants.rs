@@ -0,0 +1,105 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::model::incidence::PetriIncidenceMatrices;
+use crate::model::label::PetriTransitionLabel;
+use crate::model::net::PetriNet;
+use crate::structural::farkas::compute_minimal_semi_positive_annullers;
+
+
+
+
+/// a semi-positive T-invariant of a net, i.e., a Parikh vector x ≥ 0 such that C.x = 0
+/// so that any firing sequence with that Parikh vector leads back to the marking from which it starts
+#[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+pub struct PetriTransitionInvariant {
+    /// from transition ids to their (positive) numbers of firings
+    pub firing_counts : BTreeMap<usize,u64>
+}
+
+impl PetriTransitionInvariant {
+    pub fn new(firing_counts : BTreeMap<usize,u64>) -> Self {
+        Self { firing_counts }
+    }
+
+    /// the transitions with a positive number of firings
+    pub fn iter_support(&self) -> impl Iterator<Item=&usize> {
+        self.firing_counts.keys()
+    }
+
+    /// the labels of the transitions of the support together with their numbers of firings
+    pub fn get_labelled_firing_counts(&self, petri_net : &PetriNet) -> Vec<(Option<Arc<PetriTransitionLabel>>,u64)> {
+        self.firing_counts.iter()
+            .map(|(transition_id,count)| (petri_net.get_transitions()[*transition_id].transition_label.clone(), *count))
+            .collect()
+    }
+
+    /// a textual representation of the invariant e.g., "lock + 2*unlock"
+    /// in which unlabelled transitions are referred to by their stable identifiers
+    pub fn repr_with_labels(&self, petri_net : &PetriNet) -> String {
+        self.firing_counts.iter()
+            .map(|(transition_id,count)| {
+                let name = match &petri_net.get_transitions()[*transition_id].transition_label {
+                    Some(label) => label.label.clone(),
+                    None => petri_net.get_transition_stable_id(*transition_id).unwrap().to_string()
+                };
+                if *count == 1 {
+                    name
+                } else {
+                    format!("{}*{}", count, name)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" + ")
+    }
+}
+
+
+/// computes a generating set of the semi-positive T-invariants of a net,
+/// which consists of its minimal semi-positive T-invariants (having minimal supports and coprime numbers of firings)
+///
+/// transitions with reset arcs do not have a constant effect on the marking, so they do not belong to any T-invariant
+pub fn compute_transition_invariants(petri_net : &PetriNet) -> Vec<PetriTransitionInvariant> {
+    let matrices = PetriIncidenceMatrices::from_net(petri_net);
+    let num_places = petri_net.get_places().len();
+    let rows : Vec<Vec<i64>> = (0..petri_net.get_transitions().len())
+        .map(|transition_id| {
+            let mut row = vec![0; num_places];
+            for (place_id,effect) in matrices.incidence.iter_column(transition_id) {
+                row[*place_id] = *effect;
+            }
+            row
+        })
+        .collect();
+    compute_minimal_semi_positive_annullers(&rows, num_places).into_iter()
+        .filter(|firing_counts| {
+            firing_counts.keys().all(|transition_id| petri_net.get_transitions()[*transition_id].number_of_reset_places() == 0)
+        })
+        .map(PetriTransitionInvariant::new)
+        .collect()
+}
+
+
+/// whether every transition of the net belongs to the support of a semi-positive T-invariant
+/// (i.e., the net is covered by a positive T-invariant)
+/// which is a necessary condition for the net to be both live and bounded
+pub fn is_covered_by_transition_invariants(petri_net : &PetriNet) -> bool {
+    let invariants = compute_transition_invariants(petri_net);
+    (0..petri_net.get_transitions().len()).all(|transition_id| invariants.iter().any(|invariant| invariant.firing_counts.contains_key(&transition_id)))
+}
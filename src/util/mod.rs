@@ -18,4 +18,6 @@ limitations under the License.
 
 pub mod vizualisation;
 pub mod parse_ctl;
-pub mod parse_pnml;
\ No newline at end of file
+pub mod parse_pnml;
+#[cfg(feature = "serde")]
+pub mod serialization;
\ No newline at end of file
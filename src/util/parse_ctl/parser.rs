@@ -16,11 +16,11 @@ limitations under the License.
 
 
 
-use std::{collections::{BTreeMap, HashMap}, rc::Rc};
+use std::{collections::{BTreeMap, HashMap}, sync::Arc};
 
-use citreelo::{ctl::{BinaryCTLOperator, CTLFormula, CTLFormulaLeaf}};
+use citreelo::{ctl::{BinaryCTLOperator, CTLFormula, CTLFormulaLeaf, UnaryCTLOperator}};
 
-use crate::{model::{label::PetriTransitionLabel, net::PetriNet}, model_checking::props::{BuiltinPetriAtomicProposition, TokensCountAtom, TokensCountRelation}, util::parse_ctl::error::PetriCtlParsingError};
+use crate::{model::{label::PetriTransitionLabel, net::PetriNet, transition::PetriTransition}, model_checking::props::{BuiltinPetriAtomicProposition, TokensCountAtom, TokensCountRelation}, util::parse_ctl::error::PetriCtlParsingError};
 
 
 pub struct BuiltinPetriCtlParser {
@@ -29,17 +29,32 @@ pub struct BuiltinPetriCtlParser {
 
     /// for each transition label maps to the index representing the tag
     /// that decorates certain states to signifies that the previous transition 
-    pub transition_label_to_ref : HashMap<String,Rc<PetriTransitionLabel>>,
+    pub transition_label_to_ref : HashMap<String,Arc<PetriTransitionLabel>>,
 
     /// for each transition label
     /// the firing condition of a transition with that label 
     /// correspond to:
     /// ∨_{transitions with that label} 
     ///     ( 
-    ///         ∧_{places that require tokens in the preset marking of the transition} 
+    ///         ∧_{places that require tokens in the preset marking of the transition or via a read arc} 
     ///             (
     ///                 tokens-count(place) >= requirement  
     ///             )
+    ///         ∧
+    ///         ∧_{places connected to the transition via an inhibitor arc} 
+    ///             (
+    ///                 tokens-count(place) < threshold  
+    ///             )
+    ///         ∧
+    ///         ∧_{places with a capacity whose number of tokens would exceed it} 
+    ///             (
+    ///                 tokens-count(place) <= capacity + consumed - produced  
+    ///             )
+    ///         ∧
+    ///         ∧_{transitions with a strictly higher priority} 
+    ///             (
+    ///                 ¬ (enabling condition of that transition) 
+    ///             )
     ///     )
     pub transition_label_to_firing_condition : HashMap<String,CTLFormula<BuiltinPetriAtomicProposition>>
 }
@@ -47,13 +62,14 @@ pub struct BuiltinPetriCtlParser {
 
 
 impl BuiltinPetriCtlParser {
+    #[allow(clippy::collapsible_if)]
     pub fn from_net
     (
         petri_net : &PetriNet
     ) -> Result<Self,PetriCtlParsingError> {
         // ***
         let mut place_name_to_index = HashMap::new();
-        for (place_id,place_content) in petri_net.places.iter().enumerate() {
+        for (place_id,place_content) in petri_net.get_places().iter().enumerate() {
             if let Some(place_lab_ref) = place_content {
                 if place_name_to_index.insert(place_lab_ref.label.to_string(), place_id).is_some() {
                     return Err(PetriCtlParsingError::MultiplePlacesHaveTheSameName);
@@ -62,7 +78,7 @@ impl BuiltinPetriCtlParser {
         }
         // ***
         let mut transition_label_to_ref = HashMap::new();
-        for transition in &petri_net.transitions {
+        for transition in petri_net.get_transitions() {
             if let Some(transition_label_ref) = &transition.transition_label {
                 if let Some(other_ref) = transition_label_to_ref.insert(
                     transition_label_ref.label.to_owned(), 
@@ -76,11 +92,22 @@ impl BuiltinPetriCtlParser {
         }
         // ***
         let mut transition_label_to_firing_condition = HashMap::new();
-        for transition in &petri_net.transitions {
+        for transition in petri_net.get_transitions() {
             if let Some(transition_label_ref) = &transition.transition_label {
                 let tr_firing_condition = {
-                    let mut preset_tokens = transition.preset_tokens.iter().map(|(x,y)|(*x,*y)).collect();
-                    preset_tokens_to_firing_condition(&mut preset_tokens)
+                    let mut conjuncts = vec![transition_to_enabling_condition(petri_net, transition)];
+                    // a transition may only fire if no transition with a higher priority is enabled
+                    for other_transition in petri_net.get_transitions() {
+                        if other_transition.get_priority() > transition.get_priority() {
+                            conjuncts.push(
+                                CTLFormula::Unary(
+                                    UnaryCTLOperator::Not,
+                                    Box::new(transition_to_enabling_condition(petri_net, other_transition))
+                                )
+                            );
+                        }
+                    }
+                    formulas_to_conjunction(conjuncts)
                 };
                 // ***
                 if let Some(ctl) = transition_label_to_firing_condition.remove(&transition_label_ref.label) {
@@ -110,32 +137,94 @@ impl BuiltinPetriCtlParser {
 }
 
 
-fn preset_tokens_to_firing_condition(
-    preset_tokens    : &mut BTreeMap<usize,u32>
+/// the condition for the transition to be enabled, regardless of priorities
+fn transition_to_enabling_condition(
+    petri_net : &PetriNet,
+    transition : &PetriTransition
 ) -> CTLFormula<BuiltinPetriAtomicProposition> {
-    if preset_tokens.is_empty() {
-        return CTLFormula::Leaf(CTLFormulaLeaf::True);
+    // a place that is both in the preset and read requires the maximum of both numbers of tokens
+    let mut required_tokens : BTreeMap<usize,u32> = transition.iter_preset_tokens().map(|(x,y)|(*x,*y)).collect();
+    for (place_id,read_num_toks) in transition.iter_read_tokens() {
+        let req_num_toks = required_tokens.entry(*place_id).or_insert(0);
+        *req_num_toks = u32::max(*req_num_toks,*read_num_toks);
     }
-    if preset_tokens.len() == 1 {
-        let (place_id,req_num_toks) = preset_tokens.iter().next().unwrap();
-        let atom = BuiltinPetriAtomicProposition::TokensCount(
-            TokensCountRelation::GreaterOrEqual, 
-            TokensCountAtom::NumberOfTokensInPlace(*place_id), 
-            TokensCountAtom::RawInteger(*req_num_toks)
+    let inhibitor_tokens : BTreeMap<usize,u32> = transition.iter_inhibitor_tokens().map(|(x,y)|(*x,*y)).collect();
+    let mut atoms = Vec::new();
+    for (place_id,req_num_toks) in required_tokens {
+        atoms.push(
+            BuiltinPetriAtomicProposition::TokensCount(
+                TokensCountRelation::GreaterOrEqual, 
+                TokensCountAtom::NumberOfTokensInPlace(place_id), 
+                TokensCountAtom::RawInteger(req_num_toks)
+            )
         );
-        return CTLFormula::Leaf(CTLFormulaLeaf::AtomicProp(atom));
     }
-    let place_id = *preset_tokens.keys().next().unwrap();
-    let req_num_toks = preset_tokens.remove(&place_id).unwrap();
-    let atom = BuiltinPetriAtomicProposition::TokensCount(
-        TokensCountRelation::GreaterOrEqual, 
-        TokensCountAtom::NumberOfTokensInPlace(place_id), 
-        TokensCountAtom::RawInteger(req_num_toks)
-    );
-    let sub_phi = preset_tokens_to_firing_condition(preset_tokens);
-    CTLFormula::Binary(
-        BinaryCTLOperator::And, 
-        Box::new(CTLFormula::Leaf(CTLFormulaLeaf::AtomicProp(atom))), 
-        Box::new(sub_phi)
+    for (place_id,threshold) in inhibitor_tokens {
+        atoms.push(
+            BuiltinPetriAtomicProposition::TokensCount(
+                TokensCountRelation::StrictlyLower, 
+                TokensCountAtom::NumberOfTokensInPlace(place_id), 
+                TokensCountAtom::RawInteger(threshold)
+            )
+        );
+    }
+    let capacities : BTreeMap<usize,u32> = petri_net.iter_place_capacities().map(|(x,y)|(*x,*y)).collect();
+    for (place_id,capacity) in capacities {
+        let consumed = transition.preset_tokens.get(&place_id).cloned().unwrap_or(0);
+        let produced = transition.postset_tokens.get(&place_id).cloned().unwrap_or(0);
+        if transition.reset_places.contains(&place_id) {
+            // the place ends up with the produced tokens, which must not be more than it had before
+            if produced > capacity {
+                atoms.push(
+                    BuiltinPetriAtomicProposition::TokensCount(
+                        TokensCountRelation::GreaterOrEqual, 
+                        TokensCountAtom::NumberOfTokensInPlace(place_id), 
+                        TokensCountAtom::RawInteger(produced)
+                    )
+                );
+            }
+        } else if produced > capacity + consumed {
+            // the capacity is always exceeded
+            atoms.push(
+                BuiltinPetriAtomicProposition::TokensCount(
+                    TokensCountRelation::StrictlyLower, 
+                    TokensCountAtom::NumberOfTokensInPlace(place_id), 
+                    TokensCountAtom::RawInteger(0)
+                )
+            );
+        } else if produced > consumed {
+            atoms.push(
+                BuiltinPetriAtomicProposition::TokensCount(
+                    TokensCountRelation::LowerOrEqual, 
+                    TokensCountAtom::NumberOfTokensInPlace(place_id), 
+                    TokensCountAtom::RawInteger(capacity + consumed - produced)
+                )
+            );
+        }
+    }
+    formulas_to_conjunction(
+        atoms.into_iter().map(|atom| CTLFormula::Leaf(CTLFormulaLeaf::AtomicProp(atom))).collect()
     )
-}
\ No newline at end of file
+}
+
+
+fn formulas_to_conjunction(
+    mut formulas : Vec<CTLFormula<BuiltinPetriAtomicProposition>>
+) -> CTLFormula<BuiltinPetriAtomicProposition> {
+    match formulas.pop() {
+        None => {
+            CTLFormula::Leaf(CTLFormulaLeaf::True)
+        },
+        Some(last_formula) => {
+            let mut phi = last_formula;
+            while let Some(formula) = formulas.pop() {
+                phi = CTLFormula::Binary(
+                    BinaryCTLOperator::And, 
+                    Box::new(formula), 
+                    Box::new(phi)
+                );
+            }
+            phi
+        }
+    }
+}
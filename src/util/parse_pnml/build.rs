@@ -14,25 +14,34 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{collections::HashMap, fs::File, io::{BufRead, BufReader}, rc::Rc};
+use std::{collections::{HashMap, HashSet}, fs::File, io::{BufRead, BufReader}, sync::Arc};
 
 use xml::{reader::XmlEvent, EventReader};
 
-use crate::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, util::parse_pnml::{error::PnmlParsingError, extract::read_pnml_first_pass}};
+use crate::{model::{ids::PetriPlaceId, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, util::parse_pnml::{error::PnmlParsingError, extract::{read_pnml_first_pass, PnmlArcKind}}};
 
 use crate::util::parse_pnml::syntax::*;
 
 
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PnmlFileContent {
     pub petri_net : PetriNet,
     pub initial_marking : Marking,
-    pub place_text_id_to_int_id : HashMap<String,usize>,
+    /// maps the PNML ids of the places to their stable identifiers in the parsed net
+    /// (contrary to the indices of the places, these are not shifted when places are removed, e.g., by reductions)
+    pub place_text_id_to_stable_id : HashMap<String,PetriPlaceId>,
 }
 
 impl PnmlFileContent {
-    pub fn new(petri_net: PetriNet, initial_marking: Marking, place_text_id_to_int_id: HashMap<String,usize>) -> Self {
-        Self { petri_net, initial_marking, place_text_id_to_int_id }
+    pub fn new(petri_net: PetriNet, initial_marking: Marking, place_text_id_to_stable_id: HashMap<String,PetriPlaceId>) -> Self {
+        Self { petri_net, initial_marking, place_text_id_to_stable_id }
+    }
+
+    /// the current index of the place with a certain PNML id, or None if it does not exist or has been removed
+    pub fn get_place_index(&self, place_text_id : &str) -> Option<usize> {
+        let place_stable_id = self.place_text_id_to_stable_id.get(place_text_id)?;
+        self.petri_net.get_place_index(*place_stable_id)
     }
 }
 
@@ -75,46 +84,90 @@ fn read_pnml<R: BufRead>(mut reader: EventReader<R>) -> Result<PnmlFileContent,
 }
 
 
+#[derive(Default)]
+struct PnmlTransitionArcs {
+    preset_tokens    : HashMap<usize,u32>,
+    postset_tokens   : HashMap<usize,u32>,
+    inhibitor_tokens : HashMap<usize,u32>,
+    reset_places     : HashSet<usize>,
+    read_tokens      : HashMap<usize,u32>,
+}
+
+
 // Read PNML content and return the Petri Net
 fn read_pnml_content<R: BufRead>(reader: EventReader<R>) -> Result<PnmlFileContent, PnmlParsingError> {
     let first_pass_result = read_pnml_first_pass(reader)?;
     // ***
     let mut transitions = Vec::new();
     {
-        let mut transitions_incoming = HashMap::new();
-        let mut transitions_outgoing = HashMap::new();
+        let mut transitions_arcs : HashMap<String,PnmlTransitionArcs> = HashMap::new();
         for tr in &first_pass_result.transitions_text_ids {
-            transitions_incoming.insert(tr.clone(), HashMap::new());
-            transitions_outgoing.insert(tr.clone(), HashMap::new());
+            transitions_arcs.insert(tr.clone(), PnmlTransitionArcs::default());
         }
-        for (source_id,target_id) in first_pass_result.raw_arcs {
-            if let Some(source_place_id) = first_pass_result.place_text_id_to_int_id.get(&source_id) {
-                let tx_incoming_places = transitions_incoming.get_mut(&target_id).ok_or(PnmlParsingError::UnknownTransition)?;
-                *tx_incoming_places.get_mut(source_place_id).unwrap() += 1;
-            } else if let Some(target_place_id) = first_pass_result.place_text_id_to_int_id.get(&target_id) {
-                let tx_outgoing_places = transitions_outgoing.get_mut(&source_id).ok_or(PnmlParsingError::UnknownTransition)?;
-                *tx_outgoing_places.get_mut(target_place_id).unwrap() += 1;
+        for arc in first_pass_result.raw_arcs {
+            let source_place_id = first_pass_result.place_text_id_to_int_id.get(&arc.source);
+            let target_place_id = first_pass_result.place_text_id_to_int_id.get(&arc.target);
+            if arc.kind == PnmlArcKind::Normal {
+                if let Some(source_place_id) = source_place_id {
+                    let tx_arcs = transitions_arcs.get_mut(&arc.target).ok_or(PnmlParsingError::UnknownTransition)?;
+                    *tx_arcs.preset_tokens.entry(*source_place_id).or_insert(0) += arc.weight;
+                } else if let Some(target_place_id) = target_place_id {
+                    let tx_arcs = transitions_arcs.get_mut(&arc.source).ok_or(PnmlParsingError::UnknownTransition)?;
+                    *tx_arcs.postset_tokens.entry(*target_place_id).or_insert(0) += arc.weight;
+                } else {
+                    return Err(PnmlParsingError::NeitherSourceNotTargetOfArcIsAPlace)
+                }
             } else {
-                return Err(PnmlParsingError::NeitherSourceNotTargetOfArcIsAPlace)
+                // read, inhibitor and reset arcs connect a place and a transition regardless of their direction
+                let (place_id,transition_text_id) = match (source_place_id,target_place_id) {
+                    (Some(place_id),_) => (*place_id,&arc.target),
+                    (None,Some(place_id)) => (*place_id,&arc.source),
+                    (None,None) => {
+                        return Err(PnmlParsingError::NeitherSourceNotTargetOfArcIsAPlace)
+                    }
+                };
+                let tx_arcs = transitions_arcs.get_mut(transition_text_id).ok_or(PnmlParsingError::UnknownTransition)?;
+                match arc.kind {
+                    PnmlArcKind::Read => {
+                        *tx_arcs.read_tokens.entry(place_id).or_insert(0) += arc.weight;
+                    },
+                    PnmlArcKind::Inhibitor => {
+                        tx_arcs.inhibitor_tokens.insert(place_id, arc.weight);
+                    },
+                    PnmlArcKind::Reset => {
+                        tx_arcs.reset_places.insert(place_id);
+                    },
+                    PnmlArcKind::Normal => {
+                        unreachable!()
+                    }
+                }
             }
         }
         for transition in first_pass_result.transitions_text_ids {
-            let from_places = transitions_incoming.remove(&transition).unwrap();
-            let to_places = transitions_outgoing.remove(&transition).unwrap();
-            transitions.push(PetriTransition::new(Some(
-                Rc::new(PetriTransitionLabel::new(transition))),
-                from_places, 
-                to_places
-            ));
+            let tx_arcs = transitions_arcs.remove(&transition).unwrap();
+            transitions.push(
+                PetriTransition::new(
+                    Some(Arc::new(PetriTransitionLabel::new(transition))),
+                    tx_arcs.preset_tokens, 
+                    tx_arcs.postset_tokens
+                )
+                .with_inhibitor_tokens(tx_arcs.inhibitor_tokens)
+                .with_reset_places(tx_arcs.reset_places)
+                .with_read_tokens(tx_arcs.read_tokens)
+            );
         }
     }
     let places = first_pass_result.places
         .into_iter()
-        .map(|x| Some(Rc::new(PetriStateLabel::new(x))))
+        .map(|x| Some(Arc::new(PetriStateLabel::new(x))))
         .collect();
     let net = PetriNet::new(places, transitions);
     let marking = Marking::new(first_pass_result.initial_marking);
-    Ok(PnmlFileContent::new(net,marking,first_pass_result.place_text_id_to_int_id))
+    let place_text_id_to_stable_id = first_pass_result.place_text_id_to_int_id
+        .into_iter()
+        .map(|(text_id,place_id)| (text_id, net.get_place_stable_id(place_id).unwrap()))
+        .collect();
+    Ok(PnmlFileContent::new(net,marking,place_text_id_to_stable_id))
 }
 
 
@@ -39,6 +39,30 @@ pub enum PnmlParsingError {
     #[error("After some text, we expect the end tag '{}'", .tag)]
     ExpectedTextEnd{tag:&'static str},
     #[error("CouldNotParseInitialMarkingTokenNumberToInteger")]
-    CouldNotParseInitialMarkingTokenNumberToInteger
+    CouldNotParseInitialMarkingTokenNumberToInteger,
+    #[error("CouldNotParseArcInscriptionToInteger")]
+    CouldNotParseArcInscriptionToInteger,
+    #[error("MissingWeightInArcInscription")]
+    MissingWeightInArcInscription,
+    #[error("ZeroWeightInArcInscription")]
+    ZeroWeightInArcInscription,
+    #[error("unknown arc type '{}'", .0)]
+    UnknownArcType(String),
+    #[error("reference to unknown declaration '{}'", .0)]
+    UnknownDeclaration(String),
+    #[error("unsupported high-level term '{}'", .0)]
+    UnsupportedHighLevelTerm(String),
+    #[error("missing subterm under '{}'", .0)]
+    MissingHighLevelSubterm(String),
+    #[error("CouldNotParseHighLevelConstantToInteger")]
+    CouldNotParseHighLevelConstantToInteger,
+    #[error("constant {} out of the range [{},{}]", .value, .start, .end)]
+    HighLevelConstantOutOfRange{value:i64,start:i64,end:i64},
+    #[error("sort '{}' has no constant", .0)]
+    EmptyHighLevelSort(String),
+    #[error("named sort '{}' is defined in terms of itself", .0)]
+    CyclicNamedSort(String),
+    #[error("HighLevelMultiplicityOverflow")]
+    HighLevelMultiplicityOverflow
 }
 
@@ -25,16 +25,40 @@ use crate::util::parse_pnml::error::PnmlParsingError;
 use crate::util::parse_pnml::syntax::*;
 
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PnmlArcKind {
+    Normal,
+    /// read (or test) arc, which does not consume tokens
+    Read,
+    Inhibitor,
+    Reset
+}
+
+pub struct PnmlRawArc {
+    pub source : String,
+    pub target : String,
+    /// the inscription of the arc (1 if there is no inscription)
+    pub weight : u32,
+    pub kind : PnmlArcKind
+}
+
+impl PnmlRawArc {
+    pub fn new(source: String, target: String, weight: u32, kind: PnmlArcKind) -> Self {
+        Self { source, target, weight, kind }
+    }
+}
+
+
 pub struct PnmlParsingFirstPass {
     pub places : Vec<String>,
     pub place_text_id_to_int_id : HashMap<String,usize>,
     pub initial_marking : BTreeMap<usize,u32>,
     pub transitions_text_ids : HashSet<String>,
-    pub raw_arcs : Vec<(String,String)>
+    pub raw_arcs : Vec<PnmlRawArc>
 }
 
 impl PnmlParsingFirstPass {
-    pub fn new(places: Vec<String>, place_text_id_to_int_id: HashMap<String,usize>, initial_marking: BTreeMap<usize,u32>, transitions_text_ids: HashSet<String>, raw_arcs: Vec<(String,String)>) -> Self {
+    pub fn new(places: Vec<String>, place_text_id_to_int_id: HashMap<String,usize>, initial_marking: BTreeMap<usize,u32>, transitions_text_ids: HashSet<String>, raw_arcs: Vec<PnmlRawArc>) -> Self {
         Self { places, place_text_id_to_int_id, initial_marking, transitions_text_ids, raw_arcs }
     }
 }
@@ -42,12 +66,13 @@ impl PnmlParsingFirstPass {
 
 
 
+#[allow(clippy::collapsible_match)]
 pub fn read_pnml_first_pass<R: BufRead>(mut reader: EventReader<R>) -> Result<PnmlParsingFirstPass, PnmlParsingError> {
     let mut places = Vec::new();
     let mut place_text_id_to_int_id = HashMap::new();
     let mut initial_marking : BTreeMap<usize,u32> = BTreeMap::new();
     let mut transitions_text_ids : HashSet<String> = HashSet::new();
-    let mut raw_arcs : Vec<(String,String)> = Vec::new();
+    let mut raw_arcs : Vec<PnmlRawArc> = Vec::new();
     loop {
         match reader.next() {
             Ok(XmlEvent::StartElement{name,attributes,..}) => match name.local_name.as_str() {
@@ -68,8 +93,8 @@ pub fn read_pnml_first_pass<R: BufRead>(mut reader: EventReader<R>) -> Result<Pn
                 }
                 PNML_ARC => {
                     let attrs = collect_attributes(attributes);
-                    let (source,target) = read_arc(&mut reader, attrs)?;
-                    raw_arcs.push((source,target));
+                    let arc = read_arc(&mut reader, attrs)?;
+                    raw_arcs.push(arc);
                 }
                 _ => {}
             },
@@ -92,6 +117,7 @@ pub fn read_pnml_first_pass<R: BufRead>(mut reader: EventReader<R>) -> Result<Pn
 
 
 
+#[allow(clippy::collapsible_match)]
 fn read_place<R: BufRead>(
     reader: &mut EventReader<R>,
     mut attrs : HashMap<String,String>
@@ -117,6 +143,7 @@ fn read_place<R: BufRead>(
 }
 
 
+#[allow(clippy::collapsible_match)]
 fn read_initial_marking<R: BufRead>(
     reader: &mut EventReader<R>
 ) -> Result<u32,PnmlParsingError> {
@@ -153,6 +180,7 @@ fn read_initial_marking<R: BufRead>(
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn read_transition<R: BufRead>(
     reader: &mut EventReader<R>,
     mut attrs : HashMap<String,String>
@@ -173,15 +201,37 @@ fn read_transition<R: BufRead>(
 }
 
 
+#[allow(clippy::collapsible_match)]
 fn read_arc<R: BufRead>(
     reader: &mut EventReader<R>,
     mut attrs : HashMap<String,String>
-) -> Result<(String,String),PnmlParsingError> {
+) -> Result<PnmlRawArc,PnmlParsingError> {
     let source: String = attrs.remove(PNML_SOURCE).ok_or(PnmlParsingError::MissingAttribute{att:PNML_SOURCE,parent:PNML_ARC})?;
     let target: String = attrs.remove(PNML_TARGET).ok_or(PnmlParsingError::MissingAttribute{att:PNML_TARGET,parent:PNML_ARC})?;
+    let mut weight = 1;
+    let mut kind = PnmlArcKind::Normal;
     loop {
         match reader.next() {
             Err(e) => {return Err(PnmlParsingError::Xml(e))}
+            Ok(XmlEvent::StartElement{name,attributes,..}) => match name.local_name.as_str() {
+                PNML_INSCRIPTION => {
+                    weight = read_inscription(reader)?;
+                },
+                PNML_ARC_TYPE => {
+                    let mut type_attrs = collect_attributes(attributes);
+                    let arc_type = type_attrs.remove(PNML_VALUE).ok_or(PnmlParsingError::MissingAttribute{att:PNML_VALUE,parent:PNML_ARC_TYPE})?;
+                    kind = match arc_type.as_str() {
+                        PNML_ARC_TYPE_NORMAL => PnmlArcKind::Normal,
+                        PNML_ARC_TYPE_READ | PNML_ARC_TYPE_TEST => PnmlArcKind::Read,
+                        PNML_ARC_TYPE_INHIBITOR => PnmlArcKind::Inhibitor,
+                        PNML_ARC_TYPE_RESET => PnmlArcKind::Reset,
+                        _ => {
+                            return Err(PnmlParsingError::UnknownArcType(arc_type));
+                        }
+                    };
+                },
+                _ => {}
+            },
             Ok(XmlEvent::EndElement{name}) => {
                 if name.local_name == PNML_ARC {
                     break;
@@ -190,14 +240,47 @@ fn read_arc<R: BufRead>(
             _ => {}
         }
     }
-    Ok((source,target))
+    Ok(PnmlRawArc::new(source,target,weight,kind))
+}
+
+
+fn read_inscription<R: BufRead>(
+    reader: &mut EventReader<R>
+) -> Result<u32,PnmlParsingError> {
+    let mut weight = None;
+    loop {
+        match reader.next() {
+            Err(e) => {return Err(PnmlParsingError::Xml(e))}
+            Ok(XmlEvent::StartElement{name,..}) if name.local_name.as_str() == PNML_TEXT => {
+                let txt = read_text_then_close(reader,PNML_TEXT)?;
+                match txt.trim().parse::<u32>() {
+                    Ok(0) => {
+                        // as for the net builder, arcs of weight 0 are meaningless
+                        // (and an inhibitor arc of weight 0 would never let its transition fire)
+                        return Err(PnmlParsingError::ZeroWeightInArcInscription);
+                    }
+                    Ok(w) => {
+                        weight = Some(w);
+                    }
+                    Err(_) => {
+                        return Err(PnmlParsingError::CouldNotParseArcInscriptionToInteger);
+                    }
+                }
+            }
+            Ok(XmlEvent::EndElement{name}) if name.local_name == PNML_INSCRIPTION => {
+                break;
+            }
+            _ => {}
+        }
+    }
+    weight.ok_or(PnmlParsingError::MissingWeightInArcInscription)
 }
 
 
 
 
 
-fn collect_attributes(attributes : Vec<OwnedAttribute>) -> HashMap<String, String> {
+pub(crate) fn collect_attributes(attributes : Vec<OwnedAttribute>) -> HashMap<String, String> {
     attributes.into_iter()
         .map(|attribute| {
             (attribute.name.local_name,attribute.value)
@@ -206,6 +289,7 @@ fn collect_attributes(attributes : Vec<OwnedAttribute>) -> HashMap<String, Strin
 }
 
 
+#[allow(clippy::collapsible_match)]
 fn read_text_then_close<R: BufRead>(
     reader: &mut EventReader<R>,
     expected_end_tag : &'static str
@@ -18,4 +18,5 @@ limitations under the License.
 pub mod error;
 mod syntax;
 mod extract;
-pub mod build;
\ No newline at end of file
+pub mod build;
+pub mod symmetric;
\ No newline at end of file
@@ -0,0 +1,595 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use xml::reader::XmlEvent;
+use xml::EventReader;
+
+use crate::colored::domain::{ColorDomain, ColorDomainKind};
+use crate::colored::expression::{ColorBinding, ColorComparison, ColorGuard, ColorTerm, ColoredArcExpression};
+use crate::colored::net::{ColoredMarking, ColoredPetriNet, ColoredPlace, ColoredTransition, ColoredVariable};
+use crate::util::parse_pnml::error::PnmlParsingError;
+use crate::util::parse_pnml::extract::collect_attributes;
+use crate::util::parse_pnml::syntax::*;
+
+
+
+
+/// the content of a PNML file describing a symmetric net
+pub struct ColoredPnmlFileContent {
+    pub colored_net : ColoredPetriNet,
+    pub initial_marking : ColoredMarking,
+    pub place_text_id_to_int_id : HashMap<String,usize>,
+    pub transition_text_id_to_int_id : HashMap<String,usize>,
+}
+
+impl ColoredPnmlFileContent {
+    pub fn new(colored_net: ColoredPetriNet, initial_marking: ColoredMarking, place_text_id_to_int_id: HashMap<String,usize>, transition_text_id_to_int_id: HashMap<String,usize>) -> Self {
+        Self { colored_net, initial_marking, place_text_id_to_int_id, transition_text_id_to_int_id }
+    }
+}
+
+
+/// reads a PNML file of type "symmetricnet"
+/// as for P/T nets, places and transitions are named after their PNML ids
+pub fn read_colored_petri_net_from_pnml_file_path(path : &str) -> Result<ColoredPnmlFileContent,PnmlParsingError> {
+    match File::open(path) {
+        Ok(file) => {
+            let file = BufReader::new(file);
+            let reader = EventReader::new(file);
+            read_colored_pnml(reader)
+        },
+        Err(_) => {
+            Err(PnmlParsingError::CouldNotOpenFile)
+        },
+    }
+}
+
+
+fn read_colored_pnml<R: BufRead>(mut reader: EventReader<R>) -> Result<ColoredPnmlFileContent, PnmlParsingError> {
+    loop {
+        match reader.next() {
+            Ok(XmlEvent::StartElement{name,attributes,..}) => {
+                if name.local_name.as_str() == PNML_NET {
+                    let net_element = PnmlXmlElement::read(&mut reader, name.local_name, collect_attributes(attributes))?;
+                    return build_colored_net(&net_element);
+                }
+            },
+            Ok(XmlEvent::EndDocument) => {
+                return Err(PnmlParsingError::MissingHighLevelSubterm(PNML_NET.to_string()));
+            },
+            Ok(_) => {},
+            Err(e) => {
+                return Err(PnmlParsingError::Xml(e))
+            }
+        }
+    }
+}
+
+
+
+
+/// high-level annotations are arbitrarily nested terms
+/// so the net is first read as a tree of XML elements
+struct PnmlXmlElement {
+    name : String,
+    attrs : HashMap<String,String>,
+    children : Vec<PnmlXmlElement>,
+    text : String
+}
+
+impl PnmlXmlElement {
+
+    fn read<R: BufRead>(
+        reader: &mut EventReader<R>,
+        name : String,
+        attrs : HashMap<String,String>
+    ) -> Result<Self,PnmlParsingError> {
+        let mut children = Vec::new();
+        let mut text = String::new();
+        loop {
+            match reader.next() {
+                Err(e) => {return Err(PnmlParsingError::Xml(e))}
+                Ok(XmlEvent::StartElement{name,attributes,..}) => {
+                    children.push(Self::read(reader, name.local_name, collect_attributes(attributes))?);
+                },
+                Ok(XmlEvent::Characters(txt)) => {
+                    text.push_str(&txt);
+                },
+                Ok(XmlEvent::EndElement{..}) => {
+                    break;
+                },
+                _ => {}
+            }
+        }
+        Ok(Self { name, attrs, children, text })
+    }
+
+    fn get_attribute(&self, att : &'static str, parent : &'static str) -> Result<&str,PnmlParsingError> {
+        self.attrs.get(att).map(|x| x.as_str()).ok_or(PnmlParsingError::MissingAttribute { att, parent })
+    }
+
+    fn find_child(&self, name : &str) -> Option<&PnmlXmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn first_child(&self) -> Result<&PnmlXmlElement,PnmlParsingError> {
+        self.children.first().ok_or(PnmlParsingError::MissingHighLevelSubterm(self.name.clone()))
+    }
+
+    /// the term under the "structure" element of an annotation
+    fn get_structure_term(&self) -> Result<&PnmlXmlElement,PnmlParsingError> {
+        self.find_child(PNML_STRUCTURE)
+            .ok_or(PnmlParsingError::MissingHighLevelSubterm(self.name.clone()))?
+            .first_child()
+    }
+
+    /// the terms wrapped in the "subterm" children of the element
+    fn get_subterms(&self) -> Result<Vec<&PnmlXmlElement>,PnmlParsingError> {
+        self.children.iter()
+            .filter(|child| child.name == PNML_SUBTERM)
+            .map(|child| child.first_child())
+            .collect()
+    }
+
+    fn get_subterms_pair(&self) -> Result<(&PnmlXmlElement,&PnmlXmlElement),PnmlParsingError> {
+        let subterms = self.get_subterms()?;
+        match subterms.as_slice() {
+            [left,right] => Ok((left,right)),
+            _ => Err(PnmlParsingError::MissingHighLevelSubterm(self.name.clone()))
+        }
+    }
+
+    /// collects the elements of interest, which may be nested in pages and declarations
+    fn collect_net_elements<'a>(&'a self, collected : &mut HashMap<&'static str,Vec<&'a PnmlXmlElement>>) {
+        for child in &self.children {
+            let key = [PNML_NAMED_SORT, PNML_VARIABLE_DECL, PNML_PLACE, PNML_TRANSITION, PNML_ARC]
+                .into_iter()
+                .find(|key| *key == child.name);
+            match key {
+                Some(key) => {
+                    collected.entry(key).or_default().push(child);
+                },
+                None => {
+                    child.collect_net_elements(collected);
+                }
+            }
+        }
+    }
+}
+
+
+fn parse_integer<T : std::str::FromStr>(txt : &str) -> Result<T,PnmlParsingError> {
+    txt.trim().parse::<T>().map_err(|_| PnmlParsingError::CouldNotParseHighLevelConstantToInteger)
+}
+
+
+
+
+/// resolves the sorts, variables and constants declared in the net
+struct PnmlColoredDeclarations<'a> {
+    named_sorts : HashMap<String,&'a PnmlXmlElement>,
+    resolved_named_sorts : HashMap<String,usize>,
+    /// the named sorts which are being resolved, to detect cyclic definitions
+    resolving_named_sorts : HashSet<String>,
+    domains : Vec<ColorDomain>,
+    /// maps the ids of the constants of enumeration sorts to their domain and index
+    constants : HashMap<String,(usize,usize)>,
+    /// maps the bounds of integer ranges to their domain
+    int_ranges : HashMap<(i64,i64),usize>,
+    dot_domain : Option<usize>,
+    variables : Vec<ColoredVariable>,
+    variable_text_id_to_int_id : HashMap<String,usize>
+}
+
+impl<'a> PnmlColoredDeclarations<'a> {
+
+    fn new(named_sorts : &[&'a PnmlXmlElement]) -> Result<Self,PnmlParsingError> {
+        let mut named_sorts_map = HashMap::new();
+        for named_sort in named_sorts {
+            let id = named_sort.get_attribute(PNML_TEXT_ID, PNML_NAMED_SORT)?;
+            named_sorts_map.insert(id.to_string(), *named_sort);
+        }
+        Ok(
+            Self {
+                named_sorts : named_sorts_map,
+                resolved_named_sorts : HashMap::new(),
+                resolving_named_sorts : HashSet::new(),
+                domains : Vec::new(),
+                constants : HashMap::new(),
+                int_ranges : HashMap::new(),
+                dot_domain : None,
+                variables : Vec::new(),
+                variable_text_id_to_int_id : HashMap::new()
+            }
+        )
+    }
+
+    fn add_domain(&mut self, name : String, kind : ColorDomainKind) -> usize {
+        self.domains.push(ColorDomain::new(name, kind));
+        self.domains.len() - 1
+    }
+
+    fn get_dot_domain(&mut self) -> usize {
+        match self.dot_domain {
+            Some(domain_id) => domain_id,
+            None => {
+                let domain_id = self.add_domain(PNML_DOT.to_string(), ColorDomainKind::Enumeration(vec![PNML_DOT.to_string()]));
+                self.dot_domain = Some(domain_id);
+                domain_id
+            }
+        }
+    }
+
+    fn get_int_range_domain(&mut self, name : String, start : i64, end : i64) -> usize {
+        match self.int_ranges.get(&(start,end)) {
+            Some(domain_id) => *domain_id,
+            None => {
+                let constants = (start..=end).map(|x| x.to_string()).collect();
+                let domain_id = self.add_domain(name, ColorDomainKind::Enumeration(constants));
+                self.int_ranges.insert((start,end), domain_id);
+                domain_id
+            }
+        }
+    }
+
+    fn read_int_range(&mut self, name : String, element : &PnmlXmlElement) -> Result<usize,PnmlParsingError> {
+        let start = parse_integer(element.get_attribute(PNML_START, PNML_FINITE_INT_RANGE)?)?;
+        let end = parse_integer(element.get_attribute(PNML_END, PNML_FINITE_INT_RANGE)?)?;
+        // successors and predecessors are computed modulo the size of the domain
+        if end < start {
+            return Err(PnmlParsingError::EmptyHighLevelSort(name));
+        }
+        Ok(self.get_int_range_domain(name, start, end))
+    }
+
+    fn resolve_named_sort(&mut self, id : &str) -> Result<usize,PnmlParsingError> {
+        if let Some(domain_id) = self.resolved_named_sorts.get(id) {
+            return Ok(*domain_id);
+        }
+        let named_sort = *self.named_sorts.get(id).ok_or(PnmlParsingError::UnknownDeclaration(id.to_string()))?;
+        let name = named_sort.attrs.get(PNML_NAME).cloned().unwrap_or(id.to_string());
+        if !self.resolving_named_sorts.insert(id.to_string()) {
+            return Err(PnmlParsingError::CyclicNamedSort(id.to_string()));
+        }
+        let domain_id = self.read_sort(name, named_sort.first_child()?)?;
+        self.resolving_named_sorts.remove(id);
+        self.resolved_named_sorts.insert(id.to_string(), domain_id);
+        Ok(domain_id)
+    }
+
+    fn read_sort(&mut self, name : String, element : &PnmlXmlElement) -> Result<usize,PnmlParsingError> {
+        match element.name.as_str() {
+            PNML_USER_SORT => {
+                let id = element.get_attribute(PNML_DECLARATION_REF, PNML_USER_SORT)?;
+                self.resolve_named_sort(id)
+            },
+            PNML_DOT => {
+                Ok(self.get_dot_domain())
+            },
+            PNML_CYCLIC_ENUMERATION | PNML_FINITE_ENUMERATION => {
+                let mut constants = Vec::new();
+                let mut constants_ids = Vec::new();
+                for fe_constant in element.children.iter().filter(|child| child.name == PNML_FE_CONSTANT) {
+                    let id = fe_constant.get_attribute(PNML_TEXT_ID, PNML_FE_CONSTANT)?;
+                    constants_ids.push(id.to_string());
+                    constants.push(fe_constant.attrs.get(PNML_NAME).cloned().unwrap_or(id.to_string()));
+                }
+                if constants.is_empty() {
+                    return Err(PnmlParsingError::EmptyHighLevelSort(name));
+                }
+                let domain_id = self.add_domain(name, ColorDomainKind::Enumeration(constants));
+                for (constant_id,id) in constants_ids.into_iter().enumerate() {
+                    self.constants.insert(id, (domain_id,constant_id));
+                }
+                Ok(domain_id)
+            },
+            PNML_FINITE_INT_RANGE => {
+                self.read_int_range(name, element)
+            },
+            PNML_PRODUCT_SORT => {
+                // nested products are flattened so that all components are enumeration domains
+                let mut components = Vec::new();
+                for component in &element.children {
+                    let component_id = self.read_sort(component.name.clone(), component)?;
+                    match &self.domains.get(component_id).unwrap().kind {
+                        ColorDomainKind::Enumeration(_) => {
+                            components.push(component_id);
+                        },
+                        ColorDomainKind::Product(sub_components) => {
+                            components.extend(sub_components.iter().cloned());
+                        }
+                    }
+                }
+                Ok(self.add_domain(name, ColorDomainKind::Product(components)))
+            },
+            _ => {
+                Err(PnmlParsingError::UnsupportedHighLevelTerm(element.name.clone()))
+            }
+        }
+    }
+
+    fn read_variable(&mut self, element : &PnmlXmlElement) -> Result<(),PnmlParsingError> {
+        let id = element.get_attribute(PNML_TEXT_ID, PNML_VARIABLE_DECL)?;
+        let name = element.attrs.get(PNML_NAME).cloned().unwrap_or(id.to_string());
+        let domain_id = self.read_sort(name.clone(), element.first_child()?)?;
+        // variables range over enumeration domains
+        if let ColorDomainKind::Product(_) = self.domains.get(domain_id).unwrap().kind {
+            return Err(PnmlParsingError::UnsupportedHighLevelTerm(PNML_PRODUCT_SORT.to_string()));
+        }
+        self.variable_text_id_to_int_id.insert(id.to_string(), self.variables.len());
+        self.variables.push(ColoredVariable::new(name, domain_id));
+        Ok(())
+    }
+
+    fn read_color_term(&mut self, element : &PnmlXmlElement) -> Result<ColorTerm,PnmlParsingError> {
+        match element.name.as_str() {
+            PNML_VARIABLE => {
+                let id = element.get_attribute(PNML_REF_VARIABLE, PNML_VARIABLE)?;
+                let var_id = self.variable_text_id_to_int_id.get(id).ok_or(PnmlParsingError::UnknownDeclaration(id.to_string()))?;
+                Ok(ColorTerm::Variable(*var_id))
+            },
+            PNML_USER_OPERATOR => {
+                let id = element.get_attribute(PNML_DECLARATION_REF, PNML_USER_OPERATOR)?;
+                let (domain_id,constant_id) = self.constants.get(id).ok_or(PnmlParsingError::UnknownDeclaration(id.to_string()))?;
+                Ok(ColorTerm::Constant { domain_id: *domain_id, constant_id: *constant_id })
+            },
+            PNML_DOT_CONSTANT => {
+                Ok(ColorTerm::Constant { domain_id: self.get_dot_domain(), constant_id: 0 })
+            },
+            PNML_FINITE_INT_RANGE_CONSTANT => {
+                let value : i64 = parse_integer(element.get_attribute(PNML_VALUE, PNML_FINITE_INT_RANGE_CONSTANT)?)?;
+                let range = element.find_child(PNML_FINITE_INT_RANGE)
+                    .ok_or(PnmlParsingError::MissingHighLevelSubterm(element.name.clone()))?;
+                let domain_id = self.read_int_range(PNML_FINITE_INT_RANGE.to_string(), range)?;
+                let start : i64 = parse_integer(range.get_attribute(PNML_START, PNML_FINITE_INT_RANGE)?)?;
+                let end : i64 = parse_integer(range.get_attribute(PNML_END, PNML_FINITE_INT_RANGE)?)?;
+                if value < start || value > end {
+                    return Err(PnmlParsingError::HighLevelConstantOutOfRange { value, start, end });
+                }
+                Ok(ColorTerm::Constant { domain_id, constant_id: (value - start) as usize })
+            },
+            PNML_SUCCESSOR | PNML_PREDECESSOR => {
+                let sub_term = match element.get_subterms()?.first() {
+                    None => {
+                        return Err(PnmlParsingError::MissingHighLevelSubterm(element.name.clone()));
+                    },
+                    Some(sub_term) => {
+                        Box::new(self.read_color_term(sub_term)?)
+                    }
+                };
+                if element.name == PNML_SUCCESSOR {
+                    Ok(ColorTerm::Successor(sub_term))
+                } else {
+                    Ok(ColorTerm::Predecessor(sub_term))
+                }
+            },
+            PNML_TUPLE => {
+                let sub_terms = element.get_subterms()?.into_iter()
+                    .map(|sub_term| self.read_color_term(sub_term))
+                    .collect::<Result<Vec<ColorTerm>,PnmlParsingError>>()?;
+                Ok(ColorTerm::Tuple(sub_terms))
+            },
+            _ => {
+                Err(PnmlParsingError::UnsupportedHighLevelTerm(element.name.clone()))
+            }
+        }
+    }
+
+    fn read_multiset_term(&mut self, element : &PnmlXmlElement) -> Result<ColoredArcExpression,PnmlParsingError> {
+        match element.name.as_str() {
+            PNML_ADD => {
+                let sub_exprs = element.get_subterms()?.into_iter()
+                    .map(|sub_term| self.read_multiset_term(sub_term))
+                    .collect::<Result<Vec<ColoredArcExpression>,PnmlParsingError>>()?;
+                Ok(ColoredArcExpression::Add(sub_exprs))
+            },
+            PNML_SUBTRACT => {
+                let (left,right) = element.get_subterms_pair()?;
+                Ok(
+                    ColoredArcExpression::Subtract(
+                        Box::new(self.read_multiset_term(left)?),
+                        Box::new(self.read_multiset_term(right)?)
+                    )
+                )
+            },
+            PNML_NUMBER_OF => {
+                let (number,sub_term) = element.get_subterms_pair()?;
+                if number.name != PNML_NUMBER_CONSTANT {
+                    return Err(PnmlParsingError::UnsupportedHighLevelTerm(number.name.clone()));
+                }
+                let factor = parse_integer(number.get_attribute(PNML_VALUE, PNML_NUMBER_CONSTANT)?)?;
+                Ok(ColoredArcExpression::Scaled(factor, Box::new(self.read_multiset_term(sub_term)?)))
+            },
+            PNML_ALL => {
+                let domain_id = self.read_sort(PNML_ALL.to_string(), element.first_child()?)?;
+                Ok(ColoredArcExpression::All(domain_id))
+            },
+            _ => {
+                Ok(ColoredArcExpression::Single(self.read_color_term(element)?))
+            }
+        }
+    }
+
+    fn read_guard(&mut self, element : &PnmlXmlElement) -> Result<ColorGuard,PnmlParsingError> {
+        let comparison = match element.name.as_str() {
+            PNML_EQUALITY => Some(ColorComparison::Equal),
+            PNML_INEQUALITY => Some(ColorComparison::NotEqual),
+            PNML_LESS_THAN => Some(ColorComparison::Less),
+            PNML_LESS_THAN_OR_EQUAL => Some(ColorComparison::LessOrEqual),
+            PNML_GREATER_THAN => Some(ColorComparison::Greater),
+            PNML_GREATER_THAN_OR_EQUAL => Some(ColorComparison::GreaterOrEqual),
+            _ => None
+        };
+        if let Some(comparison) = comparison {
+            let (left,right) = element.get_subterms_pair()?;
+            return Ok(ColorGuard::Compare(self.read_color_term(left)?, comparison, self.read_color_term(right)?));
+        }
+        match element.name.as_str() {
+            PNML_AND | PNML_OR => {
+                let sub_guards = element.get_subterms()?.into_iter()
+                    .map(|sub_term| self.read_guard(sub_term))
+                    .collect::<Result<Vec<ColorGuard>,PnmlParsingError>>()?;
+                if element.name == PNML_AND {
+                    Ok(ColorGuard::And(sub_guards))
+                } else {
+                    Ok(ColorGuard::Or(sub_guards))
+                }
+            },
+            PNML_NOT => {
+                match element.get_subterms()?.first() {
+                    None => {
+                        Err(PnmlParsingError::MissingHighLevelSubterm(element.name.clone()))
+                    },
+                    Some(sub_term) => {
+                        Ok(ColorGuard::Not(Box::new(self.read_guard(sub_term)?)))
+                    }
+                }
+            },
+            PNML_IMPLY => {
+                let (left,right) = element.get_subterms_pair()?;
+                Ok(
+                    ColorGuard::Or(vec![
+                        ColorGuard::Not(Box::new(self.read_guard(left)?)),
+                        self.read_guard(right)?
+                    ])
+                )
+            },
+            PNML_BOOLEAN_CONSTANT => {
+                Ok(ColorGuard::Constant(element.get_attribute(PNML_VALUE, PNML_BOOLEAN_CONSTANT)? == "true"))
+            },
+            _ => {
+                Err(PnmlParsingError::UnsupportedHighLevelTerm(element.name.clone()))
+            }
+        }
+    }
+}
+
+
+
+
+fn build_colored_net(net_element : &PnmlXmlElement) -> Result<ColoredPnmlFileContent,PnmlParsingError> {
+    let mut collected = HashMap::new();
+    net_element.collect_net_elements(&mut collected);
+    let get_collected = |key : &'static str| collected.get(key).cloned().unwrap_or_default();
+    let mut declarations = PnmlColoredDeclarations::new(&get_collected(PNML_NAMED_SORT))?;
+    for variable in get_collected(PNML_VARIABLE_DECL) {
+        declarations.read_variable(variable)?;
+    }
+    // ***
+    let mut places = Vec::new();
+    let mut place_text_id_to_int_id = HashMap::new();
+    let mut initial_marking_exprs = Vec::new();
+    for place in get_collected(PNML_PLACE) {
+        let id = place.get_attribute(PNML_TEXT_ID, PNML_PLACE)?;
+        let domain_id = match place.find_child(PNML_TYPE) {
+            None => {
+                declarations.get_dot_domain()
+            },
+            Some(place_type) => {
+                let sort = place_type.get_structure_term()?;
+                declarations.read_sort(sort.name.clone(), sort)?
+            }
+        };
+        let place_id = places.len();
+        if let Some(hl_marking) = place.find_child(PNML_HL_INITIAL_MARKING) {
+            initial_marking_exprs.push((place_id,declarations.read_multiset_term(hl_marking.get_structure_term()?)?));
+        } else if let Some(marking) = place.find_child(PNML_INITIAL_MARKING) {
+            // a P/T initial marking gives a number of uncolored tokens
+            let text = marking.find_child(PNML_TEXT).ok_or(PnmlParsingError::MissingNumberOfTokensInInitialMarking)?;
+            let num_toks = text.text.trim().parse::<u32>().map_err(|_| PnmlParsingError::CouldNotParseInitialMarkingTokenNumberToInteger)?;
+            let dot = ColorTerm::Constant { domain_id: declarations.get_dot_domain(), constant_id: 0 };
+            initial_marking_exprs.push((place_id,ColoredArcExpression::Scaled(num_toks, Box::new(ColoredArcExpression::Single(dot)))));
+        }
+        place_text_id_to_int_id.insert(id.to_string(), place_id);
+        places.push(ColoredPlace::new(id.to_string(), domain_id));
+    }
+    // ***
+    let mut transitions = Vec::new();
+    let mut transition_text_id_to_int_id = HashMap::new();
+    for transition in get_collected(PNML_TRANSITION) {
+        let id = transition.get_attribute(PNML_TEXT_ID, PNML_TRANSITION)?;
+        let guard = match transition.find_child(PNML_CONDITION) {
+            None => {
+                ColorGuard::Constant(true)
+            },
+            Some(condition) => {
+                declarations.read_guard(condition.get_structure_term()?)?
+            }
+        };
+        transition_text_id_to_int_id.insert(id.to_string(), transitions.len());
+        transitions.push(ColoredTransition::new(id.to_string(), guard, HashMap::new(), HashMap::new()));
+    }
+    // ***
+    for arc in get_collected(PNML_ARC) {
+        let source = arc.get_attribute(PNML_SOURCE, PNML_ARC)?;
+        let target = arc.get_attribute(PNML_TARGET, PNML_ARC)?;
+        let arc_expr = match arc.find_child(PNML_HL_INSCRIPTION) {
+            None => {
+                ColoredArcExpression::Single(ColorTerm::Constant { domain_id: declarations.get_dot_domain(), constant_id: 0 })
+            },
+            Some(inscription) => {
+                declarations.read_multiset_term(inscription.get_structure_term()?)?
+            }
+        };
+        let (transition_text_id,place_id,is_preset) = match (place_text_id_to_int_id.get(source),place_text_id_to_int_id.get(target)) {
+            (Some(place_id),_) => (target,*place_id,true),
+            (None,Some(place_id)) => (source,*place_id,false),
+            (None,None) => {
+                return Err(PnmlParsingError::NeitherSourceNotTargetOfArcIsAPlace);
+            }
+        };
+        let transition_id = transition_text_id_to_int_id.get(transition_text_id).ok_or(PnmlParsingError::UnknownTransition)?;
+        let transition = transitions.get_mut(*transition_id).unwrap();
+        let arcs = if is_preset {
+            &mut transition.preset
+        } else {
+            &mut transition.postset
+        };
+        // several arcs between the same place and transition add up
+        let combined = match arcs.remove(&place_id) {
+            None => arc_expr,
+            Some(previous) => ColoredArcExpression::Add(vec![previous,arc_expr])
+        };
+        arcs.insert(place_id, combined);
+    }
+    // ***
+    let colored_net = ColoredPetriNet::new(declarations.domains, declarations.variables, places, transitions);
+    let mut initial_tokens = BTreeMap::new();
+    for (place_id,marking_expr) in initial_marking_exprs {
+        let mut variables = BTreeSet::new();
+        marking_expr.collect_variables(&mut variables);
+        if !variables.is_empty() {
+            return Err(PnmlParsingError::UnsupportedHighLevelTerm(PNML_VARIABLE.to_string()));
+        }
+        let multiset = marking_expr.evaluate(&colored_net, &ColorBinding::default())
+            .ok_or(PnmlParsingError::HighLevelMultiplicityOverflow)?;
+        if !multiset.is_empty() {
+            initial_tokens.insert(place_id, multiset);
+        }
+    }
+    Ok(
+        ColoredPnmlFileContent::new(
+            colored_net,
+            ColoredMarking::new(initial_tokens),
+            place_text_id_to_int_id,
+            transition_text_id_to_int_id
+        )
+    )
+}
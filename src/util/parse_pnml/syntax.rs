@@ -29,4 +29,56 @@ pub const PNML_SOURCE : &str = "source";
 pub const PNML_TARGET : &str = "target";
 pub const PNML_INITIAL_MARKING : &str = "initialMarking";
 pub const PNML_TEXT : &str = "text";
+pub const PNML_INSCRIPTION : &str = "inscription";
+pub const PNML_ARC_TYPE : &str = "type";
+pub const PNML_VALUE : &str = "value";
+pub const PNML_ARC_TYPE_NORMAL : &str = "normal";
+pub const PNML_ARC_TYPE_READ : &str = "read";
+pub const PNML_ARC_TYPE_TEST : &str = "test";
+pub const PNML_ARC_TYPE_INHIBITOR : &str = "inhibitor";
+pub const PNML_ARC_TYPE_RESET : &str = "reset";
 
+// high-level (symmetric nets) elements
+pub const PNML_TYPE : &str = "type";
+pub const PNML_STRUCTURE : &str = "structure";
+pub const PNML_SUBTERM : &str = "subterm";
+pub const PNML_NAME : &str = "name";
+pub const PNML_HL_INITIAL_MARKING : &str = "hlinitialMarking";
+pub const PNML_HL_INSCRIPTION : &str = "hlinscription";
+pub const PNML_CONDITION : &str = "condition";
+pub const PNML_NAMED_SORT : &str = "namedsort";
+pub const PNML_VARIABLE_DECL : &str = "variabledecl";
+pub const PNML_USER_SORT : &str = "usersort";
+pub const PNML_DOT : &str = "dot";
+pub const PNML_CYCLIC_ENUMERATION : &str = "cyclicenumeration";
+pub const PNML_FINITE_ENUMERATION : &str = "finiteenumeration";
+pub const PNML_FE_CONSTANT : &str = "feconstant";
+pub const PNML_FINITE_INT_RANGE : &str = "finiteintrange";
+pub const PNML_START : &str = "start";
+pub const PNML_END : &str = "end";
+pub const PNML_PRODUCT_SORT : &str = "productsort";
+pub const PNML_VARIABLE : &str = "variable";
+pub const PNML_REF_VARIABLE : &str = "refvariable";
+pub const PNML_USER_OPERATOR : &str = "useroperator";
+pub const PNML_DOT_CONSTANT : &str = "dotconstant";
+pub const PNML_FINITE_INT_RANGE_CONSTANT : &str = "finiteintrangeconstant";
+pub const PNML_SUCCESSOR : &str = "successor";
+pub const PNML_PREDECESSOR : &str = "predecessor";
+pub const PNML_TUPLE : &str = "tuple";
+pub const PNML_ADD : &str = "add";
+pub const PNML_SUBTRACT : &str = "subtract";
+pub const PNML_NUMBER_OF : &str = "numberof";
+pub const PNML_NUMBER_CONSTANT : &str = "numberconstant";
+pub const PNML_ALL : &str = "all";
+pub const PNML_AND : &str = "and";
+pub const PNML_OR : &str = "or";
+pub const PNML_NOT : &str = "not";
+pub const PNML_IMPLY : &str = "imply";
+pub const PNML_EQUALITY : &str = "equality";
+pub const PNML_INEQUALITY : &str = "inequality";
+pub const PNML_LESS_THAN : &str = "lessthan";
+pub const PNML_LESS_THAN_OR_EQUAL : &str = "lessthanorequal";
+pub const PNML_GREATER_THAN : &str = "greaterthan";
+pub const PNML_GREATER_THAN_OR_EQUAL : &str = "greaterthanorequal";
+pub const PNML_BOOLEAN_CONSTANT : &str = "booleanconstant";
+pub const PNML_DECLARATION_REF : &str = "declaration";
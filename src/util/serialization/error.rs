@@ -0,0 +1,34 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq, Clone)]
+pub enum PetriSerializationError {
+    #[error("reference to unknown label of index {0}")]
+    UnknownLabelIndex(usize),
+    #[error("reference to unknown state of index {0}")]
+    UnknownStateIndex(usize),
+    #[error("the net has no transition with label {0}")]
+    UnknownTransitionLabel(String),
+    #[error("reference to unknown place of index {0}")]
+    UnknownPlaceIndex(usize),
+    #[error("the stable identifiers of the places are not one per place in increasing order")]
+    InconsistentPlaceStableIds,
+    #[error("the stable identifiers of the transitions are not one per transition in increasing order")]
+    InconsistentTransitionStableIds
+}
@@ -0,0 +1,100 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use citreelo::kripke::{KripkeState, KripkeStructure};
+
+use crate::model::label::PetriTransitionLabel;
+use crate::model::marking::Marking;
+use crate::model::net::PetriNet;
+use crate::model_checking::state::PetriKripkeState;
+use crate::util::serialization::error::PetriSerializationError;
+use crate::util::serialization::labels::{SharedLabels, SharedLabelsCollector};
+
+
+
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+struct SerializableKripkeState {
+    marking : Marking,
+    /// the index of the label of the previous tagged transition
+    previous_tagged_transition_label : Option<usize>,
+    outgoing_transitions_targets : Vec<usize>
+}
+
+/// a serializable form of a Kripke structure generated from a Petri Net
+/// in which the transition labels are serialized once and referred to by their indices
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SerializableKripkeStructure {
+    transition_labels : Vec<PetriTransitionLabel>,
+    states : Vec<SerializableKripkeState>
+}
+
+impl SerializableKripkeStructure {
+    pub fn from_kripke_structure(kripke : &KripkeStructure<PetriKripkeState>) -> Self {
+        let mut transition_labels = SharedLabelsCollector::new();
+        let states = kripke.states.iter()
+            .map(|state| {
+                SerializableKripkeState {
+                    marking : state.value_in_domain.marking.clone(),
+                    previous_tagged_transition_label : state.value_in_domain.previous_tagged_transition_label.as_ref()
+                        .map(|label| transition_labels.collect(label)),
+                    outgoing_transitions_targets : state.outgoing_transitions_targets.clone()
+                }
+            })
+            .collect();
+        Self { transition_labels : transition_labels.into_labels(), states }
+    }
+
+    /// the Kripke structure, in which the states referring to the same label share the same label object
+    pub fn to_kripke_structure(&self) -> Result<KripkeStructure<PetriKripkeState>,PetriSerializationError> {
+        let transition_labels = SharedLabels::new(self.transition_labels.clone());
+        self.build_kripke_structure(|index| transition_labels.get(index))
+    }
+
+    /// the Kripke structure, in which the labels are those of the transitions of the given net
+    /// (e.g., the deserialized net from which the Kripke structure has been generated)
+    /// so that they are shared with the net as they were before serialization
+    pub fn to_kripke_structure_for_net(&self, petri_net : &PetriNet) -> Result<KripkeStructure<PetriKripkeState>,PetriSerializationError> {
+        self.build_kripke_structure(|index| {
+            let label = self.transition_labels.get(index).ok_or(PetriSerializationError::UnknownLabelIndex(index))?;
+            petri_net.get_transitions().iter()
+                .filter_map(|transition| transition.transition_label.as_ref())
+                .find(|net_label| net_label.as_ref() == label)
+                .cloned()
+                .ok_or(PetriSerializationError::UnknownTransitionLabel(label.label.clone()))
+        })
+    }
+
+    fn build_kripke_structure(
+        &self,
+        get_label : impl Fn(usize) -> Result<std::sync::Arc<PetriTransitionLabel>,PetriSerializationError>
+    ) -> Result<KripkeStructure<PetriKripkeState>,PetriSerializationError> {
+        let mut states = Vec::new();
+        for state in &self.states {
+            if let Some(target) = state.outgoing_transitions_targets.iter().find(|target| **target >= self.states.len()) {
+                return Err(PetriSerializationError::UnknownStateIndex(*target));
+            }
+            let previous_tagged_transition_label = state.previous_tagged_transition_label.map(&get_label).transpose()?;
+            states.push(
+                KripkeState::new(
+                    PetriKripkeState::new(state.marking.clone(), previous_tagged_transition_label),
+                    state.outgoing_transitions_targets.clone()
+                )
+            );
+        }
+        Ok(KripkeStructure::new(states))
+    }
+}
@@ -0,0 +1,74 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::util::serialization::error::PetriSerializationError;
+
+
+
+
+/// gathers the distinct label objects (in the sense of their shared pointers) referred to by a serialized object
+/// so that each of them is serialized once and referred to by its index
+pub(crate) struct SharedLabelsCollector<L> {
+    labels : Vec<L>,
+    pointer_to_index : HashMap<*const L,usize>
+}
+
+impl<L : Clone> SharedLabelsCollector<L> {
+    pub(crate) fn new() -> Self {
+        Self { labels : Vec::new(), pointer_to_index : HashMap::new() }
+    }
+
+    /// the index of a label object, which is collected if it is met for the first time
+    pub(crate) fn collect(&mut self, label : &Arc<L>) -> usize {
+        let pointer = Arc::as_ptr(label);
+        match self.pointer_to_index.get(&pointer) {
+            Some(index) => *index,
+            None => {
+                let index = self.labels.len();
+                self.labels.push(label.as_ref().clone());
+                self.pointer_to_index.insert(pointer, index);
+                index
+            }
+        }
+    }
+
+    pub(crate) fn into_labels(self) -> Vec<L> {
+        self.labels
+    }
+}
+
+
+/// the label objects obtained when deserializing labels collected by a SharedLabelsCollector
+/// (all the references to the same index share the same label object)
+pub(crate) struct SharedLabels<L> {
+    labels : Vec<Arc<L>>
+}
+
+impl<L> SharedLabels<L> {
+    pub(crate) fn new(labels : Vec<L>) -> Self {
+        Self { labels : labels.into_iter().map(Arc::new).collect() }
+    }
+
+    pub(crate) fn get(&self, index : usize) -> Result<Arc<L>,PetriSerializationError> {
+        match self.labels.get(index) {
+            Some(label) => Ok(label.clone()),
+            None => Err(PetriSerializationError::UnknownLabelIndex(index))
+        }
+    }
+}
@@ -0,0 +1,120 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use crate::model::ids::PetriStableIds;
+use crate::model::label::{PetriStateLabel, PetriTransitionLabel};
+use crate::model::net::PetriNet;
+use crate::model::transition::PetriTransition;
+use crate::util::serialization::error::PetriSerializationError;
+use crate::util::serialization::labels::{SharedLabels, SharedLabelsCollector};
+
+
+
+
+/// the serialized form of a PetriNet
+/// in which the labels are serialized once and referred to by their indices
+/// so that labels which are shared between several places or transitions remain shared after deserialization
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedPetriNet {
+    place_labels : Vec<PetriStateLabel>,
+    transition_labels : Vec<PetriTransitionLabel>,
+    places : Vec<Option<usize>>,
+    /// the index of the label of each transition, and the transition itself, stripped of its label
+    transitions : Vec<(Option<usize>,PetriTransition)>,
+    place_capacities : HashMap<usize,u32>,
+    place_stable_ids : PetriStableIds,
+    transition_stable_ids : PetriStableIds
+}
+
+impl From<PetriNet> for SerializedPetriNet {
+    fn from(petri_net : PetriNet) -> Self {
+        let mut place_labels = SharedLabelsCollector::new();
+        let places = petri_net.get_places().iter()
+            .map(|place_label| place_label.as_ref().map(|label| place_labels.collect(label)))
+            .collect();
+        let mut transition_labels = SharedLabelsCollector::new();
+        let transitions = petri_net.get_transitions().iter()
+            .cloned()
+            .map(|mut transition| {
+                let label_index = transition.transition_label.take().map(|label| transition_labels.collect(&label));
+                (label_index,transition)
+            })
+            .collect();
+        Self {
+            place_labels : place_labels.into_labels(),
+            transition_labels : transition_labels.into_labels(),
+            places,
+            transitions,
+            place_capacities : petri_net.place_capacities,
+            place_stable_ids : petri_net.place_stable_ids,
+            transition_stable_ids : petri_net.transition_stable_ids
+        }
+    }
+}
+
+impl TryFrom<SerializedPetriNet> for PetriNet {
+    type Error = PetriSerializationError;
+
+    /// the place ids of the arcs and capacities as well as the stable identifiers are checked
+    /// as the serialized form may have been produced or edited by other means
+    fn try_from(serialized : SerializedPetriNet) -> Result<Self, Self::Error> {
+        let num_places = serialized.places.len();
+        let check_place_id = |place_id : &usize| {
+            if *place_id < num_places {
+                Ok(())
+            } else {
+                Err(PetriSerializationError::UnknownPlaceIndex(*place_id))
+            }
+        };
+        for (_,transition) in &serialized.transitions {
+            transition.preset_tokens.keys()
+                .chain(transition.postset_tokens.keys())
+                .chain(transition.read_tokens.keys())
+                .chain(transition.inhibitor_tokens.keys())
+                .chain(transition.reset_places.iter())
+                .try_for_each(check_place_id)?;
+        }
+        serialized.place_capacities.keys().try_for_each(check_place_id)?;
+        if serialized.place_stable_ids.len() != num_places || !serialized.place_stable_ids.is_well_formed() {
+            return Err(PetriSerializationError::InconsistentPlaceStableIds);
+        }
+        if serialized.transition_stable_ids.len() != serialized.transitions.len() || !serialized.transition_stable_ids.is_well_formed() {
+            return Err(PetriSerializationError::InconsistentTransitionStableIds);
+        }
+        let place_labels = SharedLabels::new(serialized.place_labels);
+        let mut places = Vec::new();
+        for label_index in serialized.places {
+            places.push(label_index.map(|index| place_labels.get(index)).transpose()?);
+        }
+        let transition_labels = SharedLabels::new(serialized.transition_labels);
+        let mut transitions = Vec::new();
+        for (label_index,mut transition) in serialized.transitions {
+            transition.transition_label = label_index.map(|index| transition_labels.get(index)).transpose()?;
+            transitions.push(transition);
+        }
+        Ok(
+            PetriNet::from_parts(
+                places,
+                transitions,
+                serialized.place_capacities,
+                serialized.place_stable_ids,
+                serialized.transition_stable_ids
+            )
+        )
+    }
+}
@@ -17,7 +17,7 @@ limitations under the License.
 use citreelo::util::viz_kripke::KripkeStructureGraphvizDrawer;
 use itertools::Itertools;
 
-use crate::{model::net::PetriNet, model_checking::state::PetriKripkeState};
+use crate::{model::{marking::Marking, net::PetriNet}, model_checking::{state::PetriKripkeState, state_class::PetriStateClass}};
 
 
 
@@ -29,16 +29,13 @@ impl<'a> PetriKripkeVisualizer<'a> {
     pub fn new(net: &'a PetriNet) -> Self {
         Self { net }
     }
-}
-
 
-impl<'a> KripkeStructureGraphvizDrawer<PetriKripkeState> for PetriKripkeVisualizer<'a> {
-    fn get_doap_label(&self,doap : &PetriKripkeState) -> String {
-        let toks_str = doap.marking.iter_tokens()
+    fn get_marking_label(&self, marking : &Marking) -> String {
+        marking.iter_tokens()
         .filter(|(_,y)| **y>0)
         .map(
             |(place_id,num_toks)| {
-                let place = self.net.places.get(*place_id).unwrap();
+                let place = self.net.get_places().get(*place_id).unwrap();
                 if let Some(place_label) = place {
                     format!("@p{}({:}):{}",place_id,place_label,num_toks)
                 } else {
@@ -46,7 +43,14 @@ impl<'a> KripkeStructureGraphvizDrawer<PetriKripkeState> for PetriKripkeVisualiz
                 }
             }
         )
-        .join("\n");
+        .join("\n")
+    }
+}
+
+
+impl<'a> KripkeStructureGraphvizDrawer<PetriKripkeState> for PetriKripkeVisualizer<'a> {
+    fn get_doap_label(&self,doap : &PetriKripkeState) -> String {
+        let toks_str = self.get_marking_label(&doap.marking);
         match &doap.previous_tagged_transition_label {
             Some(previous_transition_label) => {
                 format!("{}\nprev:{}",toks_str,previous_transition_label)
@@ -58,3 +62,29 @@ impl<'a> KripkeStructureGraphvizDrawer<PetriKripkeState> for PetriKripkeVisualiz
     }
 }
 
+
+impl<'a> KripkeStructureGraphvizDrawer<PetriStateClass> for PetriKripkeVisualizer<'a> {
+    fn get_doap_label(&self,doap : &PetriStateClass) -> String {
+        let toks_str = self.get_marking_label(&doap.marking);
+        let domain_str = doap.firing_domain.iter_enabled_transitions()
+        .map(
+            |tr_id| {
+                let (earliest,latest) = doap.firing_domain.get_firing_times(*tr_id).unwrap();
+                match latest {
+                    Some(latest) => format!("@t{}:[{},{}]",tr_id,earliest,latest),
+                    None => format!("@t{}:[{},∞)",tr_id,earliest)
+                }
+            }
+        )
+        .join("\n");
+        let label = format!("{}\n{}",toks_str,domain_str);
+        match &doap.previous_tagged_transition_label {
+            Some(previous_transition_label) => {
+                format!("{}\nprev:{}",label,previous_transition_label)
+            },
+            None => {
+                label
+            }
+        }
+    }
+}
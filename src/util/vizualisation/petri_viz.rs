@@ -17,12 +17,13 @@ limitations under the License.
 
 
 
-use graphviz_dot_builder::{edge::{edge::GraphVizEdge, style::GraphvizEdgeStyleItem}, graph::graph::GraphVizDiGraph, item::node::{node::GraphVizNode, style::{GraphvizNodeStyleItem, GvNodeShape}}, traits::DotBuildable};
+use graphviz_dot_builder::{edge::{edge::GraphVizEdge, style::{GraphvizEdgeStyleItem, GvArrowHeadFill, GvArrowHeadSide, GvArrowHeadStyle, GvEdgeLineStyle}}, graph::graph::GraphVizDiGraph, item::node::{node::GraphVizNode, style::{GraphvizNodeStyleItem, GvNodeShape}}, traits::DotBuildable};
 
 use crate::model::{marking::Marking, net::PetriNet};
 
 
 
+#[allow(clippy::collapsible_if)]
 pub fn petri_repr(
     petri : &PetriNet, 
     marking : &Option<Marking>
@@ -30,11 +31,14 @@ pub fn petri_repr(
     // Create a new graph:
     let mut digraph = GraphVizDiGraph::new(vec![]);
     // places
-    for (place_id,place_content) in petri.places.iter().enumerate() {
+    for (place_id,place_content) in petri.get_places().iter().enumerate() {
         let mut label = format!("p{:}",place_id);
         if let Some(lab_ref) = place_content {
             label.push_str(&format!(":({:})", lab_ref));
         };
+        if let Some(capacity) = petri.get_place_capacity(&place_id) {
+            label.push_str(&format!("\ncap:{:}", capacity));
+        }
         if let Some(mrk) = marking {
             if let Some(num_tokens_at_place) = mrk.get_num_toks_at_place(&place_id) {
                 debug_assert!(*num_tokens_at_place >0);
@@ -47,8 +51,8 @@ pub fn petri_repr(
         digraph.add_node(GraphVizNode::new(format!("place{:}",place_id),style));
     }
     // transitions
-    for (tr_id,transition) in petri.transitions.iter().enumerate() {
-        let transition_label = match &transition.transition_label {
+    for (tr_id,transition) in petri.get_transitions().iter().enumerate() {
+        let mut transition_label = match &transition.transition_label {
             Some(tr_lab) => {
                 format!("{:}",tr_lab)
             },
@@ -56,6 +60,15 @@ pub fn petri_repr(
                 "".to_string()
             }
         };
+        if let Some(priority) = &transition.priority {
+            transition_label.push_str(&format!("\nprio:{:}", priority));
+        }
+        if let Some(firing_interval) = &transition.firing_interval {
+            transition_label.push_str(&format!("\n{:}", firing_interval));
+        }
+        if let Some(stochastic_timing) = &transition.stochastic_timing {
+            transition_label.push_str(&format!("\n{:}", stochastic_timing));
+        }
         let style = vec![
                 GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle),
                 GraphvizNodeStyleItem::Label(transition_label)];
@@ -92,6 +105,53 @@ pub fn petri_repr(
             );
             digraph.add_edge(edge);
         }
+        for (inhibitor_place,inhibitor_threshold) in transition.iter_inhibitor_tokens() {
+            debug_assert!(*inhibitor_threshold>0);
+            let mut style = vec![GraphvizEdgeStyleItem::Head(GvArrowHeadStyle::Dot(GvArrowHeadFill::Open))];
+            if *inhibitor_threshold > 1 {
+                style.push(GraphvizEdgeStyleItem::Label(inhibitor_threshold.to_string()));
+            }
+            let edge = GraphVizEdge::new(
+                format!("place{:}",inhibitor_place),
+                None,
+                format!("tr{:}",tr_id),
+                None,
+                style
+            );
+            digraph.add_edge(edge);
+        }
+        for reset_place in transition.iter_reset_places() {
+            let style = vec![
+                GraphvizEdgeStyleItem::Head(GvArrowHeadStyle::Tee(GvArrowHeadSide::Both)),
+                GraphvizEdgeStyleItem::Label("reset".to_string())
+            ];
+            let edge = GraphVizEdge::new(
+                format!("tr{:}",tr_id),
+                None,
+                format!("place{:}",reset_place),
+                None,
+                style
+            );
+            digraph.add_edge(edge);
+        }
+        for (read_place,read_num_toks) in transition.iter_read_tokens() {
+            debug_assert!(*read_num_toks>0);
+            let mut style = vec![
+                GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Dashed),
+                GraphvizEdgeStyleItem::Head(GvArrowHeadStyle::NoArrow)
+            ];
+            if *read_num_toks > 1 {
+                style.push(GraphvizEdgeStyleItem::Label(read_num_toks.to_string()));
+            }
+            let edge = GraphVizEdge::new(
+                format!("place{:}",read_place),
+                None,
+                format!("tr{:}",tr_id),
+                None,
+                style
+            );
+            digraph.add_edge(edge);
+        }
     }
     digraph
 }
@@ -0,0 +1,147 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{label::PetriStateLabel, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::{bounds::{compute_place_bounds, PetriPlaceBound, PetriPlaceBoundsError}, coverability::PetriCoverabilityGenerationError, to_kripke::{PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement}}};
+use map_macro::{btree_map, hash_map};
+
+
+
+
+fn tool_test_bounds(
+    title : &str,
+    pn : &PetriNet,
+    im : &Marking,
+    req : &PetriKripkeGenerationSafenessRequirement,
+    expected_bounds : Vec<Option<u32>>,
+    expected_safeness : Option<u32>
+) {
+    let bounds = compute_place_bounds(pn, im, req).unwrap();
+    for (place_id,expected) in expected_bounds.into_iter().enumerate() {
+        let bound = bounds.get_place_bound(place_id);
+        assert_eq!(bound.get_bound(), expected, "{:} : bound of place {}", title, place_id);
+        if let PetriPlaceBound::Bounded { bound, witness } = bound {
+            assert_eq!(*witness.get_num_toks_at_place(&place_id).unwrap_or(&0), *bound, "{:} : witness of place {}", title, place_id);
+        }
+    }
+    assert_eq!(bounds.get_safeness(), expected_safeness, "{:} : safeness", title);
+}
+
+
+#[test]
+pub fn test_bounds_of_unbounded_net() {
+    // "pump" puts arbitrarily many tokens in B, "split" consumes A and one token of B to put two tokens in C
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("C".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {0=>1,1=>1}),
+            PetriTransition::new(None, hash_map! {0=>1,1=>1}, hash_map! {2=>2})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    tool_test_bounds("pump_and_split", &pn, &im, &PetriKripkeGenerationSafenessRequirement::No, vec![Some(1),None,Some(2)], None);
+    let bounds = compute_place_bounds(&pn, &im, &PetriKripkeGenerationSafenessRequirement::No).unwrap();
+    assert_eq!(bounds.iter_unbounded_places().collect::<Vec<usize>>(), vec![1]);
+}
+
+
+#[test]
+pub fn test_bounds_of_bounded_nets() {
+    let lock_net = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {0=>1,2=>1}, hash_map! {1=>1}),
+            PetriTransition::new(None, hash_map! {3=>1,2=>1}, hash_map! {4=>1}),
+            PetriTransition::new(None, hash_map! {1=>1}, hash_map! {0=>1,2=>1}),
+            PetriTransition::new(None, hash_map! {4=>1}, hash_map! {3=>1,2=>1})
+        ]
+    );
+    tool_test_bounds(
+        "lock_unlock", 
+        &lock_net, 
+        &Marking::new(btree_map! {0=>1,2=>1,3=>1}), 
+        &PetriKripkeGenerationSafenessRequirement::No,
+        vec![Some(1),Some(1),Some(1),Some(1),Some(1)], 
+        Some(1)
+    );
+    // tokens are produced in P until there are 3 of them (inhibitor arc), then moved to Q one at a time
+    let inhibited = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("P".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("Q".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {}, hash_map! {0=>1}).with_inhibitor_tokens(hash_map! {0=>3,1=>1}),
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1}).with_read_tokens(hash_map! {0=>2})
+        ]
+    );
+    tool_test_bounds(
+        "inhibited",
+        &inhibited,
+        &Marking::new(btree_map! {}),
+        &PetriKripkeGenerationSafenessRequirement::KSafeness(3),
+        vec![Some(3),Some(2)],
+        Some(3)
+    );
+}
+
+
+#[test]
+pub fn test_bounds_with_capacities_and_safeness_requirement() {
+    // "produce" puts tokens in P, within its capacity, and "forward" moves them to Q, which is unbounded
+    let capacitated = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("P".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("Q".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {}, hash_map! {0=>1}),
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1})
+        ]
+    ).with_place_capacities(hash_map! {0=>2});
+    let im = Marking::new(btree_map! {});
+    tool_test_bounds("capacitated", &capacitated, &im, &PetriKripkeGenerationSafenessRequirement::No, vec![Some(2),None], None);
+    // tokens are produced in P as long as Q is empty, so that P is unbounded
+    let inhibited = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("P".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("Q".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {}, hash_map! {0=>1}).with_inhibitor_tokens(hash_map! {1=>1})
+        ]
+    );
+    assert_eq!(
+        compute_place_bounds(&inhibited, &im, &PetriKripkeGenerationSafenessRequirement::KSafeness(5)).err(),
+        Some(PetriPlaceBoundsError::Enumeration(PetriKripkeGenerationError::KSafenessViolation { place_id: 0, transition_id: 0 }))
+    );
+    // without a k-safeness requirement, the enumeration would never terminate
+    assert_eq!(
+        compute_place_bounds(&inhibited, &im, &PetriKripkeGenerationSafenessRequirement::No).err(),
+        Some(PetriPlaceBoundsError::MissingSafenessRequirement(PetriCoverabilityGenerationError::InhibitorArc { transition_id: 0 }))
+    );
+}
@@ -0,0 +1,180 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::model::{builder::{PetriNetBuilder, PetriNetBuildingError}, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition};
+use map_macro::{btree_map, hash_map, hash_set};
+
+
+
+
+#[test]
+pub fn test_builder_matches_hand_built_net() {
+    let (pn,im) = PetriNetBuilder::new()
+        .add_place("A_U")
+        .add_place("A_L")
+        .add_place("CTL")
+        .add_place("B_U")
+        .add_place("B_L")
+        .add_labelled_transition("A_lock", Some("lock"))
+        .add_labelled_transition("B_lock", Some("lock"))
+        .add_labelled_transition("A_unlock", Some("unlock"))
+        .add_labelled_transition("B_unlock", Some("unlock"))
+        .add_arc("A_U", "A_lock", 1)
+        .add_arc("CTL", "A_lock", 1)
+        .add_arc("A_lock", "A_L", 1)
+        .add_arc("B_U", "B_lock", 1)
+        .add_arc("CTL", "B_lock", 1)
+        .add_arc("B_lock", "B_L", 1)
+        .add_arc("A_L", "A_unlock", 1)
+        .add_arc("A_unlock", "A_U", 1)
+        .add_arc("A_unlock", "CTL", 1)
+        .add_arc("B_L", "B_unlock", 1)
+        .add_arc("B_unlock", "B_U", 1)
+        .add_arc("B_unlock", "CTL", 1)
+        .set_initial_tokens("A_U", 1)
+        .set_initial_tokens("CTL", 1)
+        .set_initial_tokens("B_U", 1)
+        .build()
+        .unwrap();
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let expected_pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            )
+        ]
+    );
+    assert_eq!(pn, expected_pn);
+    assert_eq!(im, Marking::new(btree_map! {0=>1,2=>1,3=>1}));
+    // transitions with the same label share it
+    assert!(Arc::ptr_eq(pn.get_transitions()[0].transition_label.as_ref().unwrap(), pn.get_transitions()[1].transition_label.as_ref().unwrap()));
+}
+
+
+#[test]
+pub fn test_builder_weights_and_extended_arcs() {
+    let (pn,im) = PetriNetBuilder::new()
+        .add_transition("t")
+        .add_labelled_transition("tau", None)
+        .add_arc("P", "t", 1)
+        .add_arc("P", "t", 2)
+        .add_arc("t", "Q", 3)
+        .add_read_arc("R", "t", 1)
+        .add_inhibitor_arc("Q", "t", 5)
+        .add_reset_arc("tau", "Q")
+        .add_place("P")
+        .add_place("Q")
+        .add_place("R")
+        .set_initial_tokens("P", 4)
+        .set_initial_tokens("R", 1)
+        .set_initial_tokens("R", 0)
+        .build()
+        .unwrap();
+    let expected_pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("P".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("Q".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("R".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("t".to_string()))),
+                hash_map! {0=>3},
+                hash_map! {1=>3}
+            ).with_read_tokens(hash_map! {2=>1}).with_inhibitor_tokens(hash_map! {1=>5}),
+            PetriTransition::new(
+                None,
+                hash_map! {},
+                hash_map! {}
+            ).with_reset_places(hash_set! {1}),
+        ]
+    );
+    assert_eq!(pn, expected_pn);
+    assert_eq!(im, Marking::new(btree_map! {0=>4}));
+}
+
+
+#[test]
+pub fn test_builder_errors() {
+    let base = || PetriNetBuilder::new().add_place("P").add_place("Q").add_transition("t");
+    assert_eq!(
+        base().add_place("t").build().err(),
+        Some(PetriNetBuildingError::DuplicateName("t".to_string()))
+    );
+    assert_eq!(
+        base().add_transition("P").build().err(),
+        Some(PetriNetBuildingError::DuplicateName("P".to_string()))
+    );
+    assert_eq!(
+        base().add_arc("X", "t", 1).build().err(),
+        Some(PetriNetBuildingError::UnknownName("X".to_string()))
+    );
+    assert_eq!(
+        base().add_arc("P", "u", 1).build().err(),
+        Some(PetriNetBuildingError::UnknownTransition("u".to_string()))
+    );
+    assert_eq!(
+        base().add_arc("t", "R", 1).build().err(),
+        Some(PetriNetBuildingError::UnknownPlace("R".to_string()))
+    );
+    assert_eq!(
+        base().set_initial_tokens("R", 1).build().err(),
+        Some(PetriNetBuildingError::UnknownPlace("R".to_string()))
+    );
+    assert_eq!(
+        base().add_arc("P", "Q", 1).build().err(),
+        Some(PetriNetBuildingError::ArcMustConnectPlaceAndTransition { from: "P".to_string(), to: "Q".to_string() })
+    );
+    assert_eq!(
+        base().add_read_arc("t", "P", 1).build().err(),
+        Some(PetriNetBuildingError::ArcMustConnectPlaceAndTransition { from: "t".to_string(), to: "P".to_string() })
+    );
+    assert_eq!(
+        base().add_arc("P", "t", 0).build().err(),
+        Some(PetriNetBuildingError::ZeroWeight { from: "P".to_string(), to: "t".to_string() })
+    );
+    assert_eq!(
+        PetriNetBuildingError::ZeroWeight { from: "P".to_string(), to: "t".to_string() }.to_string(),
+        "the arc from 'P' to 't' has a zero weight"
+    );
+}
@@ -0,0 +1,155 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{collections::HashMap, sync::Arc};
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, reduction::reduce::reduce_petri_net, util::{parse_ctl::parser::BuiltinPetriCtlParser, vizualisation::{kripke_viz::PetriKripkeVisualizer, petri_viz::petri_repr}}};
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+use map_macro::{btree_map, hash_map, hash_set};
+
+use citreelo::util::viz_kripke::KripkeStructureGraphvizDrawer;
+
+use citreelo::solve::is_ctl_formula_sat;
+use citreelo::parser::CtlFormulaParser;
+
+
+
+
+fn tool_test_capacity_mc(
+    title : &str,
+    pn : &PetriNet,
+    im : Marking,
+    expected_num_states : usize,
+    formulaes : &HashMap<&'static str, bool>
+) {
+    let folder_name = "test_capacities";
+    let _ = std::fs::create_dir(folder_name);
+    {
+        let gv = petri_repr(pn,&Some(im.clone()));
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_1net", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    let ctl_parser = BuiltinPetriCtlParser::from_net(pn).unwrap();
+    let kripke = petri_to_kripke(
+        pn,
+        im,
+        &PetriKripkeStateProducer::new(hash_set! {}),
+        &PetriKripkeGenerationSafenessRequirement::No
+    ).unwrap();
+    {
+        let gv = PetriKripkeVisualizer::new(pn).get_kripke_repr(&kripke);
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_2kripke", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    assert_eq!(kripke.states.len(), expected_num_states, "{:} : number of states", title);
+    for (phi_as_str,is_sat) in formulaes {
+        let (_,phi) = ctl_parser.parse_ctl_formula::<nom::error::Error<&str>>(
+            phi_as_str
+        ).unwrap();
+        let result = is_ctl_formula_sat(
+            &kripke,
+            &hash_set! {0},
+            &phi
+        );
+        assert_eq!(result,*is_sat,"{:} : {} -> {} | expected {}", title, phi_as_str, result, is_sat);
+    }
+}
+
+
+#[test]
+pub fn test_bounded_buffer() {
+    // without its capacity, the buffer would be unbounded
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("READY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("produce".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("consume".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("fill".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>2}
+            ).with_reset_places(hash_set! {1}),
+        ]
+    ).with_place_capacities(hash_map! {1=>2});
+    let im = Marking::new(btree_map! {0=>1});
+    let formulaes = hash_map! {
+        r#"A(G( tokens-count("BUF")<=2 ))"#                                  => true,
+        r#"E(F( tokens-count("BUF")=2 ))"#                                   => true,
+        r#"A(G( (tokens-count("BUF")=2) => (!( is-fireable("produce") )) ))"# => true,
+        r#"A(G( (tokens-count("BUF")=1) => (is-fireable("produce")) ))"#     => true,
+        r#"A(G( is-fireable("fill") ))"#                                     => true,
+    };
+    tool_test_capacity_mc("bounded_buffer", &pn, im.clone(), 3, &formulaes);
+    // nets with capacities are not reduced
+    let mut reduced_pn = pn.clone();
+    let mut reduced_im = Some(im.clone());
+    reduce_petri_net(&mut reduced_pn, &mut reduced_im);
+    assert_eq!(reduced_pn, pn);
+    // the complementary places encoding has the same behavior
+    let (compiled_pn,compiled_im,complementary_places) = pn.compile_capacities_into_complementary_places(&im);
+    assert_eq!(complementary_places, hash_map! {1=>2});
+    assert_eq!(compiled_pn.iter_place_capacities().count(), 0);
+    assert_eq!(compiled_pn.get_places()[2].as_ref().unwrap().label, "BUF_complement");
+    assert_eq!(compiled_im, Marking::new(btree_map! {0=>1,2=>2}));
+    tool_test_capacity_mc("bounded_buffer_compiled", &compiled_pn, compiled_im, 3, &formulaes);
+}
+
+
+#[test]
+pub fn test_capacity_does_not_block_consumption() {
+    // the initial marking exceeds the capacity, which only forbids adding tokens
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("take".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("give".to_string()))),
+                hash_map! {},
+                hash_map! {0=>1}
+            ),
+        ]
+    ).with_place_capacities(hash_map! {0=>1});
+    let im = Marking::new(btree_map! {0=>3});
+    let formulaes = hash_map! {
+        r#"E(F( tokens-count("A")=0 ))"#                               => true,
+        r#"A(G( (tokens-count("A")>=1) => (!( is-fireable("give") )) ))"# => true,
+        r#"A(G( (tokens-count("A")=0) => (is-fireable("give")) ))"#    => true,
+    };
+    tool_test_capacity_mc("capacity_does_not_block_consumption", &pn, im, 4, &formulaes);
+}
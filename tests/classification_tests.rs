@@ -0,0 +1,107 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, net::PetriNet, transition::PetriTransition}, structural::classification::{classify_petri_net, PetriClassViolation, PetriNetClass}};
+use map_macro::hash_map;
+
+
+
+
+#[test]
+pub fn test_classify_lock_unlock() {
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            )
+        ]
+    );
+    let classification = classify_petri_net(&pn);
+    assert_eq!(
+        classification.get_classes(),
+        vec![PetriNetClass::Ordinary, PetriNetClass::Pure, PetriNetClass::AsymmetricChoice]
+    );
+    // the lock "CTL" is an input place of both lock transitions
+    assert_eq!(classification.get_violation(PetriNetClass::StateMachine), Some(&PetriClassViolation::new(Some(2), Some(0))));
+    assert_eq!(classification.get_violation(PetriNetClass::MarkedGraph), Some(&PetriClassViolation::new(Some(2), Some(3))));
+    assert_eq!(classification.get_violation(PetriNetClass::FreeChoice), Some(&PetriClassViolation::new(Some(2), Some(0))));
+    // "A_U" shares its output transition with "CTL", which has another output transition
+    assert_eq!(classification.get_violation(PetriNetClass::ExtendedFreeChoice), Some(&PetriClassViolation::new(Some(0), Some(1))));
+}
+
+
+#[test]
+pub fn test_classify_cycles() {
+    // a simple cycle is both a state machine and a marked graph
+    let cycle = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1}),
+            PetriTransition::new(None, hash_map! {1=>1}, hash_map! {0=>1}),
+        ]
+    );
+    let classification = classify_petri_net(&cycle);
+    assert_eq!(classification.get_classes(), PetriNetClass::all());
+    // adding a choice keeps it a (free-choice) state machine, but not a marked graph
+    let mut choice = cycle.clone();
+    choice.add_transition(PetriTransition::new(None, hash_map! {0=>1}, hash_map! {0=>1}));
+    let classification = classify_petri_net(&choice);
+    assert!(classification.is_of_class(PetriNetClass::StateMachine));
+    assert!(classification.is_of_class(PetriNetClass::FreeChoice));
+    assert!(!classification.is_of_class(PetriNetClass::MarkedGraph));
+    assert_eq!(classification.get_violation(PetriNetClass::Pure), Some(&PetriClassViolation::new(Some(0), Some(2))));
+    // a read arc is a self-loop and a weighted arc makes the net not ordinary
+    let mut weighted = cycle.clone();
+    weighted.add_transition(
+        PetriTransition::new(None, hash_map! {1=>2}, hash_map! {}).with_read_tokens(hash_map! {0=>1})
+    );
+    let classification = classify_petri_net(&weighted);
+    let weighted_arc = PetriClassViolation::new(Some(1), Some(2));
+    assert_eq!(classification.get_violation(PetriNetClass::Ordinary), Some(&weighted_arc));
+    assert_eq!(classification.get_violation(PetriNetClass::StateMachine), Some(&weighted_arc));
+    assert_eq!(classification.get_violation(PetriNetClass::Pure), Some(&PetriClassViolation::new(Some(0), Some(2))));
+}
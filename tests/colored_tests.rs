@@ -0,0 +1,212 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use petricheck::{colored::{domain::{Color, ColorDomain, ColorDomainKind}, expression::{ColorBinding, ColorComparison, ColorGuard, ColorTerm, ColoredArcExpression}, net::{ColoredMarking, ColoredPetriNet, ColoredPlace, ColoredTransition, ColoredVariable}, unfold::{unfold_colored_petri_net, ColoredUnfolding, ColoredUnfoldingError}}, model::label::PetriTransitionLabel, model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, util::{parse_ctl::parser::BuiltinPetriCtlParser, parse_pnml::{error::PnmlParsingError, symmetric::read_colored_petri_net_from_pnml_file_path}, vizualisation::{kripke_viz::PetriKripkeVisualizer, petri_viz::petri_repr}}};
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+use map_macro::{btree_map, hash_map, hash_set};
+
+use citreelo::util::viz_kripke::KripkeStructureGraphvizDrawer;
+
+use citreelo::solve::is_ctl_formula_sat;
+use citreelo::parser::CtlFormulaParser;
+
+
+
+
+fn tool_test_colored_mc(
+    title : &str,
+    unfolding : &ColoredUnfolding,
+    expected_num_states : usize,
+    formulaes : HashMap<&'static str, bool>
+) {
+    let folder_name = "test_colored";
+    let _ = std::fs::create_dir(folder_name);
+    let pn = &unfolding.petri_net;
+    {
+        let gv = petri_repr(pn,&Some(unfolding.initial_marking.clone()));
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_1unfolded", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    let ctl_parser = BuiltinPetriCtlParser::from_net(pn).unwrap();
+    let kripke = petri_to_kripke(
+        pn,
+        unfolding.initial_marking.clone(),
+        &PetriKripkeStateProducer::new(hash_set! {}),
+        &PetriKripkeGenerationSafenessRequirement::KSafeness(1)
+    ).unwrap();
+    {
+        let gv = PetriKripkeVisualizer::new(pn).get_kripke_repr(&kripke);
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_2kripke", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    assert_eq!(kripke.states.len(), expected_num_states, "{:} : number of states", title);
+    for (phi_as_str,is_sat) in formulaes {
+        let (_,phi) = ctl_parser.parse_ctl_formula::<nom::error::Error<&str>>(
+            phi_as_str
+        ).unwrap();
+        let result = is_ctl_formula_sat(
+            &kripke,
+            &hash_set! {0},
+            &phi
+        );
+        assert_eq!(result,is_sat,"{:} : {} -> {} | expected {}", title, phi_as_str, result, is_sat);
+    }
+}
+
+
+#[test]
+pub fn test_philosophers_from_pnml() {
+    // the third philosopher never eats (guard of "take") and a waiter lets philosophers eat one at a time
+    let content = read_colored_petri_net_from_pnml_file_path("tests/pnml/philosophers_colored.pnml").unwrap();
+    let unfolding = unfold_colored_petri_net(&content.colored_net, &content.initial_marking).unwrap();
+    let formulaes = hash_map! {
+        r#"E(F( tokens-count("Eat_1")=1 ))"#                                        => true,
+        r#"E(F( tokens-count("Eat_3")=1 ))"#                                        => false,
+        r#"A(G( !( (tokens-count("Eat_1")=1) & (tokens-count("Eat_2")=1) ) ))"#     => true,
+        r#"A(G( (tokens-count("Waiter")=0) => (tokens-count("Fork_2")=0) ))"#       => true,
+        r#"A(G( E(F( tokens-count("Think_2")=1 )) ))"#                              => true,
+    };
+    tool_test_colored_mc("philosophers", &unfolding, 3, formulaes);
+}
+
+
+#[test]
+pub fn test_product_domain_with_guard() {
+    // two processes, each of which may send a message to the other, which then acknowledges it
+    let colored_net = ColoredPetriNet::new(
+        vec![
+            ColorDomain::new("Proc".to_string(), ColorDomainKind::Enumeration(vec!["a".to_string(),"b".to_string()])),
+            ColorDomain::new("Msg".to_string(), ColorDomainKind::Product(vec![0,0])),
+        ],
+        vec![
+            ColoredVariable::new("x".to_string(), 0),
+            ColoredVariable::new("y".to_string(), 0),
+        ],
+        vec![
+            ColoredPlace::new("Idle".to_string(), 0),
+            ColoredPlace::new("Chan".to_string(), 1),
+        ],
+        vec![
+            ColoredTransition::new(
+                "send".to_string(),
+                ColorGuard::Compare(ColorTerm::Variable(0), ColorComparison::NotEqual, ColorTerm::Variable(1)),
+                hash_map! {0=>ColoredArcExpression::Single(ColorTerm::Variable(0))},
+                hash_map! {1=>ColoredArcExpression::Single(ColorTerm::Tuple(vec![ColorTerm::Variable(0),ColorTerm::Variable(1)]))}
+            ),
+            ColoredTransition::new(
+                "ack".to_string(),
+                ColorGuard::Constant(true),
+                hash_map! {1=>ColoredArcExpression::Single(ColorTerm::Tuple(vec![ColorTerm::Variable(0),ColorTerm::Variable(1)]))},
+                hash_map! {0=>ColoredArcExpression::Single(ColorTerm::Variable(0))}
+            ),
+        ]
+    );
+    let initial_marking = ColoredMarking::new(btree_map! {0=>btree_map! {Color::new(vec![0])=>1,Color::new(vec![1])=>1}});
+    let unfolding = unfold_colored_petri_net(&colored_net, &initial_marking).unwrap();
+    // 2 + 4 places, 2 bindings of "send" satisfy the guard and all 4 bindings of "ack" are kept
+    assert_eq!(unfolding.petri_net.get_places().len(), 6);
+    assert_eq!(unfolding.petri_net.get_transitions().len(), 6);
+    let chan_ab = *unfolding.unfolded_place_ids.get(&(1,Color::new(vec![0,1]))).unwrap();
+    assert_eq!(unfolding.petri_net.get_places()[chan_ab].as_ref().unwrap().label, "Chan_a_b");
+    assert_eq!(unfolding.place_origins[chan_ab], (1,Color::new(vec![0,1])));
+    assert_eq!(
+        unfolding.transition_origins.iter().filter(|(tr_id,_)| *tr_id == 0).map(|(_,b)| b.clone()).collect::<Vec<ColorBinding>>(),
+        vec![ColorBinding::new(btree_map! {0=>0,1=>1}),ColorBinding::new(btree_map! {0=>1,1=>0})]
+    );
+    // all unfolded transitions of a colored transition share its name as label
+    assert!(
+        unfolding.petri_net.get_transitions().iter().take(2).all(
+            |tr| tr.transition_label.as_deref() == Some(&PetriTransitionLabel::new("send".to_string()))
+        )
+    );
+    let formulaes = hash_map! {
+        r#"A(G( tokens-count("Chan_a_a")=0 ))"#                                   => true,
+        r#"E(F( (tokens-count("Chan_a_b")=1) & (tokens-count("Chan_b_a")=1) ))"#  => true,
+        r#"A(G( E(F( tokens-count("Idle_b")=1 )) ))"#                             => true,
+    };
+    tool_test_colored_mc("product_domain_with_guard", &unfolding, 4, formulaes);
+}
+
+
+#[test]
+pub fn test_multiplicity_overflow_is_rejected() {
+    let colored_net = ColoredPetriNet::new(
+        vec![ColorDomain::new("Proc".to_string(), ColorDomainKind::Enumeration(vec!["a".to_string(),"b".to_string()]))],
+        vec![],
+        vec![ColoredPlace::new("P".to_string(), 0)],
+        vec![
+            ColoredTransition::new(
+                "t".to_string(),
+                ColorGuard::Constant(true),
+                hash_map! {},
+                hash_map! {0=>ColoredArcExpression::Scaled(u32::MAX, Box::new(ColoredArcExpression::Scaled(2, Box::new(ColoredArcExpression::All(0)))))}
+            ),
+        ]
+    );
+    let result = unfold_colored_petri_net(&colored_net, &ColoredMarking::new(btree_map! {}));
+    assert!(matches!(result, Err(ColoredUnfoldingError::MultiplicityOverflow { transition_id: 0 })));
+}
+
+
+#[test]
+pub fn test_duplicate_unfolded_place_names_are_rejected() {
+    // "P" with color "a_b" and "P_a" with color "b" would both be unfolded as "P_a_b"
+    let colored_net = ColoredPetriNet::new(
+        vec![
+            ColorDomain::new("D1".to_string(), ColorDomainKind::Enumeration(vec!["a_b".to_string(),"c".to_string()])),
+            ColorDomain::new("D2".to_string(), ColorDomainKind::Enumeration(vec!["b".to_string(),"c".to_string()])),
+        ],
+        vec![],
+        vec![
+            ColoredPlace::new("P".to_string(), 0),
+            ColoredPlace::new("P_a".to_string(), 1),
+        ],
+        vec![]
+    );
+    let result = unfold_colored_petri_net(&colored_net, &ColoredMarking::new(btree_map! {}));
+    assert!(matches!(result, Err(ColoredUnfoldingError::DuplicatePlaceName { name }) if name == "P_a_b"));
+}
+
+
+#[test]
+pub fn test_pnml_int_range_constant_out_of_range() {
+    let result = read_colored_petri_net_from_pnml_file_path("tests/pnml/colored_constant_out_of_range.pnml");
+    assert!(matches!(result, Err(PnmlParsingError::HighLevelConstantOutOfRange { value: 7, start: 1, end: 3 })));
+}
+
+
+#[test]
+pub fn test_pnml_empty_sorts() {
+    let result = read_colored_petri_net_from_pnml_file_path("tests/pnml/colored_empty_sort.pnml");
+    assert!(matches!(result, Err(PnmlParsingError::EmptyHighLevelSort(name)) if name == "Nobody"));
+    let result = read_colored_petri_net_from_pnml_file_path("tests/pnml/colored_empty_range.pnml");
+    assert!(matches!(result, Err(PnmlParsingError::EmptyHighLevelSort(_))));
+}
+
+
+#[test]
+pub fn test_pnml_cyclic_named_sorts() {
+    let result = read_colored_petri_net_from_pnml_file_path("tests/pnml/colored_cyclic_sort.pnml");
+    assert!(matches!(result, Err(PnmlParsingError::CyclicNamedSort(id)) if id == "Pair"));
+}
@@ -0,0 +1,283 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{collections::HashMap, sync::Arc};
+
+use petricheck::{composition::{fusion::compose_by_place_fusion, synchronization::compose_by_synchronization}, model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, util::{parse_ctl::parser::BuiltinPetriCtlParser, vizualisation::{kripke_viz::PetriKripkeVisualizer, petri_viz::petri_repr}}};
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+use map_macro::{btree_map, hash_map, hash_set};
+
+use citreelo::util::viz_kripke::KripkeStructureGraphvizDrawer;
+
+use citreelo::solve::is_ctl_formula_sat;
+use citreelo::parser::CtlFormulaParser;
+
+
+
+
+fn tool_test_composition_mc(
+    title : &str,
+    pn : &PetriNet,
+    im : Marking,
+    expected_num_states : usize,
+    formulaes : &HashMap<&'static str, bool>
+) {
+    let folder_name = "test_compositions";
+    let _ = std::fs::create_dir(folder_name);
+    {
+        let gv = petri_repr(pn,&Some(im.clone()));
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_1net", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    let ctl_parser = BuiltinPetriCtlParser::from_net(pn).unwrap();
+    let kripke = petri_to_kripke(
+        pn,
+        im,
+        &PetriKripkeStateProducer::new(hash_set! {}),
+        &PetriKripkeGenerationSafenessRequirement::No
+    ).unwrap();
+    {
+        let gv = PetriKripkeVisualizer::new(pn).get_kripke_repr(&kripke);
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_2kripke", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    assert_eq!(kripke.states.len(), expected_num_states, "{:} : number of states", title);
+    for (phi_as_str,is_sat) in formulaes {
+        let (_,phi) = ctl_parser.parse_ctl_formula::<nom::error::Error<&str>>(
+            phi_as_str
+        ).unwrap();
+        let result = is_ctl_formula_sat(
+            &kripke,
+            &hash_set! {0},
+            &phi
+        );
+        assert_eq!(result,*is_sat,"{:} : {} -> {} | expected {}", title, phi_as_str, result, is_sat);
+    }
+}
+
+
+fn client_net(name : &str) -> PetriNet {
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new(format!("{}_IDLE", name)))),
+            Some(Arc::new(PetriStateLabel::new(format!("{}_WAIT", name)))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("req".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("resp".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {0=>1}
+            ),
+        ]
+    )
+}
+
+
+fn server_net() -> PetriNet {
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("S_IDLE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("S_BUSY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("S_DONE".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("req".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("work".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("resp".to_string()))),
+                hash_map! {2=>1},
+                hash_map! {0=>1}
+            ),
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_client_server_synchronization() {
+    let client = client_net("C");
+    let server = server_net();
+    let composition = compose_by_synchronization(
+        &[
+            (&client, &Marking::new(btree_map! {0=>1})),
+            (&server, &Marking::new(btree_map! {0=>1})),
+        ],
+        &hash_set! {
+            PetriTransitionLabel::new("req".to_string()),
+            PetriTransitionLabel::new("resp".to_string())
+        }
+    );
+    assert_eq!(composition.petri_net.get_places().len(), 5);
+    assert_eq!(composition.petri_net.get_transitions().len(), 3);
+    assert_eq!(composition.initial_marking, Marking::new(btree_map! {0=>1,2=>1}));
+    assert_eq!(composition.place_origins[3], (1,1));
+    assert_eq!(composition.get_composed_place_id(1, 2), Some(4));
+    assert_eq!(composition.get_composed_place_id(0, 2), None);
+    // the interleaved transition comes first, then the synchronized ones, by label
+    assert_eq!(composition.transition_origins, vec![vec![(1,1)], vec![(0,0),(1,0)], vec![(0,1),(1,2)]]);
+    assert_eq!(composition.get_composed_transition_ids(1, 2), vec![2]);
+    let formulaes = hash_map! {
+        r#"A(G( (tokens-count("C_WAIT")=1) <=> (tokens-count("S_IDLE")=0) ))"# => true,
+        r#"A(G( (tokens-count("S_BUSY")=1) => (!( is-fireable("resp") )) ))"#  => true,
+        r#"A(G( E(F( tokens-count("C_IDLE")=1 )) ))"#                          => true,
+    };
+    tool_test_composition_mc("client_server", &composition.petri_net, composition.initial_marking.clone(), 3, &formulaes);
+}
+
+
+#[test]
+pub fn test_synchronization_with_several_instances() {
+    // two clients sharing a single server
+    // a request of the server synchronizes with a request of either client
+    let client_1 = client_net("C1");
+    let client_2 = client_net("C2");
+    let server = server_net();
+    let composition = compose_by_synchronization(
+        &[
+            (&client_1, &Marking::new(btree_map! {0=>1})),
+            (&client_2, &Marking::new(btree_map! {0=>1})),
+            (&server, &Marking::new(btree_map! {0=>1})),
+        ],
+        &hash_set! {
+            PetriTransitionLabel::new("req".to_string()),
+            PetriTransitionLabel::new("resp".to_string())
+        }
+    );
+    // unless both clients are merged into a single component net, they must synchronize together with the server
+    assert_eq!(composition.petri_net.get_transitions().len(), 3);
+    assert_eq!(composition.transition_origins[1], vec![(0,0),(1,0),(2,0)]);
+    // as a single component net, each client request is a distinct instance of "req"
+    let clients = compose_by_synchronization(
+        &[
+            (&client_1, &Marking::new(btree_map! {0=>1})),
+            (&client_2, &Marking::new(btree_map! {0=>1})),
+        ],
+        &hash_set! {}
+    );
+    let composition = compose_by_synchronization(
+        &[
+            (&clients.petri_net, &clients.initial_marking),
+            (&server, &Marking::new(btree_map! {0=>1})),
+        ],
+        &hash_set! {
+            PetriTransitionLabel::new("req".to_string()),
+            PetriTransitionLabel::new("resp".to_string())
+        }
+    );
+    assert_eq!(composition.petri_net.get_transitions().len(), 5);
+    assert_eq!(composition.transition_origins[1], vec![(0,0),(1,0)]);
+    assert_eq!(composition.transition_origins[2], vec![(0,2),(1,0)]);
+    let formulaes = hash_map! {
+        r#"A(G( !( (tokens-count("C1_WAIT")=1) & (tokens-count("C2_WAIT")=1) ) ))"# => true,
+        r#"E(F( tokens-count("C2_WAIT")=1 ))"#                                     => true,
+    };
+    tool_test_composition_mc("two_clients", &composition.petri_net, composition.initial_marking.clone(), 5, &formulaes);
+}
+
+
+fn mutex_process_net(name : &str) -> PetriNet {
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new(format!("{}_IDLE", name)))),
+            Some(Arc::new(PetriStateLabel::new(format!("{}_CS", name)))),
+            Some(Arc::new(PetriStateLabel::new("MUTEX".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new(format!("enter_{}", name)))),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new(format!("leave_{}", name)))),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_fusion_of_interface_places() {
+    let process_a = mutex_process_net("A");
+    let process_b = mutex_process_net("B");
+    let im = Marking::new(btree_map! {0=>1,2=>1});
+    let composition = compose_by_place_fusion(
+        &[
+            (&process_a, &im),
+            (&process_b, &im),
+        ],
+        &hash_set! {PetriStateLabel::new("MUTEX".to_string())}
+    );
+    assert_eq!(composition.petri_net.get_places().len(), 5);
+    assert_eq!(composition.petri_net.get_transitions().len(), 4);
+    // the shared lock is not counted twice
+    assert_eq!(composition.initial_marking, Marking::new(btree_map! {0=>1,2=>1,3=>1}));
+    assert_eq!(composition.place_origins[2], vec![(0,2),(1,2)]);
+    assert_eq!(composition.component_place_ids[1], vec![3,4,2]);
+    assert_eq!(composition.get_composed_transition_id(1, 0), Some(2));
+    let formulaes = hash_map! {
+        r#"A(G( !( (tokens-count("A_CS")=1) & (tokens-count("B_CS")=1) ) ))"# => true,
+        r#"A(G( (tokens-count("A_CS")=1) => (!( is-fireable("enter_B") )) ))"# => true,
+        r#"E(F( tokens-count("B_CS")=1 ))"#                                  => true,
+    };
+    tool_test_composition_mc("mutex_fusion", &composition.petri_net, composition.initial_marking.clone(), 3, &formulaes);
+    // formulas written against a component net can be checked on the composed net
+    let kripke = petri_to_kripke(
+        &composition.petri_net,
+        composition.initial_marking.clone(),
+        &PetriKripkeStateProducer::new(hash_set! {}),
+        &PetriKripkeGenerationSafenessRequirement::No
+    ).unwrap();
+    let component_parser = BuiltinPetriCtlParser::from_net(&process_b).unwrap();
+    let component_formulaes = hash_map! {
+        r#"A(G( (tokens-count("B_CS")=1) => (tokens-count("MUTEX")=0) ))"# => true,
+        r#"A(G( (tokens-count("MUTEX")=1) => (is-fireable("enter_B")) ))"#  => true,
+        r#"A(G( tokens-count("B_IDLE")=1 ))"#                              => false,
+    };
+    for (phi_as_str,is_sat) in component_formulaes {
+        let (_,phi) = component_parser.parse_ctl_formula::<nom::error::Error<&str>>(
+            phi_as_str
+        ).unwrap();
+        let retargeted_phi = composition.retarget_formula(1, &phi).unwrap();
+        let result = is_ctl_formula_sat(
+            &kripke,
+            &hash_set! {0},
+            &retargeted_phi
+        );
+        assert_eq!(result,is_sat,"retargeted : {} -> {} | expected {}", phi_as_str, result, is_sat);
+    }
+}
@@ -0,0 +1,229 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, omega::OmegaTokens, transition::PetriTransition}, model_checking::{backward_coverability::{check_coverability, PetriCoverabilityVerdict}, coverability::{petri_to_coverability_graph, PetriCoverabilityGenerationError}}};
+use map_macro::{btree_map, btree_set, hash_map};
+
+
+
+
+#[test]
+pub fn test_unbounded_producer_consumer() {
+    // a producer which puts tokens in a buffer from which a consumer takes them
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("READY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DONE".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("produce".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("consume".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("stop".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {}
+            )
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let graph = petri_to_coverability_graph(&pn, &im).unwrap();
+    assert!(!graph.is_bounded());
+    assert_eq!(graph.get_unbounded_places(), btree_set! {1,2});
+    assert_eq!(graph.get_place_bound(&0), Some(1));
+    assert_eq!(graph.get_place_bound(&1), None);
+    assert!(graph.can_place_reach(1, 1000));
+    assert!(graph.can_place_reach(2, 1000));
+    assert!(!graph.can_place_reach(0, 2));
+    assert!(graph.is_coverable(&Marking::new(btree_map! {0=>1,1=>5,2=>3})));
+    assert!(!graph.is_coverable(&Marking::new(btree_map! {0=>2})));
+    assert_eq!(graph.markings[0].get_tokens_at_place(&0), OmegaTokens::Finite(1));
+    // each node has an outgoing edge for each transition which is enabled in it
+    for (node_id,marking) in graph.markings.iter().enumerate() {
+        let num_enabled = pn.get_transitions().iter()
+            .filter(|transition| transition.iter_preset_tokens().all(|(place_id,req)| marking.get_tokens_at_place(place_id).covers(*req)))
+            .count();
+        assert_eq!(graph.edges[node_id].len(), num_enabled);
+    }
+}
+
+
+#[test]
+pub fn test_bounded_net_coverability_graph_is_reachability_graph() {
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {0=>1,2=>1}, hash_map! {1=>1}),
+            PetriTransition::new(None, hash_map! {3=>1,2=>1}, hash_map! {4=>1}),
+            PetriTransition::new(None, hash_map! {1=>1}, hash_map! {0=>1,2=>1}),
+            PetriTransition::new(None, hash_map! {4=>1}, hash_map! {3=>1,2=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    let graph = petri_to_coverability_graph(&pn, &im).unwrap();
+    assert!(graph.is_bounded());
+    assert_eq!(graph.markings.len(), 3);
+    assert_eq!(graph.get_place_bound(&2), Some(1));
+    assert!(!graph.is_coverable(&Marking::new(btree_map! {1=>1,4=>1})));
+}
+
+
+#[test]
+pub fn test_non_monotonic_nets_are_rejected() {
+    let im = Marking::new(btree_map! {0=>1});
+    let inhibited = PetriNet::new(
+        vec![None, None],
+        vec![
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1}),
+            PetriTransition::new(None, hash_map! {}, hash_map! {0=>1}).with_inhibitor_tokens(hash_map! {1=>1})
+        ]
+    );
+    assert_eq!(
+        petri_to_coverability_graph(&inhibited, &im).err(),
+        Some(PetriCoverabilityGenerationError::InhibitorArc { transition_id: 1 })
+    );
+    let capacitated = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("READY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DONE".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("produce".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("consume".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("stop".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {}
+            )
+        ]
+    ).with_place_capacities(hash_map! {1=>3});
+    assert_eq!(
+        petri_to_coverability_graph(&capacitated, &im).err(),
+        Some(PetriCoverabilityGenerationError::PlaceCapacity { place_id: 1 })
+    );
+    // once capacities are compiled into complementary places, the net is bounded
+    let (compiled, compiled_im, _) = capacitated.compile_capacities_into_complementary_places(&im);
+    let graph = petri_to_coverability_graph(&compiled, &compiled_im).unwrap();
+    assert_eq!(graph.get_place_bound(&1), Some(3));
+}
+
+
+fn tool_test_backward_coverability(
+    title : &str,
+    pn : &PetriNet,
+    im : &Marking,
+    target : &Marking,
+    expected_coverable : bool
+) {
+    match check_coverability(pn, im, target).unwrap() {
+        PetriCoverabilityVerdict::NotCoverable => {
+            assert!(!expected_coverable, "{:} : expected coverable", title);
+        },
+        PetriCoverabilityVerdict::Coverable { firing_sequence } => {
+            assert!(expected_coverable, "{:} : expected not coverable", title);
+            // replaying the witness must lead to a marking which covers the target
+            let mut marking = im.clone();
+            for transition_id in firing_sequence {
+                marking = pn.get_transitions()[transition_id].try_fire(pn.get_places().len(), &marking).unwrap();
+            }
+            assert!(marking.covers(target), "{:} : witness does not cover the target", title);
+        }
+    }
+    // the forward analysis must agree
+    let graph = petri_to_coverability_graph(pn, im).unwrap();
+    assert_eq!(graph.is_coverable(target), expected_coverable, "{:} : coverability graph", title);
+}
+
+
+#[test]
+pub fn test_backward_coverability_producer_consumer() {
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("READY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DONE".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("produce".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("consume".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("stop".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {}
+            )
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    tool_test_backward_coverability("buffer_and_done", &pn, &im, &Marking::new(btree_map! {0=>1,1=>3,2=>2}), true);
+    tool_test_backward_coverability("two_ready", &pn, &im, &Marking::new(btree_map! {0=>2}), false);
+    tool_test_backward_coverability("empty_target", &pn, &im, &Marking::new(btree_map! {}), true);
+    let stopped = Marking::new(btree_map! {});
+    tool_test_backward_coverability("stopped", &pn, &stopped, &Marking::new(btree_map! {1=>1}), false);
+}
+
+
+#[test]
+pub fn test_backward_coverability_mutual_exclusion() {
+    // an unbounded pool of clients, each of which may enter the critical section when it holds the lock
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("IDLE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CRIT".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("LOCK".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {}, hash_map! {0=>1}),
+            PetriTransition::new(None, hash_map! {0=>1,2=>1}, hash_map! {1=>1}),
+            PetriTransition::new(None, hash_map! {1=>1}, hash_map! {0=>1,2=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {2=>1});
+    tool_test_backward_coverability("two_in_critical_section", &pn, &im, &Marking::new(btree_map! {1=>2}), false);
+    tool_test_backward_coverability("crowded", &pn, &im, &Marking::new(btree_map! {0=>4,1=>1}), true);
+}
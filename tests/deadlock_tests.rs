@@ -0,0 +1,122 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::{deadlock::find_deadlocks, to_kripke::{PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement}}};
+use map_macro::{btree_map, hash_map};
+
+
+
+
+fn label(name : &str) -> Option<Arc<PetriTransitionLabel>> {
+    Some(Arc::new(PetriTransitionLabel::new(name.to_string())))
+}
+
+
+/// two processes which take two locks in opposite orders
+fn opposite_locks_net() -> PetriNet {
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_IDLE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_HAS_1".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_HAS_BOTH".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("L1".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("L2".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_IDLE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_HAS_2".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_HAS_BOTH".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(label("a_take_1"), hash_map! {0=>1,3=>1}, hash_map! {1=>1}),
+            PetriTransition::new(label("a_take_2"), hash_map! {1=>1,4=>1}, hash_map! {2=>1}),
+            PetriTransition::new(label("a_release"), hash_map! {2=>1}, hash_map! {0=>1,3=>1,4=>1}),
+            PetriTransition::new(label("b_take_2"), hash_map! {5=>1,4=>1}, hash_map! {6=>1}),
+            PetriTransition::new(label("b_take_1"), hash_map! {6=>1,3=>1}, hash_map! {7=>1}),
+            PetriTransition::new(label("b_release"), hash_map! {7=>1}, hash_map! {5=>1,3=>1,4=>1})
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_shortest_deadlock_witness() {
+    let pn = opposite_locks_net();
+    let im = Marking::new(btree_map! {0=>1,3=>1,4=>1,5=>1});
+    let witnesses = find_deadlocks(&pn, im.clone(), true, &PetriKripkeGenerationSafenessRequirement::KSafeness(1)).unwrap();
+    assert_eq!(witnesses.len(), 1);
+    let witness = &witnesses[0];
+    assert_eq!(witness.firing_sequence, vec![0,3]);
+    assert_eq!(witness.repr_with_labels(&pn), "a_take_1.b_take_2");
+    assert_eq!(witness.get_labelled_firing_sequence(&pn), vec![label("a_take_1"),label("b_take_2")]);
+    assert_eq!(witness.markings.len(), 3);
+    assert_eq!(witness.markings[0], im);
+    assert_eq!(*witness.get_dead_marking(), Marking::new(btree_map! {1=>1,6=>1}));
+    assert!(pn.get_fireable_transitions(witness.get_dead_marking()).is_empty());
+}
+
+
+#[test]
+pub fn test_all_deadlocks_by_increasing_distance() {
+    // from S, either "quick" leads to a dead marking, or "slow" then "end" lead to another one
+    // and "loop" may fire forever
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("S".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("Q".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("M".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("E".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(label("slow"), hash_map! {0=>1}, hash_map! {2=>1}),
+            PetriTransition::new(label("end"), hash_map! {2=>1}, hash_map! {3=>1}),
+            PetriTransition::new(label("quick"), hash_map! {0=>1}, hash_map! {1=>1}),
+            PetriTransition::new(label("loop"), hash_map! {0=>1}, hash_map! {4=>1}),
+            PetriTransition::new(None, hash_map! {4=>1}, hash_map! {0=>1}),
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let witnesses = find_deadlocks(&pn, im, false, &PetriKripkeGenerationSafenessRequirement::No).unwrap();
+    let reprs : Vec<String> = witnesses.iter().map(|witness| witness.repr_with_labels(&pn)).collect();
+    assert_eq!(reprs, vec!["quick".to_string(), "slow.end".to_string()]);
+}
+
+
+#[test]
+pub fn test_deadlock_free_and_unbounded_nets() {
+    let lock_net = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(label("lock"), hash_map! {0=>1}, hash_map! {1=>1}),
+            PetriTransition::new(label("unlock"), hash_map! {1=>1}, hash_map! {0=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let witnesses = find_deadlocks(&lock_net, im.clone(), true, &PetriKripkeGenerationSafenessRequirement::No).unwrap();
+    assert!(witnesses.is_empty());
+    let unbounded = PetriNet::new(
+        vec![None, None],
+        vec![PetriTransition::new(None, hash_map! {0=>1}, hash_map! {0=>1,1=>1})]
+    );
+    assert_eq!(
+        find_deadlocks(&unbounded, im, true, &PetriKripkeGenerationSafenessRequirement::KSafeness(3)).err(),
+        Some(PetriKripkeGenerationError::KSafenessViolation { place_id: 1, transition_id: 0 })
+    );
+}
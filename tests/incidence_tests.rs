@@ -0,0 +1,110 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::model::{incidence::PetriIncidenceMatrices, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition};
+use map_macro::{btree_map, hash_map, hash_set};
+
+
+
+
+#[test]
+pub fn test_incidence_matrices() {
+    let mut pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("READY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CONSUMED".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("produce".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>2}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("consume".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ).with_read_tokens(hash_map! {0=>1}),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("flush".to_string()))),
+                hash_map! {},
+                hash_map! {}
+            ).with_reset_places(hash_set! {2}),
+        ]
+    );
+    let matrices = PetriIncidenceMatrices::from_net(&pn);
+    assert_eq!(matrices.pre.num_rows(), 3);
+    assert_eq!(matrices.pre.num_columns(), 3);
+    assert_eq!(matrices.pre.iter_entries().collect::<Vec<_>>(), vec![(0,0,1),(1,1,1)]);
+    assert_eq!(matrices.post.iter_entries().collect::<Vec<_>>(), vec![(0,0,1),(1,0,2),(2,1,1)]);
+    // the self-loop on READY cancels out, read and reset arcs are not represented
+    assert_eq!(matrices.incidence.iter_entries().collect::<Vec<_>>(), vec![(1,0,2),(1,1,-1),(2,1,1)]);
+    assert_eq!(matrices.incidence.get(0, 0), 0);
+    assert_eq!(matrices.incidence.iter_row(1).map(|(column,value)| (*column,*value)).collect::<Vec<_>>(), vec![(0,2),(1,-1)]);
+    assert_eq!(matrices.incidence.iter_column(2).count(), 0);
+    // rows and columns map to stable identifiers
+    pn.remove_transition(0);
+    let matrices = PetriIncidenceMatrices::from_net(&pn);
+    let consume_id = pn.get_transition_stable_id(0).unwrap();
+    assert_eq!(matrices.column_transitions[0], consume_id);
+    assert_eq!(matrices.get_column(consume_id), Some(0));
+    assert_eq!(matrices.get_row(pn.get_place_stable_id(2).unwrap()), Some(2));
+    assert_eq!(matrices.incidence.get(1, 0), -1);
+}
+
+
+#[test]
+pub fn test_state_equation() {
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("READY".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CONSUMED".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("produce".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>2}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("consume".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ).with_read_tokens(hash_map! {0=>1}),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("flush".to_string()))),
+                hash_map! {},
+                hash_map! {}
+            ).with_reset_places(hash_set! {2}),
+        ]
+    );
+    let matrices = PetriIncidenceMatrices::from_net(&pn);
+    let im = Marking::new(btree_map! {0=>1});
+    assert_eq!(matrices.get_parikh_vector_effect(&hash_map! {0=>2,1=>3}), vec![0,1,3]);
+    // the marking reached by a firing sequence is that given by the state equation
+    let mut marking = im.clone();
+    for transition_id in [0,1,0,1,1] {
+        marking = pn.get_transitions()[transition_id].try_fire(pn.get_places().len(), &marking).unwrap();
+    }
+    assert_eq!(matrices.apply_state_equation(&im, &hash_map! {0=>2,1=>3}), Some(marking));
+    // no firing sequence may consume more tokens than have been produced
+    assert_eq!(matrices.apply_state_equation(&im, &hash_map! {0=>1,1=>3}), None);
+    assert_eq!(matrices.apply_state_equation(&im, &hash_map! {}), Some(im));
+}
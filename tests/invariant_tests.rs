@@ -0,0 +1,224 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, structural::{p_invariants::{compute_place_invariants, compute_place_invariants_with_token_sums, is_covered_by_place_invariants, PetriPlaceInvariant}, t_invariants::{compute_transition_invariants, is_covered_by_transition_invariants, PetriTransitionInvariant}}};
+use map_macro::{btree_map, hash_map, hash_set};
+
+
+
+
+#[test]
+pub fn test_place_invariants_lock_unlock() {
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            )
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    let invariants = compute_place_invariants_with_token_sums(&pn, &im);
+    assert_eq!(
+        invariants,
+        vec![
+            (PetriPlaceInvariant::new(btree_map! {0=>1,1=>1}), 1),
+            (PetriPlaceInvariant::new(btree_map! {1=>1,2=>1,4=>1}), 1),
+            (PetriPlaceInvariant::new(btree_map! {3=>1,4=>1}), 1),
+        ]
+    );
+    assert!(is_covered_by_place_invariants(&pn));
+    // the token sums hold in the reachable markings
+    let mut marking = im.clone();
+    for transition_id in [0,2,1] {
+        marking = pn.get_transitions()[transition_id].try_fire(pn.get_places().len(), &marking).unwrap();
+        for (invariant,token_sum) in &invariants {
+            assert_eq!(invariant.get_token_sum(&marking), *token_sum);
+        }
+    }
+    assert_eq!(invariants[1].0.get_place_bound(&2, 1), Some(1));
+}
+
+
+#[test]
+pub fn test_place_invariants_weights_and_coverage() {
+    // two tokens of A are assembled into one token of B, which may be duplicated into an unbounded place C
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("C".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("D".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("E".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {0=>2},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {1=>1},
+                hash_map! {0=>2}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {},
+                hash_map! {2=>1}
+            ).with_read_tokens(hash_map! {1=>1}),
+            // tokens move from D to E, which may be reset
+            PetriTransition::new(
+                None,
+                hash_map! {3=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {},
+                hash_map! {}
+            ).with_reset_places(hash_set! {4}),
+        ]
+    );
+    assert_eq!(compute_place_invariants(&pn), vec![PetriPlaceInvariant::new(btree_map! {0=>1,1=>2})]);
+    assert!(!is_covered_by_place_invariants(&pn));
+    let im = Marking::new(btree_map! {0=>4});
+    assert_eq!(compute_place_invariants_with_token_sums(&pn, &im)[0].1, 4);
+}
+
+
+#[test]
+pub fn test_transition_invariants_lock_unlock() {
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            )
+        ]
+    );
+    let invariants = compute_transition_invariants(&pn);
+    assert_eq!(
+        invariants,
+        vec![
+            PetriTransitionInvariant::new(btree_map! {0=>1,2=>1}),
+            PetriTransitionInvariant::new(btree_map! {1=>1,3=>1}),
+        ]
+    );
+    assert!(is_covered_by_transition_invariants(&pn));
+    assert_eq!(invariants[0].repr_with_labels(&pn), "lock + unlock");
+    let labelled = invariants[1].get_labelled_firing_counts(&pn);
+    assert_eq!(labelled.len(), 2);
+    assert_eq!(labelled[0].0.as_ref().unwrap().label, "lock");
+    // firing an invariant leads back to the initial marking
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    let mut marking = im.clone();
+    for transition_id in [1,3] {
+        marking = pn.get_transitions()[transition_id].try_fire(pn.get_places().len(), &marking).unwrap();
+    }
+    assert_eq!(marking, im);
+}
+
+
+#[test]
+pub fn test_transition_invariants_weights_and_coverage() {
+    // "split" produces two tokens that are moved back one at a time, so that the surplus must be drained
+    // the transition with a reset arc does not belong to any invariant
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("split".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {1=>2}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {1=>1},
+                hash_map! {0=>1}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("drain".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {}
+            ),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("reset".to_string()))),
+                hash_map! {},
+                hash_map! {0=>1}
+            ).with_reset_places(hash_set! {1}),
+        ]
+    );
+    let invariants = compute_transition_invariants(&pn);
+    assert_eq!(invariants, vec![PetriTransitionInvariant::new(btree_map! {0=>1,1=>2,2=>1})]);
+    assert_eq!(invariants[0].repr_with_labels(&pn), "split + 2*t#1 + drain");
+    assert!(!is_covered_by_transition_invariants(&pn));
+}
@@ -16,7 +16,7 @@ limitations under the License.
 
 
 
-use std::rc::Rc;
+use std::sync::Arc;
 
 use petricheck::{model::{label::PetriTransitionLabel, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::to_kripke::{PetriKripkeGenerationError, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, util::vizualisation::petri_viz::petri_repr};
 use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
@@ -70,7 +70,7 @@ fn tool_test_ksafeness_violation_detection(
 
 #[test]
 pub fn test_not_1safe1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None
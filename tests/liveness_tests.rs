@@ -0,0 +1,114 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::{liveness::{compute_liveness_levels, PetriLivenessLevel}, to_kripke::PetriKripkeGenerationSafenessRequirement}};
+use map_macro::{btree_map, hash_map};
+
+
+
+
+fn label(name : &str) -> Arc<PetriTransitionLabel> {
+    Arc::new(PetriTransitionLabel::new(name.to_string()))
+}
+
+fn labels(names : &[&str]) -> BTreeSet<Arc<PetriTransitionLabel>> {
+    names.iter().map(|name| label(name)).collect()
+}
+
+
+#[test]
+pub fn test_lock_unlock_is_live() {
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(Some(label("lock")), hash_map! {0=>1,2=>1}, hash_map! {1=>1}),
+            PetriTransition::new(Some(label("lock")), hash_map! {3=>1,2=>1}, hash_map! {4=>1}),
+            PetriTransition::new(Some(label("unlock")), hash_map! {1=>1}, hash_map! {0=>1,2=>1}),
+            PetriTransition::new(Some(label("unlock")), hash_map! {4=>1}, hash_map! {3=>1,2=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    let analysis = compute_liveness_levels(&pn, im, &PetriKripkeGenerationSafenessRequirement::KSafeness(1)).unwrap();
+    assert!(analysis.is_live());
+    assert_eq!(analysis.levels, vec![PetriLivenessLevel::Live; 4]);
+    assert_eq!(analysis.live_labels, labels(&["lock","unlock"]));
+}
+
+
+#[test]
+pub fn test_liveness_levels() {
+    // "start" fires once, then "work" may fire any number of times until "stop" leads to a deadlock
+    // "never" requires a token in a place which is never marked
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("S".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("D".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("E".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(Some(label("start")), hash_map! {0=>1}, hash_map! {1=>1}),
+            PetriTransition::new(Some(label("work")), hash_map! {1=>1}, hash_map! {1=>1}),
+            PetriTransition::new(Some(label("stop")), hash_map! {1=>1}, hash_map! {2=>1}),
+            PetriTransition::new(Some(label("never")), hash_map! {3=>1}, hash_map! {0=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let analysis = compute_liveness_levels(&pn, im, &PetriKripkeGenerationSafenessRequirement::KSafeness(1)).unwrap();
+    assert_eq!(
+        analysis.levels, 
+        vec![PetriLivenessLevel::L1Live, PetriLivenessLevel::L3Live, PetriLivenessLevel::L1Live, PetriLivenessLevel::Dead]
+    );
+    assert_eq!(analysis.get_transition_level(1).to_string(), "L3");
+    assert!(!analysis.is_quasi_live());
+    assert_eq!(analysis.quasi_live_labels, labels(&["start","work","stop"]));
+    assert!(analysis.live_labels.is_empty());
+}
+
+
+#[test]
+pub fn test_label_liveness() {
+    // a choice leads to one of two loops, each made of a distinct transition labelled "work"
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("S".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(Some(label("choose_a")), hash_map! {0=>1}, hash_map! {1=>1}),
+            PetriTransition::new(Some(label("choose_b")), hash_map! {0=>1}, hash_map! {2=>1}),
+            PetriTransition::new(Some(label("work")), hash_map! {1=>1}, hash_map! {1=>1}),
+            PetriTransition::new(Some(label("work")), hash_map! {2=>1}, hash_map! {2=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let analysis = compute_liveness_levels(&pn, im, &PetriKripkeGenerationSafenessRequirement::KSafeness(1)).unwrap();
+    assert_eq!(
+        analysis.levels, 
+        vec![PetriLivenessLevel::L1Live, PetriLivenessLevel::L1Live, PetriLivenessLevel::L3Live, PetriLivenessLevel::L3Live]
+    );
+    assert!(!analysis.is_live());
+    assert_eq!(analysis.live_labels, labels(&["work"]));
+}
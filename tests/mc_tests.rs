@@ -14,7 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{collections::{HashMap, HashSet}, rc::Rc};
+use std::{collections::{HashMap, HashSet}, sync::Arc};
 
 use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::to_kripke::{PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer, petri_to_kripke}, util::{parse_ctl::parser::BuiltinPetriCtlParser, vizualisation::{kripke_viz::PetriKripkeVisualizer, petri_viz::petri_repr}}};
 use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
@@ -87,15 +87,15 @@ fn tool_test_pn_mc(
 
 #[test]
 pub fn test_lock_unlock() {
-    let lock_tr = Rc::new(PetriTransitionLabel::new("lock".to_string()));
-    let unlock_tr = Rc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
     let pn = PetriNet::new(
         vec![
-            Some(Rc::new(PetriStateLabel::new("A_U".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("A_L".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("CTL".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("B_U".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("B_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
         ], 
         vec![
             PetriTransition::new(
@@ -181,3 +181,163 @@ pub fn test_lock_unlock() {
 
 
 
+
+
+#[test]
+pub fn test_inhibitor_priority() {
+    let take_a_tr = Arc::new(PetriTransitionLabel::new("take_a".to_string()));
+    let take_b_tr = Arc::new(PetriTransitionLabel::new("take_b".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DA".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DB".to_string()))),
+        ], 
+        vec![
+            PetriTransition::new(
+                Some(take_a_tr.clone()),
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ),
+            // "take_b" may only fire once "A" is empty
+            PetriTransition::new(
+                Some(take_b_tr.clone()),
+                hash_map! {2=>1},
+                hash_map! {3=>1}
+            ).with_inhibitor_tokens(hash_map! {0=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,2=>1});
+    let initial_states = hash_set! {0};
+    
+    let formulae = hash_map!{
+        // at initial state
+        r#"is-fireable("take_a")"# => true,  
+        r#"is-fireable("take_b")"# => false,  
+        // safety properties
+        r#"A(G( (tokens-count("A")>0) => (!(is-fireable("take_b"))) ))"#   => true,
+        r#"A(G( (tokens-count("DB")>0) => (tokens-count("DA")>0) ))"#      => true,
+        r#"A(G( (tokens-count("DA")>0) => (tokens-count("DB")>0) ))"#      => false,
+        // reachability
+        r#"E(F( tokens-count("DB")=1 ))"#                                  => true,
+    };
+
+    tool_test_pn_mc(
+        "inhibitor_priority",
+        pn,
+        im,
+        initial_states,
+        vec![
+            ("no_prev_labs",hash_set!{},formulae),
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_reset_flush() {
+    let push_tr = Arc::new(PetriTransitionLabel::new("push".to_string()));
+    let flush_tr = Arc::new(PetriTransitionLabel::new("flush".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF_A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("BUF_B".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("ERROR".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("HANDLED".to_string()))),
+        ], 
+        vec![
+            PetriTransition::new(
+                Some(push_tr.clone()),
+                hash_map! {0=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(push_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {3=>1}
+            ),
+            // "flush" empties the buffer, whatever its content
+            PetriTransition::new(
+                Some(flush_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {5=>1}
+            ).with_reset_places(hash_set! {2,3})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,1=>1,4=>1});
+    let initial_states = hash_set! {0};
+    
+    let formulae_no_prev_labs = hash_map!{
+        r#"E(F( (tokens-count("BUF_A")=1) & (tokens-count("BUF_B")=1) ))"#   => true,
+        r#"E(F( (tokens-count("HANDLED")=1) & (tokens-count("BUF_A")=1) ))"# => true,
+        r#"A(F( tokens-count("HANDLED")=1 ))"#                               => true,
+        r#"A(G( (tokens-count("A")=0) => (tokens-count("BUF_A")=1) ))"#      => false,
+    };
+    let formulae_with_prev_labs = hash_map!{
+        r#"A(G( (is-previous("flush")) => ((tokens-count("BUF_A")=0) & (tokens-count("BUF_B")=0)) ))"# => true,
+    };
+
+    tool_test_pn_mc(
+        "reset_flush",
+        pn,
+        im,
+        initial_states,
+        vec![
+            ("no_prev_labs",hash_set!{},formulae_no_prev_labs),
+            ("with_prev_labs",hash_set!{(*flush_tr).clone()},formulae_with_prev_labs),
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_priorities() {
+    let urgent_tr = Arc::new(PetriTransitionLabel::new("urgent".to_string()));
+    let normal_tr = Arc::new(PetriTransitionLabel::new("normal".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("REQ_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("REQ_N".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CPU".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DONE_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("DONE_N".to_string()))),
+        ], 
+        vec![
+            PetriTransition::new(
+                Some(urgent_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {2=>1,3=>1}
+            ).with_priority(1),
+            PetriTransition::new(
+                Some(normal_tr.clone()),
+                hash_map! {1=>1,2=>1},
+                hash_map! {2=>1,4=>1}
+            )
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,1=>1,2=>1});
+    let initial_states = hash_set! {0};
+    
+    let formulae = hash_map!{
+        // at initial state, both are enabled but "normal" is blocked by "urgent"
+        r#"is-fireable("urgent")"# => true,  
+        r#"is-fireable("normal")"# => false,  
+        // the urgent request is always served before the normal one
+        r#"A(G( (tokens-count("DONE_N")>0) => (tokens-count("DONE_U")>0) ))"# => true,
+        r#"E(F( tokens-count("DONE_N")=1 ))"#                                => true,
+        r#"A(G( (is-fireable("normal")) => (tokens-count("REQ_U")=0) ))"#    => true,
+    };
+
+    tool_test_pn_mc(
+        "priorities",
+        pn,
+        im,
+        initial_states,
+        vec![
+            ("no_prev_labs",hash_set!{},formulae),
+        ]
+    )
+}
@@ -14,7 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, sync::Arc};
 
 use petricheck::model::label::{PetriStateLabel, PetriTransitionLabel};
 use petricheck::model::net::PetriNet;
@@ -60,15 +60,15 @@ fn tool_test_parse_ctl(
 
 #[test]
 pub fn test_tokens_count() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
-    let tr_c = Rc::new(PetriTransitionLabel::new("C".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_c = Arc::new(PetriTransitionLabel::new("C".to_string()));
     let mut pn = PetriNet::new(
         vec![
-            Some(Rc::new(PetriStateLabel::new("P1".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("P2".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("P3".to_string()))),
-            Some(Rc::new(PetriStateLabel::new("P4".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("P1".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("P2".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("P3".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("P4".to_string()))),
         ], 
         vec![
             PetriTransition::new(
@@ -0,0 +1,105 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use petricheck::model::marking::Marking;
+use itertools::Itertools;
+use petricheck::colored::unfold::unfold_colored_petri_net;
+use petricheck::util::parse_pnml::build::read_petri_net_from_pnml_file_path;
+use petricheck::util::parse_pnml::error::PnmlParsingError;
+use petricheck::util::parse_pnml::symmetric::read_colored_petri_net_from_pnml_file_path;
+use map_macro::btree_map;
+
+
+
+#[test]
+pub fn test_read_arcs() {
+    let content = read_petri_net_from_pnml_file_path("tests/pnml/read_arcs.pnml").unwrap();
+    let config = content.get_place_index("CONFIG").unwrap();
+    let idle = content.get_place_index("IDLE").unwrap();
+    let done = content.get_place_index("DONE").unwrap();
+    assert_eq!(content.initial_marking, Marking::new(btree_map! {config=>1,idle=>2}));
+    let work = content.petri_net.get_transitions().first().unwrap();
+    assert_eq!(work.iter_preset_tokens().map(|(x,y)|(*x,*y)).collect::<HashMap<usize,u32>>(), HashMap::from([(idle,1)]));
+    assert_eq!(work.iter_postset_tokens().map(|(x,y)|(*x,*y)).collect::<HashMap<usize,u32>>(), HashMap::from([(done,2)]));
+    assert_eq!(work.iter_read_tokens().map(|(x,y)|(*x,*y)).collect::<HashMap<usize,u32>>(), HashMap::from([(config,1)]));
+    // the token in "CONFIG" is tested but not consumed
+    let num_places = content.petri_net.get_places().len();
+    let after_one = work.try_fire(num_places, &content.initial_marking).unwrap();
+    assert_eq!(after_one, Marking::new(btree_map! {config=>1,idle=>1,done=>2}));
+    let after_two = work.try_fire(num_places, &after_one).unwrap();
+    assert_eq!(after_two, Marking::new(btree_map! {config=>1,done=>4}));
+    assert!(work.try_fire(num_places, &after_two).is_none());
+    // without the token in "CONFIG" the transition is not fireable
+    let without_config = Marking::new(btree_map! {idle=>2});
+    assert!(work.try_fire(num_places, &without_config).is_none());
+}
+
+
+#[test]
+pub fn test_place_text_ids_survive_removals() {
+    let mut content = read_petri_net_from_pnml_file_path("tests/pnml/read_arcs.pnml").unwrap();
+    let config = content.get_place_index("CONFIG").unwrap();
+    let done = content.get_place_index("DONE").unwrap();
+    assert!(config < done);
+    // the place is isolated once the only transition is removed
+    content.petri_net.remove_transition(0);
+    content.petri_net.remove_place(config);
+    // the following places are shifted, but can still be found by their PNML ids
+    assert_eq!(content.get_place_index("CONFIG"), None);
+    assert_eq!(content.get_place_index("DONE"), Some(done - 1));
+    assert_eq!(content.petri_net.get_places()[done - 1].as_ref().unwrap().label, "DONE");
+}
+
+
+#[test]
+pub fn test_symmetric_net() {
+    let content = read_colored_petri_net_from_pnml_file_path("tests/pnml/philosophers_colored.pnml").unwrap();
+    let net = &content.colored_net;
+    assert_eq!(net.places.len(), 4);
+    assert_eq!(net.transitions.len(), 2);
+    let fork = *content.place_text_id_to_int_id.get("Fork").unwrap();
+    let waiter = *content.place_text_id_to_int_id.get("Waiter").unwrap();
+    assert_eq!(net.get_domain_size(net.places[fork].domain_id), 3);
+    assert_eq!(net.get_domain_size(net.places[waiter].domain_id), 1);
+    assert_eq!(content.initial_marking.tokens.get(&fork).unwrap().values().sum::<u32>(), 3);
+    assert_eq!(content.initial_marking.tokens.get(&waiter).unwrap().values().sum::<u32>(), 1);
+    // the guard of "take" excludes the third philosopher
+    let unfolding = unfold_colored_petri_net(net, &content.initial_marking).unwrap();
+    assert_eq!(unfolding.petri_net.get_places().len(), 10);
+    assert_eq!(unfolding.petri_net.get_transitions().len(), 5);
+    // philosopher 3 takes its own fork and that of philosopher 1 (cyclic successor)
+    let take = *content.transition_text_id_to_int_id.get("take").unwrap();
+    let release = *content.transition_text_id_to_int_id.get("release").unwrap();
+    let (unfolded_release_id,_) = unfolding.transition_origins.iter()
+        .find_position(|(tr_id,binding)| *tr_id == release && binding.values.get(&0) == Some(&2))
+        .unwrap();
+    let forks : Vec<String> = unfolding.petri_net.get_transitions()[unfolded_release_id].iter_postset_tokens()
+        .map(|(place_id,_)| unfolding.petri_net.get_places()[*place_id].as_ref().unwrap().label.clone())
+        .filter(|name| name.starts_with("Fork"))
+        .sorted()
+        .collect();
+    assert_eq!(forks, vec!["Fork_1".to_string(), "Fork_3".to_string()]);
+    assert!(unfolding.transition_origins.iter().all(|(tr_id,binding)| *tr_id != take || binding.values.get(&0) != Some(&2)));
+}
+
+
+#[test]
+pub fn test_zero_weight_inscription_is_rejected() {
+    let result = read_petri_net_from_pnml_file_path("tests/pnml/zero_weight.pnml");
+    assert!(matches!(result, Err(PnmlParsingError::ZeroWeightInArcInscription)));
+}
@@ -0,0 +1,231 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, stochastic::PetriStochasticTiming, transition::PetriTransition}, model_checking::to_kripke::PetriKripkeGenerationSafenessRequirement, performance::{ctmc::{petri_to_ctmc, PetriCtmc, PetriCtmcGenerationError}, solve::{PetriCtmcSolvingError, PetriPerformanceMeasures}}, util::vizualisation::petri_viz::petri_repr};
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+use map_macro::{btree_map, hash_map};
+
+
+
+
+const TOLERANCE : f64 = 1e-9;
+
+
+fn tool_test_gspn(
+    title : &str,
+    pn : &PetriNet,
+    im : Marking,
+    expected_num_tangible : usize
+) -> PetriCtmc {
+    let folder_name = "test_performance";
+    let _ = std::fs::create_dir(folder_name);
+    {
+        let gv = petri_repr(pn,&Some(im.clone()));
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    let ctmc = petri_to_ctmc(
+        pn,
+        im,
+        &PetriKripkeGenerationSafenessRequirement::KSafeness(1)
+    ).unwrap();
+    assert_eq!(ctmc.tangible_markings.len(), expected_num_tangible, "{:} : number of tangible markings", title);
+    ctmc
+}
+
+fn assert_measures(
+    title : &str,
+    measures : &PetriPerformanceMeasures,
+    expected_tokens : &[f64],
+    expected_throughputs : &[f64]
+) {
+    assert!((measures.marking_probabilities.iter().sum::<f64>() - 1.0).abs() < TOLERANCE, "{:} : probabilities sum", title);
+    for (place_id,expected) in expected_tokens.iter().enumerate() {
+        let got = measures.expected_tokens[place_id];
+        assert!((got - expected).abs() < TOLERANCE, "{:} : tokens at place {} -> {} | expected {}", title, place_id, got, expected);
+    }
+    for (tr_id,expected) in expected_throughputs.iter().enumerate() {
+        let got = measures.throughputs[tr_id];
+        assert!((got - expected).abs() < TOLERANCE, "{:} : throughput of transition {} -> {} | expected {}", title, tr_id, got, expected);
+    }
+}
+
+
+fn on_off_net() -> PetriNet {
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("ON".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("OFF".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("fail".to_string()))),
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ).with_stochastic_timing(PetriStochasticTiming::Exponential(1.0)),
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("repair".to_string()))),
+                hash_map! {1=>1},
+                hash_map! {0=>1}
+            ).with_stochastic_timing(PetriStochasticTiming::Exponential(2.0))
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_on_off_steady_state() {
+    let pn = on_off_net();
+    let ctmc = tool_test_gspn("on_off", &pn, Marking::new(btree_map! {0=>1}), 2);
+    let measures = ctmc.solve_steady_state().unwrap();
+    assert_measures("on_off_steady", &measures, &[2.0/3.0, 1.0/3.0], &[2.0/3.0, 2.0/3.0]);
+}
+
+
+#[test]
+pub fn test_on_off_transient() {
+    let pn = on_off_net();
+    let ctmc = tool_test_gspn("on_off", &pn, Marking::new(btree_map! {0=>1}), 2);
+    // P(ON at t) = 2/3 + 1/3.exp(-3t)
+    let initial = ctmc.solve_transient(0.0).unwrap();
+    assert_measures("on_off_t0", &initial, &[1.0, 0.0], &[1.0, 0.0]);
+    let p_on = 2.0/3.0 + (-1.5_f64).exp()/3.0;
+    let at_half = ctmc.solve_transient(0.5).unwrap();
+    assert_measures("on_off_t0.5", &at_half, &[p_on, 1.0 - p_on], &[p_on, 2.0 * (1.0 - p_on)]);
+    // long horizons are split in several uniformization steps
+    let at_hundred = ctmc.solve_transient(100.0).unwrap();
+    assert_measures("on_off_t100", &at_hundred, &[2.0/3.0, 1.0/3.0], &[2.0/3.0, 2.0/3.0]);
+    // negative or non finite time instants
+    for time in [-1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(ctmc.solve_transient(time).err(), Some(PetriCtmcSolvingError::InvalidTimeInstant));
+    }
+}
+
+
+#[test]
+pub fn test_vanishing_markings_elimination() {
+    // after failing, the kind of failure is chosen immediately (with weights 1 and 3)
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("ON".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CHOICE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("OFF_A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("OFF_B".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ).with_stochastic_timing(PetriStochasticTiming::Immediate(1.0)),
+            PetriTransition::new(
+                None,
+                hash_map! {1=>1},
+                hash_map! {3=>1}
+            ).with_stochastic_timing(PetriStochasticTiming::Immediate(3.0)),
+            PetriTransition::new(
+                None,
+                hash_map! {2=>1},
+                hash_map! {0=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {3=>1},
+                hash_map! {0=>1}
+            ),
+        ]
+    );
+    let ctmc = tool_test_gspn("vanishing_elimination", &pn, Marking::new(btree_map! {0=>1}), 3);
+    let measures = ctmc.solve_steady_state().unwrap();
+    assert_measures(
+        "vanishing_elimination", 
+        &measures, 
+        &[0.5, 0.0, 0.125, 0.375], 
+        &[0.5, 0.125, 0.375, 0.125, 0.375]
+    );
+    // starting from the vanishing marking, the choice is made in zero time
+    let ctmc = tool_test_gspn("vanishing_initial", &pn, Marking::new(btree_map! {1=>1}), 3);
+    let initial = ctmc.solve_transient(0.0).unwrap();
+    assert_measures("vanishing_initial", &initial, &[0.0, 0.0, 0.25, 0.75], &[]);
+}
+
+
+#[test]
+pub fn test_zero_immediate_weights_are_rejected() {
+    // two conflicting immediate transitions with zero weights
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("P".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("Q".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("R".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1})
+                .with_stochastic_timing(PetriStochasticTiming::Immediate(0.0)),
+            PetriTransition::new(None, hash_map! {0=>1}, hash_map! {2=>1})
+                .with_stochastic_timing(PetriStochasticTiming::Immediate(0.0)),
+            PetriTransition::new(None, hash_map! {1=>1}, hash_map! {0=>1}),
+            PetriTransition::new(None, hash_map! {2=>1}, hash_map! {0=>1})
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    assert_eq!(
+        petri_to_ctmc(&pn, im.clone(), &PetriKripkeGenerationSafenessRequirement::KSafeness(1)).err(),
+        Some(PetriCtmcGenerationError::ZeroImmediateWeights { marking: im })
+    );
+}
+
+
+#[test]
+pub fn test_invalid_stochastic_timings_are_rejected() {
+    let im = Marking::new(btree_map! {0=>1});
+    let timings = [
+        (PetriStochasticTiming::Exponential(0.0), true),
+        (PetriStochasticTiming::Exponential(-1.0), true),
+        (PetriStochasticTiming::Exponential(f64::INFINITY), true),
+        (PetriStochasticTiming::Immediate(-0.5), true),
+        (PetriStochasticTiming::Immediate(f64::NAN), true),
+        (PetriStochasticTiming::Exponential(2.5), false),
+    ];
+    for (timing,is_invalid) in timings {
+        let pn = PetriNet::new(
+            vec![
+                Some(Arc::new(PetriStateLabel::new("ON".to_string()))),
+                Some(Arc::new(PetriStateLabel::new("OFF".to_string()))),
+            ],
+            vec![
+                PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1}),
+                PetriTransition::new(None, hash_map! {1=>1}, hash_map! {0=>1}).with_stochastic_timing(timing)
+            ]
+        );
+        let result = petri_to_ctmc(&pn, im.clone(), &PetriKripkeGenerationSafenessRequirement::KSafeness(1));
+        if is_invalid {
+            assert_eq!(result.err(), Some(PetriCtmcGenerationError::InvalidStochasticTiming { transition_id: 1 }), "{}", timing);
+        } else {
+            assert!(result.is_ok(), "{}", timing);
+        }
+    }
+}
@@ -14,9 +14,9 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, sync::Arc};
 
-use petricheck::{model::{label::{PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, reduction::reduce::reduce_petri_net, util::{vizualisation::petri_viz::petri_repr}};
+use petricheck::{model::{ids::{PetriPlaceId, PetriTransitionId}, label::{PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, reduction::reduce::reduce_petri_net, util::{vizualisation::petri_viz::petri_repr}};
 use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
 use map_macro::{btree_map, hash_map};
 
@@ -28,7 +28,7 @@ fn tool_test_pn_reduction(
         title : &str, 
         pn : PetriNet, 
         im : Option<Marking>, 
-        relabelling : HashMap<PetriTransitionLabel, Option<Rc<PetriTransitionLabel>>>,
+        relabelling : HashMap<PetriTransitionLabel, Option<Arc<PetriTransitionLabel>>>,
         expected_reduced_pn : PetriNet,
         expected_reduced_im : Option<Marking>,
         should_not_change_without_relabel : bool
@@ -79,8 +79,8 @@ fn tool_test_pn_reduction(
 
 #[test]
 pub fn test_series_places1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -131,9 +131,9 @@ pub fn test_series_places1() {
 
 #[test]
 pub fn test_series_transitions1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
-    let tr_c = Rc::new(PetriTransitionLabel::new("C".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_c = Arc::new(PetriTransitionLabel::new("C".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -196,9 +196,9 @@ pub fn test_series_transitions1() {
 
 #[test]
 pub fn test_series_transitions2() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
-    let tr_c = Rc::new(PetriTransitionLabel::new("C".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_c = Arc::new(PetriTransitionLabel::new("C".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -262,7 +262,7 @@ pub fn test_series_transitions2() {
 
 #[test]
 pub fn test_self_loop_place1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -307,8 +307,8 @@ pub fn test_self_loop_place1() {
 
 #[test]
 pub fn test_self_loop_place2() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -366,8 +366,8 @@ pub fn test_self_loop_place2() {
 
 #[test]
 pub fn test_self_loop_transition1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None
@@ -416,9 +416,9 @@ pub fn test_self_loop_transition1() {
 
 #[test]
 pub fn test_self_loop_transition2() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
-    let tr_c = Rc::new(PetriTransitionLabel::new("C".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_c = Arc::new(PetriTransitionLabel::new("C".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None
@@ -476,8 +476,8 @@ pub fn test_self_loop_transition2() {
 
 #[test]
 pub fn test_parallel_places1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None,None
@@ -530,9 +530,9 @@ pub fn test_parallel_places1() {
 
 #[test]
 pub fn test_parallel_places2() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
-    let tr_c = Rc::new(PetriTransitionLabel::new("C".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_c = Arc::new(PetriTransitionLabel::new("C".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -596,9 +596,9 @@ pub fn test_parallel_places2() {
 
 #[test]
 pub fn test_parallel_transitions1() {
-    let tr_a = Rc::new(PetriTransitionLabel::new("A".to_string()));
-    let tr_b = Rc::new(PetriTransitionLabel::new("B".to_string()));
-    let tr_c = Rc::new(PetriTransitionLabel::new("C".to_string()));
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let tr_c = Arc::new(PetriTransitionLabel::new("C".to_string()));
     let pn = PetriNet::new(
         vec![
             None,None,None
@@ -663,3 +663,109 @@ pub fn test_parallel_transitions1() {
 }
 
 
+
+
+#[test]
+pub fn test_series_places_blocked_by_inhibitor() {
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            None,None,None
+        ], 
+        vec![
+            PetriTransition::new(
+                Some(tr_a.clone()),
+                hash_map! {0=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(tr_b.clone()),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ).with_inhibitor_tokens(hash_map! {0=>1})
+        ]
+    );
+    let im = Some(Marking::new(btree_map! {0=>1}));
+    let relabelling = hash_map! {(*tr_a).clone()=>None};
+    // the origin place is connected to an inhibitor arc so the series places must not be fused
+    let expected_reduced_pn = PetriNet::new(
+        vec![
+            None,None,None
+        ], 
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {0=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(tr_b.clone()),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            ).with_inhibitor_tokens(hash_map! {0=>1})
+        ]
+    );
+    let expected_reduced_im = im.clone();
+    tool_test_pn_reduction(
+        "series_places_blocked_by_inhibitor",
+        pn,
+        im,
+        relabelling,
+        expected_reduced_pn,
+        expected_reduced_im,
+        true
+    );
+}
+
+
+#[test]
+pub fn test_stable_ids_survive_reduction() {
+    let tr_a = Arc::new(PetriTransitionLabel::new("A".to_string()));
+    let tr_b = Arc::new(PetriTransitionLabel::new("B".to_string()));
+    let mut pn = PetriNet::new(
+        vec![
+            None,None,None
+        ], 
+        vec![
+            PetriTransition::new(
+                Some(tr_a.clone()),
+                hash_map! {0=>1},
+                hash_map! {2=>1}
+            ),
+            PetriTransition::new(
+                Some(tr_b.clone()),
+                hash_map! {1=>1},
+                hash_map! {2=>1}
+            )
+        ]
+    );
+    let mut im = Some(Marking::new(btree_map! {0=>1}));
+    let place_ids : Vec<PetriPlaceId> = (0..3).map(|place_id| pn.get_place_stable_id(place_id).unwrap()).collect();
+    let tr_ids : Vec<PetriTransitionId> = (0..2).map(|tr_id| pn.get_transition_stable_id(tr_id).unwrap()).collect();
+    pn.relabel_transitions(hash_map! {(*tr_a).clone()=>None});
+    reduce_petri_net(&mut pn, &mut im);
+    // the first place is fused into the third one, and the transition between them is removed
+    assert_eq!(pn.get_places().len(), 2);
+    assert_eq!(pn.get_place_index(place_ids[0]), None);
+    assert_eq!(pn.get_place_index(place_ids[1]), Some(0));
+    assert_eq!(pn.get_place_index(place_ids[2]), Some(1));
+    assert_eq!(pn.get_place_stable_id(1), Some(place_ids[2]));
+    assert_eq!(pn.get_transition_index(tr_ids[0]), None);
+    assert_eq!(pn.get_transition_index(tr_ids[1]), Some(0));
+    assert_eq!(pn.get_transition(tr_ids[1]).unwrap().transition_label, Some(tr_b));
+    // added nodes get fresh identifiers, which are never reused
+    let new_place = pn.add_place(None);
+    let new_place_id = pn.get_place_stable_id(new_place).unwrap();
+    assert!(!place_ids.contains(&new_place_id));
+    assert_eq!(pn.get_place_index(new_place_id), Some(2));
+    // and are allocated in increasing order
+    let other_place = pn.add_place(None);
+    let other_place_id = pn.get_place_stable_id(other_place).unwrap();
+    assert!(other_place_id > new_place_id);
+    assert_eq!(pn.get_place_index(other_place_id), Some(3));
+    pn.remove_place(new_place);
+    assert_eq!(pn.get_place_index(new_place_id), None);
+    assert_eq!(pn.get_place_index(place_ids[2]), Some(1));
+    assert_eq!(pn.get_place_index(other_place_id), Some(2));
+}
@@ -0,0 +1,187 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use petricheck::{model::{interval::PetriFiringInterval, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, util::{parse_ctl::parser::BuiltinPetriCtlParser, parse_pnml::build::{read_petri_net_from_pnml_file_path, PnmlFileContent}, serialization::{error::PetriSerializationError, kripke::SerializableKripkeStructure}}};
+use map_macro::{btree_map, hash_map, hash_set};
+
+use citreelo::solve::is_ctl_formula_sat;
+use citreelo::parser::CtlFormulaParser;
+
+
+
+
+fn lock_unlock_net() -> PetriNet {
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let mut pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+            None,
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {},
+                hash_map! {}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ).with_priority(1),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            ).with_inhibitor_tokens(hash_map! {5=>1}),
+            // a distinct label object with the same content
+            PetriTransition::new(
+                Some(Arc::new(PetriTransitionLabel::new("unlock".to_string()))),
+                hash_map! {5=>1},
+                hash_map! {}
+            ),
+        ]
+    ).with_place_capacities(hash_map! {2=>1});
+    // so that the stable identifiers of the transitions differ from their indices
+    pn.remove_transition(0);
+    pn
+}
+
+
+#[test]
+pub fn test_net_round_trip() {
+    let pn = lock_unlock_net();
+    let json = serde_json::to_string(&pn).unwrap();
+    let deserialized : PetriNet = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, pn);
+    for transition_id in 0..pn.get_transitions().len() {
+        assert_eq!(deserialized.get_transition_stable_id(transition_id), pn.get_transition_stable_id(transition_id));
+    }
+    // shared labels remain shared and distinct label objects remain distinct
+    let label_of = |transition_id : usize| deserialized.get_transitions()[transition_id].transition_label.clone().unwrap();
+    assert!(Arc::ptr_eq(&label_of(0), &label_of(1)));
+    assert!(Arc::ptr_eq(&label_of(2), &label_of(3)));
+    assert!(!Arc::ptr_eq(&label_of(3), &label_of(4)));
+    assert_eq!(label_of(3), label_of(4));
+    // markings
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    let json = serde_json::to_string(&im).unwrap();
+    assert_eq!(serde_json::from_str::<Marking>(&json).unwrap(), im);
+}
+
+
+#[test]
+pub fn test_kripke_round_trip() {
+    let pn = lock_unlock_net();
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    let kripke = petri_to_kripke(
+        &pn,
+        im,
+        &PetriKripkeStateProducer::new(hash_set! {PetriTransitionLabel::new("lock".to_string())}),
+        &PetriKripkeGenerationSafenessRequirement::KSafeness(1)
+    ).unwrap();
+    let net_json = serde_json::to_string(&pn).unwrap();
+    let kripke_json = serde_json::to_string(&SerializableKripkeStructure::from_kripke_structure(&kripke)).unwrap();
+    // ***
+    let deserialized_net : PetriNet = serde_json::from_str(&net_json).unwrap();
+    let serializable_kripke : SerializableKripkeStructure = serde_json::from_str(&kripke_json).unwrap();
+    let deserialized_kripke = serializable_kripke.to_kripke_structure_for_net(&deserialized_net).unwrap();
+    assert_eq!(deserialized_kripke.states.len(), kripke.states.len());
+    let net_lock_label = deserialized_net.get_transitions()[0].transition_label.clone().unwrap();
+    let mut num_tagged_states = 0;
+    for (state,deserialized_state) in kripke.states.iter().zip(deserialized_kripke.states.iter()) {
+        assert_eq!(state.value_in_domain, deserialized_state.value_in_domain);
+        assert_eq!(state.outgoing_transitions_targets, deserialized_state.outgoing_transitions_targets);
+        if let Some(label) = &deserialized_state.value_in_domain.previous_tagged_transition_label {
+            assert!(Arc::ptr_eq(label, &net_lock_label));
+            num_tagged_states += 1;
+        }
+    }
+    assert_eq!(num_tagged_states, 2);
+    // the deserialized structures can be model-checked
+    let ctl_parser = BuiltinPetriCtlParser::from_net(&deserialized_net).unwrap();
+    let (_,phi) = ctl_parser.parse_ctl_formula::<nom::error::Error<&str>>(
+        r#"A(G( (is-previous("lock")) => (tokens-count("CTL")=0) ))"#
+    ).unwrap();
+    assert!(is_ctl_formula_sat(&deserialized_kripke, &hash_set! {0}, &phi));
+    // without the net, the labels are shared among the states
+    let standalone_kripke = serializable_kripke.to_kripke_structure().unwrap();
+    let tagged_labels : Vec<Arc<PetriTransitionLabel>> = standalone_kripke.states.iter()
+        .filter_map(|state| state.value_in_domain.previous_tagged_transition_label.clone())
+        .collect();
+    assert!(Arc::ptr_eq(&tagged_labels[0], &tagged_labels[1]));
+}
+
+
+#[test]
+pub fn test_pnml_content_round_trip() {
+    let content = read_petri_net_from_pnml_file_path("tests/pnml/read_arcs.pnml").unwrap();
+    let json = serde_json::to_string(&content).unwrap();
+    let deserialized : PnmlFileContent = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.petri_net, content.petri_net);
+    assert_eq!(deserialized.initial_marking, content.initial_marking);
+    assert_eq!(deserialized.place_text_id_to_stable_id, content.place_text_id_to_stable_id);
+}
+
+
+#[test]
+pub fn test_empty_firing_interval_is_rejected() {
+    let interval = PetriFiringInterval::try_new(2, Some(5)).unwrap();
+    let json = serde_json::to_string(&interval).unwrap();
+    assert_eq!(serde_json::from_str::<PetriFiringInterval>(&json).unwrap(), interval);
+    assert!(serde_json::from_str::<PetriFiringInterval>(r#"{"earliest":5,"latest":2}"#).is_err());
+}
+
+
+#[test]
+pub fn test_inconsistent_net_is_rejected() {
+    let pn = PetriNet::new(
+        vec![Some(Arc::new(PetriStateLabel::new("P".to_string()))), None],
+        vec![PetriTransition::new(None, hash_map! {0=>1}, hash_map! {1=>1})]
+    ).with_place_capacities(hash_map! {1=>2});
+    let value = serde_json::to_value(&pn).unwrap();
+    assert_eq!(serde_json::from_value::<PetriNet>(value.clone()).unwrap(), pn);
+    let tampered_cases : Vec<(&str,serde_json::Value,PetriSerializationError)> = vec![
+        ("/transitions/0/1/read_tokens", serde_json::json!({"5":1}), PetriSerializationError::UnknownPlaceIndex(5)),
+        ("/transitions/0/1/reset_places", serde_json::json!([7]), PetriSerializationError::UnknownPlaceIndex(7)),
+        ("/place_capacities", serde_json::json!({"9":1}), PetriSerializationError::UnknownPlaceIndex(9)),
+        ("/place_stable_ids/stable_ids", serde_json::json!([1,0]), PetriSerializationError::InconsistentPlaceStableIds),
+        ("/place_stable_ids/stable_ids", serde_json::json!([0]), PetriSerializationError::InconsistentPlaceStableIds),
+        ("/transition_stable_ids/stable_ids", serde_json::json!([0,1]), PetriSerializationError::InconsistentTransitionStableIds),
+    ];
+    for (pointer,tampered_value,expected_error) in tampered_cases {
+        let mut tampered = value.clone();
+        *tampered.pointer_mut(pointer).unwrap() = tampered_value;
+        let error = serde_json::from_value::<PetriNet>(tampered).err().unwrap();
+        assert_eq!(error.to_string(), expected_error.to_string(), "{}", pointer);
+    }
+}
@@ -0,0 +1,138 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use petricheck::{model::{label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::to_kripke::{petri_to_kripke, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, structural::siphons::{compute_maximal_traps_of_minimal_siphons, compute_minimal_siphons, find_siphons_without_marked_trap, get_maximal_siphon, get_maximal_trap}};
+use map_macro::{btree_map, btree_set, hash_map, hash_set};
+
+
+
+
+/// two processes which take two forks in opposite orders
+fn crossed_forks_net() -> PetriNet {
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("F1".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("F2".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_IDLE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_HAS_F1".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_IDLE".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_HAS_F2".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {2=>1,0=>1},
+                hash_map! {3=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {3=>1,1=>1},
+                hash_map! {2=>1,0=>1,1=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {4=>1,1=>1},
+                hash_map! {5=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {5=>1,0=>1},
+                hash_map! {4=>1,0=>1,1=>1}
+            ),
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_siphons_lock_unlock() {
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            )
+        ]
+    );
+    assert_eq!(
+        compute_minimal_siphons(&pn),
+        vec![btree_set! {0,1}, btree_set! {1,2,4}, btree_set! {3,4}]
+    );
+    // in this net, the minimal siphons are also traps
+    for (siphon,trap) in compute_maximal_traps_of_minimal_siphons(&pn) {
+        assert_eq!(siphon, trap);
+    }
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    assert_eq!(find_siphons_without_marked_trap(&pn, &im), vec![]);
+    // without the lock, the siphon which contains it is never marked
+    let im = Marking::new(btree_map! {0=>1,3=>1});
+    let unprotected = find_siphons_without_marked_trap(&pn, &im);
+    assert_eq!(unprotected.len(), 1);
+    assert_eq!(unprotected[0].siphon, btree_set! {1,2,4});
+}
+
+
+#[test]
+pub fn test_siphons_crossed_forks() {
+    let pn = crossed_forks_net();
+    let all_places : BTreeSet<usize> = (0..6).collect();
+    assert_eq!(get_maximal_siphon(&pn, &all_places), all_places);
+    assert_eq!(get_maximal_trap(&pn, &btree_set! {0,1}), btree_set! {});
+    let im = Marking::new(btree_map! {0=>1,1=>1,2=>1,4=>1});
+    assert_eq!(
+        compute_minimal_siphons(&pn),
+        vec![btree_set! {0,1}, btree_set! {0,3}, btree_set! {1,5}, btree_set! {2,3}, btree_set! {4,5}]
+    );
+    let unprotected = find_siphons_without_marked_trap(&pn, &im);
+    assert_eq!(unprotected.len(), 1);
+    assert_eq!(unprotected[0].siphon, btree_set! {0,1});
+    assert_eq!(unprotected[0].maximal_trap, btree_set! {});
+    // the forks may be emptied, which is indeed a deadlock
+    let kripke = petri_to_kripke(
+        &pn,
+        im,
+        &PetriKripkeStateProducer::new(hash_set! {}),
+        &PetriKripkeGenerationSafenessRequirement::No
+    ).unwrap();
+    assert!(kripke.states.iter().any(|state| state.outgoing_transitions_targets.is_empty()));
+}
@@ -0,0 +1,181 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{collections::HashMap, sync::Arc};
+
+use petricheck::{model::{interval::{PetriFiringInterval, PetriFiringIntervalError}, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::{to_kripke::{PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}, to_state_class_graph::petri_to_state_class_graph}, util::{parse_ctl::parser::BuiltinPetriCtlParser, vizualisation::{kripke_viz::PetriKripkeVisualizer, petri_viz::petri_repr}}};
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+use map_macro::{btree_map, hash_map, hash_set};
+
+use citreelo::util::viz_kripke::KripkeStructureGraphvizDrawer;
+
+use citreelo::solve::is_ctl_formula_sat;
+use citreelo::parser::CtlFormulaParser;
+
+
+
+
+
+
+fn tool_test_tpn_mc(
+    title : &str,
+    pn : PetriNet,
+    im : Marking,
+    expected_num_classes : usize,
+    formulaes : HashMap<&'static str, bool>
+) {
+    let folder_name = "test_state_classes";
+    let _ = std::fs::create_dir(folder_name);
+    {
+        let gv = petri_repr(&pn,&Some(im.clone()));
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_1initial", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    let ctl_parser = BuiltinPetriCtlParser::from_net(&pn).unwrap();
+    let kripke = petri_to_state_class_graph(
+        &pn,
+        im,
+        &PetriKripkeStateProducer::new(hash_set! {}),
+        &PetriKripkeGenerationSafenessRequirement::KSafeness(1)
+    ).unwrap();
+    {
+        let gv = PetriKripkeVisualizer::new(&pn).get_kripke_repr(&kripke);
+        gv.print_dot(
+            &[".".to_string()],
+            &format!("{}/{}_2classes", folder_name, title),
+            &GraphVizOutputFormat::png
+        ).unwrap();
+    }
+    assert_eq!(kripke.states.len(), expected_num_classes, "{:} : number of state classes", title);
+    for (phi_as_str,is_sat) in formulaes {
+        let (_,phi) = ctl_parser.parse_ctl_formula::<nom::error::Error<&str>>(
+            phi_as_str
+        ).unwrap();
+        let result = is_ctl_formula_sat(
+            &kripke,
+            &hash_set! {0},
+            &phi
+        );
+        assert_eq!(result,is_sat,"{:} : {} -> {} | expected {}", title, phi_as_str, result, is_sat);
+    }
+}
+
+
+fn timeout_net(reply_interval : PetriFiringInterval, timeout_interval : PetriFiringInterval) -> PetriNet {
+    let reply_tr = Arc::new(PetriTransitionLabel::new("reply".to_string()));
+    let timeout_tr = Arc::new(PetriTransitionLabel::new("timeout".to_string()));
+    PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("WAIT".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("REPLIED".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("TIMEDOUT".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(reply_tr),
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ).with_firing_interval(reply_interval),
+            PetriTransition::new(
+                Some(timeout_tr),
+                hash_map! {0=>1},
+                hash_map! {2=>1}
+            ).with_firing_interval(timeout_interval)
+        ]
+    )
+}
+
+
+#[test]
+pub fn test_timeout_may_occur() {
+    let pn = timeout_net(
+        PetriFiringInterval::try_new(3, Some(5)).unwrap(),
+        PetriFiringInterval::try_new(4, Some(4)).unwrap()
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let formulaes = hash_map! {
+        r#"E(F( tokens-count("REPLIED")=1 ))"#  => true,
+        r#"E(F( tokens-count("TIMEDOUT")=1 ))"# => true,
+    };
+    tool_test_tpn_mc("timeout_may_occur", pn, im, 3, formulaes);
+}
+
+
+#[test]
+pub fn test_timeout_always_occurs() {
+    let pn = timeout_net(
+        PetriFiringInterval::try_new(3, Some(5)).unwrap(),
+        PetriFiringInterval::try_new(2, Some(2)).unwrap()
+    );
+    let im = Marking::new(btree_map! {0=>1});
+    let formulaes = hash_map! {
+        r#"E(F( tokens-count("REPLIED")=1 ))"#  => false,
+        r#"A(F( tokens-count("TIMEDOUT")=1 ))"# => true,
+    };
+    tool_test_tpn_mc("timeout_always_occurs", pn, im, 2, formulaes);
+}
+
+
+#[test]
+pub fn test_persistent_transitions() {
+    // three concurrent transitions which, once enabled, fire at times 1, 3 and 2 respectively
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("C".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("D".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("E".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("F".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {0=>1},
+                hash_map! {1=>1}
+            ).with_firing_interval(PetriFiringInterval::try_new(1, Some(1)).unwrap()),
+            PetriTransition::new(
+                None,
+                hash_map! {2=>1},
+                hash_map! {3=>1}
+            ).with_firing_interval(PetriFiringInterval::try_new(3, Some(3)).unwrap()),
+            PetriTransition::new(
+                None,
+                hash_map! {4=>1},
+                hash_map! {5=>1}
+            ).with_firing_interval(PetriFiringInterval::try_new(2, Some(2)).unwrap()),
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,2=>1,4=>1});
+    let formulaes = hash_map! {
+        r#"A(G( (tokens-count("F")>0) => (tokens-count("B")>0) ))"# => true,
+        r#"A(G( (tokens-count("D")>0) => (tokens-count("F")>0) ))"# => true,
+        r#"A(F( tokens-count("D")=1 ))"#                            => true,
+    };
+    tool_test_tpn_mc("persistent_transitions", pn, im, 4, formulaes);
+}
+
+
+#[test]
+pub fn test_inverted_interval_is_rejected() {
+    assert_eq!(
+        PetriFiringInterval::try_new(5, Some(2)),
+        Err(PetriFiringIntervalError::EmptyInterval { earliest: 5, latest: 2 })
+    );
+}
@@ -0,0 +1,179 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::{collections::HashMap, sync::Arc};
+
+use petricheck::{model::{incidence::PetriIncidenceMatrices, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::props::{BuiltinPetriAtomicProposition, TokensCountAtom, TokensCountRelation}, structural::{rational::PetriRational, state_equation::{check_state_equation, PetriStateEquationVerdict}}};
+use map_macro::{btree_map, hash_map, hash_set};
+
+
+
+
+fn tokens_count(place_id : usize, relation : TokensCountRelation, value : u32) -> BuiltinPetriAtomicProposition {
+    BuiltinPetriAtomicProposition::TokensCount(
+        relation,
+        TokensCountAtom::NumberOfTokensInPlace(place_id),
+        TokensCountAtom::RawInteger(value)
+    )
+}
+
+
+#[test]
+pub fn test_state_equation_mutual_exclusion() {
+    let lock_tr = Arc::new(PetriTransitionLabel::new("lock".to_string()));
+    let unlock_tr = Arc::new(PetriTransitionLabel::new("unlock".to_string()));
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("A_L".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("CTL".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_U".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B_L".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {0=>1,2=>1},
+                hash_map! {1=>1}
+            ),
+            PetriTransition::new(
+                Some(lock_tr.clone()),
+                hash_map! {3=>1,2=>1},
+                hash_map! {4=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {1=>1},
+                hash_map! {0=>1,2=>1}
+            ),
+            PetriTransition::new(
+                Some(unlock_tr.clone()),
+                hash_map! {4=>1},
+                hash_map! {3=>1,2=>1}
+            )
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,2=>1,3=>1});
+    // both actors holding the lock
+    let verdict = check_state_equation(
+        &pn,
+        &im,
+        &[tokens_count(1, TokensCountRelation::Equal, 1), tokens_count(4, TokensCountRelation::StrictlyGreater, 0)]
+    );
+    assert_eq!(verdict, PetriStateEquationVerdict::ProvablyUnreachable);
+    // the lock being held while no actor holds it
+    let no_holder = BuiltinPetriAtomicProposition::TokensCount(
+        TokensCountRelation::StrictlyLower,
+        TokensCountAtom::NumberOfTokensInPlace(2),
+        TokensCountAtom::RawInteger(1)
+    );
+    let verdict = check_state_equation(
+        &pn,
+        &im,
+        &[no_holder, tokens_count(1, TokensCountRelation::LowerOrEqual, 0), tokens_count(4, TokensCountRelation::LowerOrEqual, 0)]
+    );
+    assert_eq!(verdict, PetriStateEquationVerdict::ProvablyUnreachable);
+    // actor A holding the lock is reachable, and the candidate leads there
+    let verdict = check_state_equation(&pn, &im, &[tokens_count(1, TokensCountRelation::Equal, 1)]);
+    let parikh_vector : HashMap<usize,u32> = match verdict {
+        PetriStateEquationVerdict::Inconclusive { parikh_vector } => {
+            parikh_vector.into_iter()
+                .map(|(transition_id,value)| {
+                    assert!(value.is_integer());
+                    (transition_id, value.get_numerator() as u32)
+                })
+                .collect()
+        },
+        PetriStateEquationVerdict::ProvablyUnreachable => {
+            panic!("reachable marking declared unreachable");
+        }
+    };
+    let reached = PetriIncidenceMatrices::from_net(&pn).apply_state_equation(&im, &parikh_vector).unwrap();
+    assert_eq!(reached.get_num_toks_at_place(&1), Some(&1));
+}
+
+
+#[test]
+pub fn test_state_equation_integrality_and_resets() {
+    // tokens are consumed by pairs so an odd number of them cannot be reached from an even one
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {0=>2},
+                hash_map! {1=>1}
+            ),
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>4});
+    let verdict = check_state_equation(&pn, &im, &[tokens_count(0, TokensCountRelation::Equal, 1)]);
+    assert_eq!(verdict, PetriStateEquationVerdict::ProvablyUnreachable);
+    let verdict = check_state_equation(&pn, &im, &[tokens_count(1, TokensCountRelation::Equal, 2)]);
+    assert_eq!(verdict, PetriStateEquationVerdict::Inconclusive { parikh_vector : btree_map! {0=>PetriRational::from_integer(2)} });
+    // a reset arc may empty a place in which tokens only accumulate otherwise
+    let pn = PetriNet::new(
+        vec![
+            Some(Arc::new(PetriStateLabel::new("A".to_string()))),
+            Some(Arc::new(PetriStateLabel::new("B".to_string()))),
+        ],
+        vec![
+            PetriTransition::new(
+                None,
+                hash_map! {0=>1},
+                hash_map! {0=>1,1=>1}
+            ),
+            PetriTransition::new(
+                None,
+                hash_map! {},
+                hash_map! {}
+            ).with_reset_places(hash_set! {1}),
+        ]
+    );
+    let im = Marking::new(btree_map! {0=>1,1=>5});
+    let verdict = check_state_equation(&pn, &im, &[tokens_count(1, TokensCountRelation::Equal, 0)]);
+    assert_ne!(verdict, PetriStateEquationVerdict::ProvablyUnreachable);
+    // and a capacity bounds the number of tokens of a place
+    let pn = pn.with_place_capacities(hash_map! {1=>6});
+    let verdict = check_state_equation(&pn, &im, &[tokens_count(1, TokensCountRelation::GreaterOrEqual, 7)]);
+    assert_eq!(verdict, PetriStateEquationVerdict::ProvablyUnreachable);
+}
+
+
+#[test]
+pub fn test_state_equation_overflow_is_inconclusive() {
+    // the numbers of firings along a chain of transitions with large coprime weights
+    // have denominators which do not fit in an i128
+    let (big_pre, big_post) = (4294967291, 4294967279);
+    let num_places = 8;
+    let pn = PetriNet::new(
+        (0..num_places).map(|_| None).collect(),
+        (0..num_places - 1).map(|place_id| PetriTransition::new(None, hash_map! {place_id=>big_pre}, hash_map! {place_id + 1=>big_post})).collect()
+    );
+    let im = Marking::new(btree_map! {0=>u32::MAX});
+    let verdict = check_state_equation(&pn, &im, &[tokens_count(num_places - 1, TokensCountRelation::Equal, 1)]);
+    assert_eq!(verdict, PetriStateEquationVerdict::Inconclusive { parikh_vector : btree_map! {} });
+    // checked operations report overflows, and comparisons do not overflow
+    let max = PetriRational::from_integer(i128::MAX);
+    assert_eq!(max.checked_add(PetriRational::from_integer(1)), None);
+    assert_eq!(max.checked_mul(PetriRational::from_integer(2)), None);
+    assert_eq!(max.checked_div(PetriRational::new(1, i128::MAX - 1)), None);
+    assert!(PetriRational::new(i128::MAX, 3) > PetriRational::new(i128::MAX - 1, 3));
+    assert!(PetriRational::new(i128::MAX - 1, i128::MAX) > PetriRational::new(i128::MAX - 2, i128::MAX - 1));
+}
@@ -0,0 +1,110 @@
+/*
+Copyright 2025 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use citreelo::kripke::KripkeStructure;
+use citreelo::parser::CtlFormulaParser;
+use citreelo::solve::is_ctl_formula_sat;
+use map_macro::{btree_map, hash_map, hash_set};
+use petricheck::{colored::{net::{ColoredMarking, ColoredPetriNet}, unfold::ColoredUnfolding}, model::{builder::PetriNetBuilder, label::{PetriStateLabel, PetriTransitionLabel}, marking::Marking, net::PetriNet, transition::PetriTransition}, model_checking::{props::BuiltinPetriAtomicProposition, state::PetriKripkeState, state_class::PetriStateClass, to_kripke::{petri_to_kripke, PetriKripkeGenerationSafenessRequirement, PetriKripkeStateProducer}}, performance::ctmc::PetriCtmc, util::{parse_ctl::parser::BuiltinPetriCtlParser, parse_pnml::build::PnmlFileContent}};
+
+
+
+
+fn assert_send_sync<T : Send + Sync>() {}
+
+
+#[test]
+pub fn test_public_types_are_send_and_sync() {
+    assert_send_sync::<PetriStateLabel>();
+    assert_send_sync::<PetriTransitionLabel>();
+    assert_send_sync::<PetriNet>();
+    assert_send_sync::<PetriTransition>();
+    assert_send_sync::<Marking>();
+    assert_send_sync::<PetriKripkeState>();
+    assert_send_sync::<PetriStateClass>();
+    assert_send_sync::<BuiltinPetriAtomicProposition>();
+    assert_send_sync::<KripkeStructure<PetriKripkeState>>();
+    assert_send_sync::<BuiltinPetriCtlParser>();
+    assert_send_sync::<PnmlFileContent>();
+    assert_send_sync::<ColoredPetriNet>();
+    assert_send_sync::<ColoredMarking>();
+    assert_send_sync::<ColoredUnfolding>();
+    assert_send_sync::<PetriCtmc>();
+}
+
+
+#[test]
+pub fn test_parallel_model_checking() {
+    let (pn,im) = PetriNetBuilder::new()
+        .add_place("A_U")
+        .add_place("A_L")
+        .add_place("CTL")
+        .add_place("B_U")
+        .add_place("B_L")
+        .add_labelled_transition("A_lock", Some("lock"))
+        .add_labelled_transition("B_lock", Some("lock"))
+        .add_labelled_transition("A_unlock", Some("unlock"))
+        .add_labelled_transition("B_unlock", Some("unlock"))
+        .add_arc("A_U", "A_lock", 1)
+        .add_arc("CTL", "A_lock", 1)
+        .add_arc("A_lock", "A_L", 1)
+        .add_arc("B_U", "B_lock", 1)
+        .add_arc("CTL", "B_lock", 1)
+        .add_arc("B_lock", "B_L", 1)
+        .add_arc("A_L", "A_unlock", 1)
+        .add_arc("A_unlock", "A_U", 1)
+        .add_arc("A_unlock", "CTL", 1)
+        .add_arc("B_L", "B_unlock", 1)
+        .add_arc("B_unlock", "B_U", 1)
+        .add_arc("B_unlock", "CTL", 1)
+        .set_initial_tokens("A_U", 1)
+        .set_initial_tokens("CTL", 1)
+        .set_initial_tokens("B_U", 1)
+        .build()
+        .unwrap();
+    assert_eq!(im, Marking::new(btree_map! {0=>1,2=>1,3=>1}));
+    // the net, the parser and the Kripke structure are shared between the threads
+    let ctl_parser = Arc::new(BuiltinPetriCtlParser::from_net(&pn).unwrap());
+    let kripke = Arc::new(
+        petri_to_kripke(
+            &pn,
+            im,
+            &PetriKripkeStateProducer::new(hash_set! {}),
+            &PetriKripkeGenerationSafenessRequirement::KSafeness(1)
+        ).unwrap()
+    );
+    let formulaes = hash_map! {
+        r#"A(G(!((tokens-count("A_L")>0)&(tokens-count("B_L")>0))))"# => true,
+        r#"A(G(is-fireable("lock")))"#                                 => false,
+        r#"A(G(E(F(tokens-count("B_L")=1))))"#                         => true,
+    };
+    std::thread::scope(|scope| {
+        let handles : Vec<_> = formulaes.into_iter().map(|(phi_as_str,is_sat)| {
+            let ctl_parser = ctl_parser.clone();
+            let kripke = kripke.clone();
+            scope.spawn(move || {
+                let (_,phi) = ctl_parser.parse_ctl_formula::<nom::error::Error<&str>>(phi_as_str).unwrap();
+                let result = is_ctl_formula_sat(&kripke, &hash_set! {0}, &phi);
+                assert_eq!(result, is_sat, "{} -> {} | expected {}", phi_as_str, result, is_sat);
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    });
+}